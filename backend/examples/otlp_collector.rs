@@ -0,0 +1,98 @@
+//! Stand-in for an OpenTelemetry collector, for checking exported spans locally
+//!
+//! Listens on `OTLP_COLLECTOR_ADDR` (default `127.0.0.1:4318`) for OTLP/HTTP
+//! trace exports in JSON and prints one line per span.
+//!
+//! ```sh
+//! cargo run -p backend --example otlp_collector
+//! OTEL_EXPORTER_OTLP_ENDPOINT=http://127.0.0.1:4318 OTEL_EXPORTER_OTLP_PROTOCOL=http/json cargo run -p backend
+//! ```
+//!
+//! Spans of one request share a trace id; the `request` span carries the
+//! `request_id` returned in the `X-Request-Id` header.
+
+use axum::{extract::rejection::JsonRejection, http::StatusCode, routing::post, Json, Router};
+use serde_json::{json, Value};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let addr =
+        std::env::var("OTLP_COLLECTOR_ADDR").unwrap_or_else(|_| "127.0.0.1:4318".to_string());
+
+    let app = Router::new().route("/v1/traces", post(receive));
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    println!("OTLP collector listening on http://{}/v1/traces", addr);
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+/// Accepts an export request, only JSON bodies are understood
+async fn receive(body: Result<Json<Value>, JsonRejection>) -> (StatusCode, Json<Value>) {
+    let Ok(Json(request)) = body else {
+        println!("Rejected export: not JSON, set OTEL_EXPORTER_OTLP_PROTOCOL=http/json");
+        return (StatusCode::UNSUPPORTED_MEDIA_TYPE, Json(json!({})));
+    };
+
+    let spans = request["resourceSpans"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|resource| resource["scopeSpans"].as_array().into_iter().flatten())
+        .flat_map(|scope| scope["spans"].as_array().into_iter().flatten());
+
+    for span in spans {
+        let duration_ms = (nanos(&span["endTimeUnixNano"]) - nanos(&span["startTimeUnixNano"]))
+            as f64
+            / 1_000_000.0;
+        println!(
+            "trace={} span={} parent={} {} {:.1}ms {}",
+            span["traceId"].as_str().unwrap_or_default(),
+            span["spanId"].as_str().unwrap_or_default(),
+            span["parentSpanId"]
+                .as_str()
+                .filter(|id| !id.is_empty())
+                .unwrap_or("-"),
+            span["name"].as_str().unwrap_or_default(),
+            duration_ms,
+            attributes(&span["attributes"]),
+        );
+    }
+
+    (StatusCode::OK, Json(json!({})))
+}
+
+/// Timestamps are encoded as strings or numbers depending on the exporter
+fn nanos(value: &Value) -> i128 {
+    match value {
+        Value::String(nanos) => nanos.parse().unwrap_or_default(),
+        Value::Number(nanos) => nanos.as_u64().unwrap_or_default().into(),
+        _ => 0,
+    }
+}
+
+/// Attributes written by the backend itself, e.g. `request_id=... operation=...`
+fn attributes(attributes: &Value) -> String {
+    attributes
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|attribute| {
+            let key = attribute["key"].as_str()?;
+            if key.starts_with("code.")
+                || key.starts_with("thread.")
+                || key == "busy_ns"
+                || key == "idle_ns"
+                || key == "target"
+            {
+                return None;
+            }
+            let value = &attribute["value"];
+            let value = value["stringValue"]
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| value.to_string());
+            Some(format!("{}={}", key, value))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,105 @@
+//! Local receiver for testing webhook deliveries
+//!
+//! Listens on `WEBHOOK_RECEIVER_ADDR` (default `127.0.0.1:9090`), checks the
+//! signature of every delivery with `WEBHOOK_SECRET` and prints the event.
+//! Set `WEBHOOK_RECEIVER_FAIL=true` to answer with `500` and watch the
+//! backend retry and eventually dead-letter the deliveries.
+//!
+//! ```sh
+//! WEBHOOK_SECRET=whsec_... cargo run -p backend --example webhook_receiver
+//! ```
+//!
+//! Register `http://127.0.0.1:9090/` with `POST /api/admin/webhooks`.
+
+use axum::{body::Bytes, http::HeaderMap, http::StatusCode, routing::post, Router};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Oldest signature timestamp accepted, against replayed requests
+const MAX_SIGNATURE_AGE_SECONDS: i64 = 300;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let addr =
+        std::env::var("WEBHOOK_RECEIVER_ADDR").unwrap_or_else(|_| "127.0.0.1:9090".to_string());
+
+    let app = Router::new().route("/", post(receive));
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    println!("Webhook receiver listening on http://{}/", addr);
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn receive(headers: HeaderMap, body: Bytes) -> StatusCode {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let event = header("X-RoyalPizza-Event");
+    let delivery = header("X-RoyalPizza-Delivery");
+    let payload = String::from_utf8_lossy(&body);
+
+    let verified = match std::env::var("WEBHOOK_SECRET") {
+        Ok(secret) => verify(&secret, &header("X-RoyalPizza-Signature"), &payload),
+        Err(_) => {
+            println!("WEBHOOK_SECRET is not set, signature not checked");
+            true
+        }
+    };
+    if !verified {
+        println!("Rejected delivery {}: invalid signature", delivery);
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    println!("{} ({})\n{}\n", event, delivery, payload);
+
+    if std::env::var("WEBHOOK_RECEIVER_FAIL").as_deref() == Ok("true") {
+        StatusCode::INTERNAL_SERVER_ERROR
+    } else {
+        StatusCode::NO_CONTENT
+    }
+}
+
+/// Checks a `t=<unix time>,v1=<hex HMAC-SHA256 of "<t>.<payload>">` signature
+fn verify(secret: &str, signature: &str, payload: &str) -> bool {
+    let mut timestamp = None;
+    let mut expected = None;
+    for part in signature.split(',') {
+        match part.split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => expected = Some(value),
+            _ => {}
+        }
+    }
+    let (Some(timestamp), Some(expected)) = (timestamp, expected) else {
+        return false;
+    };
+
+    let age = chrono::Utc::now().timestamp() - timestamp;
+    if age.abs() > MAX_SIGNATURE_AGE_SECONDS {
+        return false;
+    }
+
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.", timestamp).as_bytes());
+    mac.update(payload.as_bytes());
+    // Constant time comparison of the decoded signature
+    match decode_hex(expected) {
+        Some(expected) => mac.verify_slice(&expected).is_ok(),
+        None => false,
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
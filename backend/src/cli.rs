@@ -0,0 +1,86 @@
+use crate::config::{Config, DatabaseEngine};
+use crate::middleware::actor::act_as;
+use crate::repository::db::Database;
+use crate::repository::{health_repo, seed};
+use crate::services::backup_service::{self, BackupArchive};
+use crate::services::pizza_service::MenuCache;
+use shared::dto::RestoreConflictPolicy;
+use shared::models::{Actor, ActorKind};
+use std::path::PathBuf;
+
+const USAGE: &str = "usage:
+  backend                                   start the server
+  backend backup [DIR]                      write a backup archive to DIR, BACKUP_DIR or .
+  backend restore FILE [--on-conflict P]    restore an archive; P is fail (default), skip or overwrite";
+
+/// Runs a command given on the command line instead of the server
+pub async fn run(args: &[String], config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    match args {
+        [command, dir @ ..] if command == "backup" && dir.len() <= 1 => {
+            let dir = dir
+                .first()
+                .map(PathBuf::from)
+                .or_else(|| config.backup.dir.clone())
+                .unwrap_or_else(|| PathBuf::from("."));
+
+            let db = Database::connect(&config.database).await?;
+            let path = backup_service::write_backup(&db.client(), &dir)
+                .await
+                .map_err(|e| format!("Backup failed: {:?}", e))?;
+            println!("Wrote {}", path.display());
+            Ok(())
+        }
+        [command, file, options @ ..] if command == "restore" => {
+            let policy = match options {
+                [] => RestoreConflictPolicy::Fail,
+                [flag, policy] if flag == "--on-conflict" => parse_policy(policy)?,
+                _ => return Err(USAGE.into()),
+            };
+            if config.database.engine == DatabaseEngine::Memory {
+                return Err("A mem:// database ends with this command, restore through \
+                            POST /api/admin/restore instead"
+                    .into());
+            }
+
+            let archive: BackupArchive = serde_json::from_slice(&tokio::fs::read(file).await?)
+                .map_err(|e| format!("{} is not a backup archive: {}", file, e))?;
+
+            let db = Database::connect(&config.database).await?;
+            let db = db.client();
+            // An empty database gets the schema first, without the seed menu
+            if health_repo::query_schema_version(&db).await?.is_none() {
+                seed::init_schema_only(&db).await?;
+            }
+
+            let cli = Actor::named(ActorKind::System, "cli");
+            // A running server has its own cache, which expires on its own
+            let menu_cache = MenuCache::default();
+            let restored = act_as(
+                cli,
+                backup_service::restore_backup(&db, &menu_cache, archive, policy),
+            )
+            .await
+            .map_err(|e| format!("Restore failed: {:?}", e))?;
+            for table in restored.tables {
+                println!(
+                    "{}: {} restored, {} skipped, {} replaced",
+                    table.table, table.restored, table.skipped, table.replaced
+                );
+            }
+            Ok(())
+        }
+        _ => Err(USAGE.into()),
+    }
+}
+
+fn parse_policy(policy: &str) -> Result<RestoreConflictPolicy, String> {
+    match policy {
+        "fail" => Ok(RestoreConflictPolicy::Fail),
+        "skip" => Ok(RestoreConflictPolicy::Skip),
+        "overwrite" => Ok(RestoreConflictPolicy::Overwrite),
+        other => Err(format!(
+            "Invalid --on-conflict value: {} (expected fail, skip or overwrite)",
+            other
+        )),
+    }
+}
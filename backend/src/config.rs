@@ -1,13 +1,32 @@
+use crate::images::ImageStore;
+use crate::notification::Notifiers;
+use crate::payment::mock::MockOutcome;
+use crate::payment::PaymentProvider;
+use crate::repository::db::Database;
+use crate::services::health_service::Workers;
+use crate::services::pizza_service::MenuCache;
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use metrics_exporter_prometheus::PrometheusHandle;
 use serde::Deserialize;
+use shared::models::TaxCategory;
+use shared::validation::{constants::DEFAULT_PHONE_REGION, PhoneRegion};
+use std::path::PathBuf;
 use std::sync::Arc;
-use surrealdb::engine::remote::ws::Client;
-use surrealdb::Surreal;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     pub server: ServerConfig,
+    pub shop: ShopConfig,
     pub database: DatabaseConfig,
+    pub tax: TaxConfig,
+    pub payment: PaymentConfig,
+    pub admin: AdminConfig,
+    pub phone: PhoneConfig,
+    pub notification: NotificationConfig,
+    pub backup: BackupConfig,
+    pub images: ImageConfig,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -17,28 +36,204 @@ pub struct ServerConfig {
     pub cors_allow_origin: String,
 }
 
+/// Where the shop is, for times printed for staff and customers
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShopConfig {
+    /// IANA time zone of the shop, e.g. `Europe/Amsterdam`
+    pub timezone: Tz,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct DatabaseConfig {
+    /// Connection URL with its scheme, e.g. `ws://localhost:8000` or `surrealkv://data/royalpizza`
     pub url: String,
+    pub engine: DatabaseEngine,
     pub namespace: String,
     pub name: String,
     pub username: String,
     pub password: String,
 }
 
+/// Where SurrealDB runs, chosen by the scheme of `DATABASE_URL`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum DatabaseEngine {
+    /// SurrealDB server over WebSocket: `ws://`, `wss://` or a bare `host:port`
+    Remote,
+    /// In the backend process, in memory: `mem://`; data is lost on restart
+    Memory,
+    /// In the backend process, on local disk: `surrealkv://<path>`
+    SurrealKv,
+    /// In the backend process, on local disk: `rocksdb://<path>`
+    RocksDb,
+}
+
+impl DatabaseEngine {
+    /// Whether the database runs inside the backend process
+    pub fn is_embedded(self) -> bool {
+        self != DatabaseEngine::Remote
+    }
+
+    /// Cargo feature the backend must be built with for this engine
+    fn required_feature(self) -> Option<&'static str> {
+        match self {
+            DatabaseEngine::Remote => None,
+            DatabaseEngine::Memory | DatabaseEngine::SurrealKv => {
+                (!cfg!(feature = "embedded")).then_some("embedded")
+            }
+            DatabaseEngine::RocksDb => (!cfg!(feature = "rocksdb")).then_some("rocksdb"),
+        }
+    }
+}
+
+/// Tax rates per product category, as fractions (0.07 = 7%)
+#[derive(Debug, Clone, Deserialize)]
+pub struct TaxConfig {
+    pub takeaway_food_rate: f64,
+    pub drink_rate: f64,
+    /// Whether menu prices already include tax (VAT style) or tax is added on top
+    pub prices_include_tax: bool,
+}
+
+impl TaxConfig {
+    /// Get the tax rate applicable to a product category
+    pub fn rate_for(&self, category: TaxCategory) -> f64 {
+        match category {
+            TaxCategory::TakeawayFood => self.takeaway_food_rate,
+            TaxCategory::Drink => self.drink_rate,
+        }
+    }
+}
+
+/// Behaviour of the mock payment provider
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaymentConfig {
+    pub mock_outcome: MockOutcome,
+    /// How long the mock waits before reporting the outcome
+    pub mock_delay: Duration,
+    /// Shared secret webhook calls are signed with; the webhook rejects all calls without one
+    pub webhook_secret: Option<String>,
+}
+
+/// Access to the administrative endpoints
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdminConfig {
+    /// Bearer token expected on admin requests; admin endpoints are disabled without one
+    pub api_token: Option<String>,
+}
+
+/// How phone numbers entered by customers are read and checked
+#[derive(Debug, Clone, Deserialize)]
+pub struct PhoneConfig {
+    /// ISO code of the region of numbers entered without a country code
+    pub default_region: String,
+    /// Whether orders need a phone number verified with a one-time code
+    pub verification_required: bool,
+}
+
+impl PhoneConfig {
+    pub fn region(&self) -> &'static PhoneRegion {
+        PhoneRegion::find(&self.default_region)
+            .expect("phone region is validated when the configuration is loaded")
+    }
+}
+
+/// Transports used to notify customers about their orders
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotificationConfig {
+    pub sms: SmsTransportConfig,
+    pub email: EmailTransportConfig,
+    /// File the log transport appends messages to; they are only logged without one
+    pub log_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum SmsTransportConfig {
+    Disabled,
+    Log,
+    Http(HttpSmsConfig),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HttpSmsConfig {
+    pub url: String,
+    pub api_key: Option<String>,
+    /// Sender number or name shown to the customer
+    pub sender: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum EmailTransportConfig {
+    Disabled,
+    Log,
+    Smtp(SmtpConfig),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Sender address, optionally with a name: `Royal Pizza <orders@example.com>`
+    pub from: String,
+}
+
+/// Automatic backups of orders and the menu to a local directory
+#[derive(Debug, Clone, Deserialize)]
+pub struct BackupConfig {
+    /// Directory archives are written to; no automatic backups without one
+    pub dir: Option<PathBuf>,
+    pub interval: Duration,
+    /// Number of archives kept, older ones are deleted
+    pub retention: usize,
+}
+
+/// Storage of uploaded pizza images
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageConfig {
+    pub store: ImageStoreConfig,
+    /// URL the stored images are reachable under, written into pizza image URLs
+    pub public_url: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum ImageStoreConfig {
+    /// Files in a local directory, served by the backend under `/api/images`
+    Local { dir: PathBuf },
+}
+
 #[derive(Clone)]
 pub struct AppState {
-    pub db: Arc<Surreal<Client>>,
-    #[allow(dead_code)]
+    pub db: Database,
     pub config: Arc<Config>,
+    pub payments: Arc<dyn PaymentProvider>,
+    pub notifiers: Notifiers,
+    pub images: Arc<dyn ImageStore>,
+    pub metrics: PrometheusHandle,
+    pub workers: Workers,
+    pub menu_cache: MenuCache,
     pub startup_time: DateTime<Utc>,
 }
 
 impl AppState {
-    pub fn new(db: Arc<Surreal<Client>>, config: Arc<Config>) -> Self {
+    pub fn new(
+        db: Database,
+        config: Arc<Config>,
+        payments: Arc<dyn PaymentProvider>,
+        notifiers: Notifiers,
+        images: Arc<dyn ImageStore>,
+        metrics: PrometheusHandle,
+        workers: Workers,
+    ) -> Self {
         Self {
             db,
             config,
+            payments,
+            notifiers,
+            images,
+            metrics,
+            workers,
+            menu_cache: MenuCache::default(),
             startup_time: Utc::now(),
         }
     }
@@ -60,6 +255,7 @@ impl Config {
 
         let database_url = std::env::var("DATABASE_URL")
             .map_err(|_| "DATABASE_URL environment variable is required".to_string())?;
+        let (database_url, database_engine) = parse_database_url(&database_url)?;
         let database_namespace = std::env::var("DATABASE_NAMESPACE")
             .unwrap_or_else(|_| "royalpizza".to_string());
         let database_name =
@@ -69,19 +265,237 @@ impl Config {
         let database_password =
             std::env::var("DATABASE_PASSWORD").unwrap_or_else(|_| "root".to_string());
 
+        let takeaway_food_rate = parse_tax_rate("TAX_RATE_TAKEAWAY_FOOD")?;
+        let drink_rate = parse_tax_rate("TAX_RATE_DRINK")?;
+        let prices_include_tax = std::env::var("TAX_PRICES_INCLUDE_TAX")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse::<bool>()
+            .map_err(|e| format!("Invalid TAX_PRICES_INCLUDE_TAX value: {}", e))?;
+
+        let mock_outcome = std::env::var("PAYMENT_MOCK_OUTCOME")
+            .unwrap_or_else(|_| "succeed".to_string())
+            .parse::<MockOutcome>()
+            .map_err(|e| format!("Invalid PAYMENT_MOCK_OUTCOME value: {}", e))?;
+        let mock_delay_ms = std::env::var("PAYMENT_MOCK_DELAY_MS")
+            .unwrap_or_else(|_| "2000".to_string())
+            .parse::<u64>()
+            .map_err(|e| format!("Invalid PAYMENT_MOCK_DELAY_MS value: {}", e))?;
+        let payment_webhook_secret = std::env::var("PAYMENT_WEBHOOK_SECRET")
+            .ok()
+            .filter(|secret| !secret.trim().is_empty());
+
+        let admin_api_token = std::env::var("ADMIN_API_TOKEN")
+            .ok()
+            .filter(|token| !token.trim().is_empty());
+
+        let shop_timezone = std::env::var("SHOP_TIMEZONE")
+            .unwrap_or_else(|_| "UTC".to_string())
+            .parse::<Tz>()
+            .map_err(|e| format!("Invalid SHOP_TIMEZONE value: {}", e))?;
+
+        let phone_default_region = std::env::var("PHONE_DEFAULT_REGION")
+            .unwrap_or_else(|_| DEFAULT_PHONE_REGION.to_string());
+        let phone_default_region = PhoneRegion::find(&phone_default_region)
+            .ok_or_else(|| {
+                format!(
+                    "Unsupported PHONE_DEFAULT_REGION value: {}",
+                    phone_default_region
+                )
+            })?
+            .code
+            .to_string();
+
+        let phone_verification_required = std::env::var("PHONE_VERIFICATION_REQUIRED")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse::<bool>()
+            .map_err(|e| format!("Invalid PHONE_VERIFICATION_REQUIRED value: {}", e))?;
+
+        // Customer notifications are written to the log unless a transport is configured
+        let sms_transport = match std::env::var("NOTIFY_SMS_TRANSPORT")
+            .unwrap_or_else(|_| "log".to_string())
+            .as_str()
+        {
+            "disabled" => SmsTransportConfig::Disabled,
+            "log" => SmsTransportConfig::Log,
+            "http" => SmsTransportConfig::Http(HttpSmsConfig {
+                url: required_env("SMS_GATEWAY_URL", "http SMS")?,
+                api_key: optional_env("SMS_GATEWAY_API_KEY"),
+                sender: required_env("SMS_SENDER", "http SMS")?,
+            }),
+            other => {
+                return Err(format!(
+                    "Invalid NOTIFY_SMS_TRANSPORT value: {} (expected disabled, log or http)",
+                    other
+                ))
+            }
+        };
+        let email_transport = match std::env::var("NOTIFY_EMAIL_TRANSPORT")
+            .unwrap_or_else(|_| "log".to_string())
+            .as_str()
+        {
+            "disabled" => EmailTransportConfig::Disabled,
+            "log" => EmailTransportConfig::Log,
+            "smtp" => EmailTransportConfig::Smtp(SmtpConfig {
+                host: required_env("SMTP_HOST", "smtp email")?,
+                port: std::env::var("SMTP_PORT")
+                    .unwrap_or_else(|_| "587".to_string())
+                    .parse::<u16>()
+                    .map_err(|e| format!("Invalid SMTP_PORT value: {}", e))?,
+                username: optional_env("SMTP_USERNAME"),
+                password: optional_env("SMTP_PASSWORD"),
+                from: required_env("SMTP_FROM", "smtp email")?,
+            }),
+            other => {
+                return Err(format!(
+                    "Invalid NOTIFY_EMAIL_TRANSPORT value: {} (expected disabled, log or smtp)",
+                    other
+                ))
+            }
+        };
+
+        let backup_interval_hours = std::env::var("BACKUP_INTERVAL_HOURS")
+            .unwrap_or_else(|_| "24".to_string())
+            .parse::<u64>()
+            .ok()
+            .filter(|hours| *hours > 0)
+            .ok_or_else(|| "BACKUP_INTERVAL_HOURS must be a whole number of hours".to_string())?;
+        let backup_retention = std::env::var("BACKUP_RETENTION")
+            .unwrap_or_else(|_| "7".to_string())
+            .parse::<usize>()
+            .ok()
+            .filter(|retention| *retention > 0)
+            .ok_or_else(|| "BACKUP_RETENTION must be at least 1".to_string())?;
+
+        let image_store = match std::env::var("IMAGE_STORE")
+            .unwrap_or_else(|_| "local".to_string())
+            .as_str()
+        {
+            "local" => ImageStoreConfig::Local {
+                dir: PathBuf::from(
+                    optional_env("IMAGE_DIR").unwrap_or_else(|| "data/images".to_string()),
+                ),
+            },
+            other => {
+                return Err(format!(
+                    "Invalid IMAGE_STORE value: {} (expected local)",
+                    other
+                ))
+            }
+        };
+        let image_public_url = optional_env("IMAGE_PUBLIC_URL")
+            .unwrap_or_else(|| "http://localhost:8080/api/images".to_string())
+            .trim_end_matches('/')
+            .to_string();
+
         Ok(Config {
             server: ServerConfig {
                 host,
                 port,
                 cors_allow_origin,
             },
+            shop: ShopConfig {
+                timezone: shop_timezone,
+            },
             database: DatabaseConfig {
                 url: database_url,
+                engine: database_engine,
                 namespace: database_namespace,
                 name: database_name,
                 username: database_username,
                 password: database_password,
             },
+            tax: TaxConfig {
+                takeaway_food_rate,
+                drink_rate,
+                prices_include_tax,
+            },
+            payment: PaymentConfig {
+                mock_outcome,
+                mock_delay: Duration::from_millis(mock_delay_ms),
+                webhook_secret: payment_webhook_secret,
+            },
+            admin: AdminConfig {
+                api_token: admin_api_token,
+            },
+            phone: PhoneConfig {
+                default_region: phone_default_region,
+                verification_required: phone_verification_required,
+            },
+            notification: NotificationConfig {
+                sms: sms_transport,
+                email: email_transport,
+                log_path: optional_env("NOTIFY_LOG_PATH").map(PathBuf::from),
+            },
+            backup: BackupConfig {
+                dir: optional_env("BACKUP_DIR").map(PathBuf::from),
+                interval: Duration::from_secs(backup_interval_hours * 3600),
+                retention: backup_retention,
+            },
+            images: ImageConfig {
+                store: image_store,
+                public_url: image_public_url,
+            },
         })
     }
 }
+
+/// Reads an environment variable, treating an empty value as unset
+fn optional_env(var: &str) -> Option<String> {
+    std::env::var(var)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// Reads a variable the selected transport cannot work without
+fn required_env(var: &str, transport: &str) -> Result<String, String> {
+    optional_env(var).ok_or_else(|| format!("{} is required for the {} transport", var, transport))
+}
+
+/// Picks the database engine from the URL scheme
+///
+/// A URL without a scheme is a SurrealDB server, as before embedded storage
+/// was supported, and gets `ws://` prepended.
+fn parse_database_url(url: &str) -> Result<(String, DatabaseEngine), String> {
+    let url = url.trim();
+    let (url, engine) = match url.split_once("://") {
+        None => (format!("ws://{}", url), DatabaseEngine::Remote),
+        Some(("ws" | "wss", _)) => (url.to_string(), DatabaseEngine::Remote),
+        Some(("mem", _)) => (url.to_string(), DatabaseEngine::Memory),
+        Some(("surrealkv", path)) if !path.is_empty() => {
+            (url.to_string(), DatabaseEngine::SurrealKv)
+        }
+        Some(("rocksdb", path)) if !path.is_empty() => (url.to_string(), DatabaseEngine::RocksDb),
+        Some(("surrealkv" | "rocksdb", _)) => {
+            return Err(format!("DATABASE_URL needs a storage path: {}", url))
+        }
+        Some((scheme, _)) => {
+            return Err(format!(
+                "Unsupported DATABASE_URL scheme: {} (expected ws, wss, mem, surrealkv or rocksdb)",
+                scheme
+            ))
+        }
+    };
+
+    if let Some(feature) = engine.required_feature() {
+        return Err(format!(
+            "DATABASE_URL {} needs the backend built with the `{}` feature",
+            url, feature
+        ));
+    }
+
+    Ok((url, engine))
+}
+
+/// Parses a tax rate fraction from the environment, defaulting to no tax
+fn parse_tax_rate(var: &str) -> Result<f64, String> {
+    let rate = std::env::var(var)
+        .unwrap_or_else(|_| "0".to_string())
+        .parse::<f64>()
+        .map_err(|e| format!("Invalid {} value: {}", var, e))?;
+
+    if !(0.0..1.0).contains(&rate) {
+        return Err(format!("{} must be a fraction between 0 and 1", var));
+    }
+
+    Ok(rate)
+}
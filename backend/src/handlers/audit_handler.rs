@@ -0,0 +1,40 @@
+use crate::config::AppState;
+use crate::middleware::admin_auth::AdminAuth;
+use crate::middleware::error::AppError;
+use crate::repository::audit_repo::AuditFilter;
+use crate::services::audit_service;
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use shared::dto::GetAuditEventsResponse;
+
+#[derive(Debug, Deserialize)]
+pub struct AuditLogQuery {
+    /// Only changes to this kind of record, e.g. `order`
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    /// Start of the time range, inclusive
+    pub from: Option<DateTime<Utc>>,
+    /// End of the time range, exclusive
+    pub to: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+}
+
+pub async fn get_audit_events_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<Json<GetAuditEventsResponse>, AppError> {
+    let filter = AuditFilter {
+        entity_type: query.entity_type,
+        entity_id: query.entity_id,
+        from: query.from,
+        to: query.to,
+    };
+
+    let events = audit_service::query_events(&state.db.client(), filter, query.limit).await?;
+    Ok(Json(events))
+}
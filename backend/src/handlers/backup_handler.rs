@@ -0,0 +1,55 @@
+use crate::config::AppState;
+use crate::middleware::admin_auth::AdminAuth;
+use crate::middleware::error::AppError;
+use crate::services::backup_service::{self, BackupArchive};
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use shared::dto::{RestoreConflictPolicy, RestoreResponse};
+
+/// Largest archive accepted by the restore endpoint
+pub const MAX_ARCHIVE_SIZE: usize = 256 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct RestoreQuery {
+    #[serde(default)]
+    pub on_conflict: RestoreConflictPolicy,
+}
+
+/// Downloads the menu, customers, orders, refunds and webhooks as a JSON archive
+pub async fn create_backup_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let archive = backup_service::create_backup(&state.db.client()).await?;
+
+    Ok((
+        [(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", archive.file_name()),
+        )],
+        Json(archive),
+    )
+        .into_response())
+}
+
+/// Restores an archive from `create_backup_handler`; `409 Conflict` when tables are not empty
+pub async fn restore_backup_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Query(query): Query<RestoreQuery>,
+    Json(archive): Json<BackupArchive>,
+) -> Result<Json<RestoreResponse>, AppError> {
+    let restored = backup_service::restore_backup(
+        &state.db.client(),
+        &state.menu_cache,
+        archive,
+        query.on_conflict,
+    )
+    .await?;
+    Ok(Json(restored))
+}
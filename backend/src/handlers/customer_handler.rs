@@ -0,0 +1,52 @@
+use crate::config::AppState;
+use crate::middleware::admin_auth::AdminAuth;
+use crate::middleware::error::AppError;
+use crate::services::customer_service;
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use shared::dto::{CustomerHistoryResponse, CustomerLookupRequest, GetCustomersResponse};
+
+#[derive(Debug, Deserialize)]
+pub struct CustomerSearchQuery {
+    /// Matches phone number or name
+    pub search: Option<String>,
+}
+
+pub async fn lookup_customer_handler(
+    State(state): State<AppState>,
+    Json(request): Json<CustomerLookupRequest>,
+) -> Result<Json<CustomerHistoryResponse>, AppError> {
+    let history = customer_service::lookup_customer_history(
+        &state.db.client(),
+        state.config.phone.region(),
+        &request,
+    )
+    .await?;
+    Ok(Json(history))
+}
+
+pub async fn search_customers_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Query(query): Query<CustomerSearchQuery>,
+) -> Result<Json<GetCustomersResponse>, AppError> {
+    let customers = customer_service::search_customers(
+        &state.db.client(),
+        state.config.phone.region(),
+        query.search.as_deref(),
+    )
+    .await?;
+    Ok(Json(customers))
+}
+
+pub async fn get_customer_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<CustomerHistoryResponse>, AppError> {
+    let history = customer_service::get_customer_history(&state.db.client(), &id).await?;
+    Ok(Json(history))
+}
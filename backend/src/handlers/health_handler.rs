@@ -1,65 +1,44 @@
-use axum::{extract::State, http::StatusCode, Json};
-use chrono::Utc;
-use serde_json::{json, Value};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
 
 use crate::config::AppState;
+use crate::middleware::admin_auth::is_admin;
+use crate::services::health_service::{self, Liveness, Readiness, ReadinessStatus};
 
-/// Health check endpoint for monitoring and Docker health checks
+/// Liveness probe: the process is up and serving requests
 ///
-/// This endpoint checks:
-/// - Service availability
-/// - Database connectivity
-/// - Returns current timestamp
-///
-/// Used by Docker health checks and monitoring systems
-pub async fn health_check(State(state): State<AppState>) -> (StatusCode, Json<Value>) {
-    // Check database connectivity
-    let db_status = check_database_health(&state).await;
-
-    let overall_status = if db_status.is_connected {
-        "healthy"
-    } else {
-        "unhealthy"
-    };
-
-    let status_code = if db_status.is_connected {
-        StatusCode::OK
-    } else {
-        StatusCode::SERVICE_UNAVAILABLE
-    };
+/// Checks no dependencies, so a database outage does not get the container
+/// restarted. Always `200 OK`.
+pub async fn liveness_check(State(state): State<AppState>) -> Json<Liveness> {
+    Json(health_service::check_liveness(state.uptime_seconds()))
+}
 
-    (
-        status_code,
-        Json(json!({
-            "status": overall_status,
-            "service": "royal-pizza-backend",
-            "version": env!("CARGO_PKG_VERSION"),
-            "services": {
-                "database": db_status.status,
-            },
-            "uptime_seconds": state.uptime_seconds(),
-            "timestamp": Utc::now().to_rfc3339()
-        })),
+/// Readiness probe: the database, schema, menu and background workers
+///
+/// Responds `503 Service Unavailable` when orders cannot be taken. A stalled
+/// worker or an outdated schema is reported as `degraded` with `200 OK`.
+/// Error messages are only included for requests with the admin token.
+///
+/// Also served as `/api/health` for Docker health checks.
+pub async fn readiness_check(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> (StatusCode, Json<Readiness>) {
+    let readiness = health_service::check_readiness(
+        &state.db,
+        &state.workers,
+        state.uptime_seconds(),
+        is_admin(&headers, &state),
     )
-}
+    .await;
 
-/// Database health check structure
-struct DbHealth {
-    is_connected: bool,
-    status: String,
-}
+    let status_code = match readiness.status {
+        ReadinessStatus::Ready | ReadinessStatus::Degraded => StatusCode::OK,
+        ReadinessStatus::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+    };
 
-/// Checks if the database is accessible and responsive
-async fn check_database_health(state: &AppState) -> DbHealth {
-    // Try a simple query to verify database connectivity
-    match state.db.query("SELECT * FROM pizza LIMIT 0").await {
-        Ok(_) => DbHealth {
-            is_connected: true,
-            status: "connected".to_string(),
-        },
-        Err(e) => DbHealth {
-            is_connected: false,
-            status: format!("disconnected: {}", e),
-        },
-    }
+    (status_code, Json(readiness))
 }
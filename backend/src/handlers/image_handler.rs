@@ -0,0 +1,75 @@
+use crate::config::AppState;
+use crate::middleware::admin_auth::AdminAuth;
+use crate::middleware::error::AppError;
+use crate::services::image_service;
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
+    Json,
+};
+use shared::models::Pizza;
+
+/// Largest upload accepted by the image endpoint
+pub const MAX_IMAGE_SIZE: usize = 10 * 1024 * 1024;
+/// Variants never change under a key, a new upload gets new keys
+const IMAGE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Replaces a pizza's photo with the JPEG, PNG or WebP image sent as the request body
+pub async fn upload_pizza_image_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<Pizza>, AppError> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+
+    let pizza = image_service::upload_pizza_image(
+        &state.db.client(),
+        state.images.as_ref(),
+        &state.menu_cache,
+        &state.config.images.public_url,
+        &id,
+        content_type,
+        body.to_vec(),
+    )
+    .await?;
+    Ok(Json(pizza))
+}
+
+pub async fn delete_pizza_image_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Pizza>, AppError> {
+    let pizza = image_service::delete_pizza_image(
+        &state.db.client(),
+        state.images.as_ref(),
+        &state.menu_cache,
+        &id,
+    )
+    .await?;
+    Ok(Json(pizza))
+}
+
+/// Serves an uploaded image variant, cacheable for good
+pub async fn get_image_handler(
+    State(state): State<AppState>,
+    Path(key): Path<String>,
+) -> Result<Response, AppError> {
+    let (format, bytes) = image_service::get_image(state.images.as_ref(), &key).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type()),
+            (header::CACHE_CONTROL, IMAGE_CACHE_CONTROL),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+        ],
+        bytes,
+    )
+        .into_response())
+}
@@ -0,0 +1,16 @@
+use axum::{extract::State, http::header, response::IntoResponse};
+
+use crate::config::AppState;
+use crate::services::health_service;
+
+/// Prometheus scrape endpoint
+///
+/// Probes the database first so `db_up` is current on every scrape.
+pub async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    health_service::check_database(&state.db, false).await;
+
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+}
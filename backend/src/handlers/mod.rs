@@ -1,3 +1,15 @@
+pub mod audit_handler;
+pub mod backup_handler;
+pub mod customer_handler;
 pub mod health_handler;
+pub mod image_handler;
+pub mod metrics_handler;
 pub mod order_handler;
+pub mod payment_handler;
 pub mod pizza_handler;
+pub mod quote_handler;
+pub mod receipt_handler;
+pub mod refund_handler;
+pub mod report_handler;
+pub mod verification_handler;
+pub mod webhook_handler;
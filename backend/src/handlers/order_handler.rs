@@ -1,19 +1,51 @@
 use crate::config::AppState;
+use crate::middleware::admin_auth::AdminAuth;
 use crate::middleware::error::AppError;
+use crate::repository::order_repo::OrderFilter;
 use crate::services::order_service;
+use crate::utils::query::{parse_order_statuses, TimeBound};
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
-use shared::dto::{CreateOrderRequest, CreateOrderResponse};
+use serde::Deserialize;
+use shared::dto::{
+    CreateOrderRequest, CreateOrderResponse, GetOrdersResponse, OrderSort, SortDirection,
+    UpdateOrderStatusRequest,
+};
 use shared::models::Order;
 
+#[derive(Debug, Deserialize)]
+pub struct OrderListQuery {
+    /// Comma separated order statuses, e.g. `Confirmed,Preparing`
+    pub status: Option<String>,
+    pub created_from: Option<TimeBound>,
+    pub created_to: Option<TimeBound>,
+    pub pickup_from: Option<TimeBound>,
+    pub pickup_to: Option<TimeBound>,
+    /// Matches order number, customer name or phone
+    pub search: Option<String>,
+    #[serde(default)]
+    pub sort: OrderSort,
+    #[serde(default)]
+    pub direction: SortDirection,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
 pub async fn create_order_handler(
     State(state): State<AppState>,
     Json(request): Json<CreateOrderRequest>,
 ) -> Result<(StatusCode, Json<CreateOrderResponse>), AppError> {
-    let response = order_service::create_order(&state.db, request).await?;
+    let response = order_service::create_order(
+        &state.db.client(),
+        &state.config.tax,
+        &state.config.phone,
+        state.payments.as_ref(),
+        request,
+    )
+    .await?;
     Ok((StatusCode::CREATED, Json(response)))
 }
 
@@ -21,6 +53,51 @@ pub async fn get_order_by_id_handler(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<Json<Order>, AppError> {
-    let order = order_service::get_order_by_id(&state.db, &id).await?;
+    let order = order_service::get_order_by_id(&state.db.client(), &id).await?;
+    Ok(Json(order))
+}
+
+pub async fn list_orders_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Query(query): Query<OrderListQuery>,
+) -> Result<Json<GetOrdersResponse>, AppError> {
+    let filter = OrderFilter {
+        created_from: query.created_from.map(TimeBound::start),
+        created_to: query.created_to.map(TimeBound::end),
+        pickup_from: query.pickup_from.map(TimeBound::start),
+        pickup_to: query.pickup_to.map(TimeBound::end),
+        statuses: match &query.status {
+            Some(status) => parse_order_statuses(status)?,
+            None => Vec::new(),
+        },
+        search: query.search,
+    };
+
+    let response = order_service::list_orders(
+        &state.db.client(),
+        filter,
+        query.sort,
+        query.direction,
+        query.cursor.as_deref(),
+        query.limit,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+pub async fn update_order_status_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateOrderStatusRequest>,
+) -> Result<Json<Order>, AppError> {
+    let order = order_service::update_order_status(
+        &state.db.client(),
+        state.payments.as_ref(),
+        &id,
+        request.status,
+    )
+    .await?;
     Ok(Json(order))
 }
@@ -0,0 +1,20 @@
+use crate::config::AppState;
+use crate::middleware::error::AppError;
+use crate::services::payment_service;
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+};
+
+/// Receives payment outcomes from the payment provider
+pub async fn payment_webhook_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, AppError> {
+    let event = state.payments.parse_webhook(&headers, &body)?;
+    payment_service::apply_payment_event(&state.db.client(), state.payments.as_ref(), event)
+        .await?;
+    Ok(StatusCode::OK)
+}
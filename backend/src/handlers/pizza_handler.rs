@@ -1,24 +1,67 @@
 use crate::config::AppState;
+use crate::middleware::admin_auth::AdminAuth;
 use crate::middleware::error::AppError;
 use crate::services::pizza_service;
+use crate::utils::http_cache::{self, Tagged};
 use axum::{
     extract::{Path, State},
+    http::HeaderMap,
+    response::Response,
     Json,
 };
-use shared::dto::GetPizzasResponse;
+use shared::dto::{GetPizzasResponse, GetPriceHistoryResponse, UpdatePizzaRequest};
 use shared::models::Pizza;
 
+/// Clients may keep the menu but check its ETag before every use, so edits show at once
+const MENU_CACHE_CONTROL: &str = "public, no-cache";
+
+/// The menu, or `304 Not Modified` when `If-None-Match` has its current ETag
 pub async fn get_all_pizzas(
     State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let menu =
+        pizza_service::get_all_available_pizzas(&state.db.client(), &state.menu_cache).await?;
+    Ok(http_cache::cached_json(&headers, menu, MENU_CACHE_CONTROL))
+}
+
+pub async fn get_pizza_by_id(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let pizza = pizza_service::get_pizza_by_id(&state.db.client(), &id).await?;
+    Ok(http_cache::cached_json(
+        &headers,
+        Tagged::new(pizza)?,
+        MENU_CACHE_CONTROL,
+    ))
+}
+
+pub async fn get_menu_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
 ) -> Result<Json<GetPizzasResponse>, AppError> {
-    let response = pizza_service::get_all_available_pizzas(&state.db).await?;
+    let response = pizza_service::get_all_pizzas(&state.db.client()).await?;
     Ok(Json(response))
 }
 
-pub async fn get_pizza_by_id(
+pub async fn update_pizza_handler(
+    _admin: AdminAuth,
     State(state): State<AppState>,
     Path(id): Path<String>,
+    Json(request): Json<UpdatePizzaRequest>,
 ) -> Result<Json<Pizza>, AppError> {
-    let pizza = pizza_service::get_pizza_by_id(&state.db, &id).await?;
+    let pizza =
+        pizza_service::update_pizza(&state.db.client(), &state.menu_cache, &id, request).await?;
     Ok(Json(pizza))
 }
+
+pub async fn get_price_history_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<GetPriceHistoryResponse>, AppError> {
+    let history = pizza_service::get_price_history(&state.db.client(), &id).await?;
+    Ok(Json(history))
+}
@@ -0,0 +1,14 @@
+use crate::config::AppState;
+use crate::middleware::error::AppError;
+use crate::services::pricing_service;
+use axum::{extract::State, Json};
+use shared::dto::{QuoteRequest, QuoteResponse};
+
+pub async fn create_quote_handler(
+    State(state): State<AppState>,
+    Json(request): Json<QuoteRequest>,
+) -> Result<Json<QuoteResponse>, AppError> {
+    let quote =
+        pricing_service::build_quote(&state.db.client(), &state.config.tax, &request.items).await?;
+    Ok(Json(quote))
+}
@@ -0,0 +1,47 @@
+use crate::config::AppState;
+use crate::middleware::error::AppError;
+use crate::receipt::{ReceiptCopy, ReceiptFormat};
+use crate::services::receipt_service;
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::IntoResponse,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ReceiptQuery {
+    #[serde(default)]
+    pub format: ReceiptFormat,
+    #[serde(default)]
+    pub copy: ReceiptCopy,
+}
+
+pub async fn get_receipt_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ReceiptQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let (file_name, body) = receipt_service::render_receipt(
+        &state.db.client(),
+        &id,
+        query.copy,
+        query.format,
+        state.config.shop.timezone,
+    )
+    .await?;
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                query.format.content_type().to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("inline; filename=\"{}\"", file_name),
+            ),
+        ],
+        body,
+    ))
+}
@@ -0,0 +1,38 @@
+use crate::config::AppState;
+use crate::middleware::admin_auth::AdminAuth;
+use crate::middleware::error::AppError;
+use crate::services::{order_service, refund_service};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use shared::dto::{CreateRefundRequest, GetRefundsResponse};
+use shared::models::Refund;
+
+pub async fn create_refund_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<CreateRefundRequest>,
+) -> Result<(StatusCode, Json<Refund>), AppError> {
+    let order = order_service::get_order_by_id(&state.db.client(), &id).await?;
+    let refund = refund_service::refund_order(
+        &state.db.client(),
+        state.payments.as_ref(),
+        &order,
+        request.lines.as_deref(),
+        request.reason,
+    )
+    .await?;
+    Ok((StatusCode::CREATED, Json(refund)))
+}
+
+pub async fn get_refunds_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<GetRefundsResponse>, AppError> {
+    let refunds = refund_service::get_refunds_for_order(&state.db.client(), &id).await?;
+    Ok(Json(refunds))
+}
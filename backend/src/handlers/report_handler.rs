@@ -0,0 +1,160 @@
+use crate::config::AppState;
+use crate::middleware::admin_auth::AdminAuth;
+use crate::middleware::error::AppError;
+use crate::services::report_service::{self, ReportRange};
+use crate::utils::csv::write_csv;
+use crate::utils::query::parse_order_statuses;
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use shared::dto::{ItemSalesReport, ReportGrouping, SalesReport, SalesSummary};
+
+/// Output format of a report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportOutput {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    /// Comma separated order statuses, e.g. `PickedUp,Ready`
+    pub status: Option<String>,
+    #[serde(default)]
+    pub group_by: ReportGrouping,
+    #[serde(default)]
+    pub format: ReportOutput,
+}
+
+impl ReportQuery {
+    fn range(&self) -> Result<ReportRange, AppError> {
+        let statuses = match &self.status {
+            Some(status) => Some(parse_order_statuses(status)?),
+            None => None,
+        };
+        ReportRange::new(self.from, self.to, statuses)
+    }
+}
+
+pub async fn get_sales_report_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Response, AppError> {
+    let report =
+        report_service::get_sales_report(&state.db.client(), &query.range()?, query.group_by)
+            .await?;
+    Ok(respond(query.format, "sales", &report, sales_csv))
+}
+
+pub async fn get_item_sales_report_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Response, AppError> {
+    let report = report_service::get_item_sales_report(&state.db.client(), &query.range()?).await?;
+    Ok(respond(query.format, "items", &report, item_sales_csv))
+}
+
+pub async fn get_sales_summary_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Response, AppError> {
+    let summary = report_service::get_sales_summary(&state.db.client(), &query.range()?).await?;
+    Ok(respond(query.format, "summary", &summary, summary_csv))
+}
+
+fn respond<T: Serialize>(
+    format: ReportOutput,
+    name: &str,
+    report: &T,
+    to_csv: fn(&T) -> String,
+) -> Response {
+    match format {
+        ReportOutput::Json => Json(report).into_response(),
+        ReportOutput::Csv => (
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}.csv\"", name),
+                ),
+            ],
+            to_csv(report),
+        )
+            .into_response(),
+    }
+}
+
+fn sales_csv(report: &SalesReport) -> String {
+    write_csv(
+        &[
+            "period",
+            "order_count",
+            "gross_revenue",
+            "refunded_amount",
+            "net_revenue",
+            "average_ticket",
+        ],
+        report.periods.iter().map(|period| {
+            vec![
+                period.period.clone(),
+                period.order_count.to_string(),
+                format!("{:.2}", period.gross_revenue),
+                format!("{:.2}", period.refunded_amount),
+                format!("{:.2}", period.net_revenue),
+                format!("{:.2}", period.average_ticket),
+            ]
+        }),
+    )
+}
+
+fn item_sales_csv(report: &ItemSalesReport) -> String {
+    write_csv(
+        &["pizza_id", "name", "size", "quantity", "revenue"],
+        report.items.iter().map(|item| {
+            vec![
+                item.pizza_id.clone().unwrap_or_default(),
+                item.name.clone(),
+                item.size.as_str().to_string(),
+                item.quantity.to_string(),
+                format!("{:.2}", item.revenue),
+            ]
+        }),
+    )
+}
+
+fn summary_csv(summary: &SalesSummary) -> String {
+    write_csv(
+        &[
+            "from",
+            "to",
+            "order_count",
+            "gross_revenue",
+            "refunded_amount",
+            "net_revenue",
+            "average_ticket",
+            "cancellation_rate",
+        ],
+        [vec![
+            summary.from.to_string(),
+            summary.to.to_string(),
+            summary.order_count.to_string(),
+            format!("{:.2}", summary.gross_revenue),
+            format!("{:.2}", summary.refunded_amount),
+            format!("{:.2}", summary.net_revenue),
+            format!("{:.2}", summary.average_ticket),
+            format!("{:.4}", summary.cancellation_rate),
+        ]],
+    )
+}
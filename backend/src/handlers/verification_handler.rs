@@ -0,0 +1,35 @@
+use crate::config::AppState;
+use crate::middleware::error::AppError;
+use crate::services::verification_service;
+use axum::{extract::State, Json};
+use shared::dto::{
+    ConfirmVerificationCodeRequest, ConfirmVerificationCodeResponse, SendVerificationCodeRequest,
+    SendVerificationCodeResponse,
+};
+
+pub async fn send_code_handler(
+    State(state): State<AppState>,
+    Json(request): Json<SendVerificationCodeRequest>,
+) -> Result<Json<SendVerificationCodeResponse>, AppError> {
+    let response = verification_service::send_code(
+        &state.db.client(),
+        state.notifiers.sms.as_deref(),
+        state.config.phone.region(),
+        &request,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+pub async fn confirm_code_handler(
+    State(state): State<AppState>,
+    Json(request): Json<ConfirmVerificationCodeRequest>,
+) -> Result<Json<ConfirmVerificationCodeResponse>, AppError> {
+    let response = verification_service::confirm_code(
+        &state.db.client(),
+        state.config.phone.region(),
+        &request,
+    )
+    .await?;
+    Ok(Json(response))
+}
@@ -0,0 +1,77 @@
+use crate::config::AppState;
+use crate::middleware::admin_auth::AdminAuth;
+use crate::middleware::error::AppError;
+use crate::services::webhook_service;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use shared::dto::{
+    CreateWebhookRequest, GetWebhookDeliveriesResponse, GetWebhooksResponse, UpdateWebhookRequest,
+};
+use shared::models::{WebhookDelivery, WebhookDeliveryStatus, WebhookEndpoint};
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryLogQuery {
+    /// Only deliveries in this state, e.g. `DeadLetter`
+    pub status: Option<WebhookDeliveryStatus>,
+    pub limit: Option<u32>,
+}
+
+pub async fn list_webhooks_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+) -> Result<Json<GetWebhooksResponse>, AppError> {
+    let webhooks = webhook_service::list_webhooks(&state.db.client()).await?;
+    Ok(Json(webhooks))
+}
+
+pub async fn create_webhook_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<WebhookEndpoint>), AppError> {
+    let webhook = webhook_service::create_webhook(&state.db.client(), request).await?;
+    Ok((StatusCode::CREATED, Json(webhook)))
+}
+
+pub async fn update_webhook_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateWebhookRequest>,
+) -> Result<Json<WebhookEndpoint>, AppError> {
+    let webhook = webhook_service::update_webhook(&state.db.client(), &id, request).await?;
+    Ok(Json(webhook))
+}
+
+pub async fn delete_webhook_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    webhook_service::delete_webhook(&state.db.client(), &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_deliveries_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<DeliveryLogQuery>,
+) -> Result<Json<GetWebhookDeliveriesResponse>, AppError> {
+    let deliveries =
+        webhook_service::get_deliveries(&state.db.client(), &id, query.status, query.limit).await?;
+    Ok(Json(deliveries))
+}
+
+pub async fn retry_delivery_handler(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<WebhookDelivery>, AppError> {
+    let delivery = webhook_service::retry_delivery(&state.db.client(), &id).await?;
+    Ok(Json(delivery))
+}
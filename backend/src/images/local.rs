@@ -0,0 +1,62 @@
+use super::{ImageError, ImageStore, StoreFuture};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// Keeps images as files below a directory, one file per key
+pub struct LocalImageStore {
+    dir: PathBuf,
+}
+
+impl LocalImageStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+}
+
+impl ImageStore for LocalImageStore {
+    fn put<'a>(&'a self, key: &'a str, bytes: Vec<u8>) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.dir.join(key);
+            // Written under a temporary name, so readers never see half an image
+            let mut partial = path.clone().into_os_string();
+            partial.push(".partial");
+            let write = async {
+                if let Some(parent) = path.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                tokio::fs::write(&partial, &bytes).await?;
+                tokio::fs::rename(&partial, &path).await
+            };
+            write
+                .await
+                .map_err(|e| ImageError::Storage(format!("{}: {}", path.display(), e)))
+        })
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> StoreFuture<'a, Option<Vec<u8>>> {
+        Box::pin(async move {
+            match tokio::fs::read(self.dir.join(key)).await {
+                Ok(bytes) => Ok(Some(bytes)),
+                Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(ImageError::Storage(format!("{}: {}", key, e))),
+            }
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.dir.join(key);
+            match tokio::fs::remove_file(&path).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(ImageError::Storage(format!("{}: {}", key, e))),
+            }
+
+            // Drop the version directory once its last variant is gone
+            if let Some(parent) = path.parent() {
+                let _ = tokio::fs::remove_dir(parent).await;
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,75 @@
+//! Uploaded pizza images
+//!
+//! Uploads are decoded, resized to a few widths and encoded as WebP and JPEG
+//! by [`variants`]. The variants are kept in an [`ImageStore`] under keys that
+//! change with every upload, so they can be cached forever. The local store
+//! keeps them in a directory; object storage fits behind the same trait.
+
+pub mod local;
+pub mod variants;
+
+use crate::config::{ImageConfig, ImageStoreConfig};
+use crate::middleware::error::AppError;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Boxed future returned by stores, so they can be used as trait objects
+pub type StoreFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, ImageError>> + Send + 'a>>;
+
+#[derive(Debug)]
+pub enum ImageError {
+    /// The upload is not an image this backend accepts
+    Invalid(String),
+    /// The store could not read or write an image
+    Storage(String),
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageError::Invalid(msg) => write!(f, "Invalid image: {}", msg),
+            ImageError::Storage(msg) => write!(f, "Image storage failed: {}", msg),
+        }
+    }
+}
+
+impl From<ImageError> for AppError {
+    fn from(err: ImageError) -> Self {
+        match err {
+            ImageError::Invalid(_) => AppError::ValidationError(vec![err.to_string()]),
+            ImageError::Storage(_) => AppError::InternalError(err.to_string()),
+        }
+    }
+}
+
+/// Place image variants are kept, addressed by keys like `pizzas/<id>/<version>/640.webp`
+pub trait ImageStore: Send + Sync {
+    fn put<'a>(&'a self, key: &'a str, bytes: Vec<u8>) -> StoreFuture<'a, ()>;
+
+    /// Contents of an image, `None` when there is none under the key
+    fn get<'a>(&'a self, key: &'a str) -> StoreFuture<'a, Option<Vec<u8>>>;
+
+    /// Removes an image; removing a missing one is not an error
+    fn delete<'a>(&'a self, key: &'a str) -> StoreFuture<'a, ()>;
+}
+
+/// Builds the configured store
+pub fn create_store(config: &ImageConfig) -> Arc<dyn ImageStore> {
+    match &config.store {
+        ImageStoreConfig::Local { dir } => Arc::new(local::LocalImageStore::new(dir.clone())),
+    }
+}
+
+/// Whether a key is safe to hand to a store: relative, without `..` or odd characters
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.split('/').all(|segment| {
+            !segment.is_empty()
+                && !segment.starts_with('.')
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        })
+}
@@ -0,0 +1,121 @@
+use super::ImageError;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageReader, Limits, RgbImage, RgbaImage};
+use shared::models::ImageFormat;
+use std::io::Cursor;
+
+/// Widths variants are made in; an upload is never scaled up
+pub const VARIANT_WIDTHS: &[u32] = &[320, 640, 1024];
+/// Largest width or height an upload may have
+const MAX_DIMENSION: u32 = 8192;
+const JPEG_QUALITY: u8 = 80;
+const WEBP_QUALITY: f32 = 80.0;
+
+/// One encoded size and format of an upload
+pub struct EncodedVariant {
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+    pub bytes: Vec<u8>,
+}
+
+/// Decoder for an accepted upload content type, `None` for anything else
+pub fn upload_format(content_type: &str) -> Option<image::ImageFormat> {
+    match content_type {
+        "image/jpeg" => Some(image::ImageFormat::Jpeg),
+        "image/png" => Some(image::ImageFormat::Png),
+        "image/webp" => Some(image::ImageFormat::WebP),
+        _ => None,
+    }
+}
+
+/// Decodes an upload and encodes it as WebP and JPEG in every variant width
+///
+/// The bytes must really be in `format`, whatever the client claimed. This is
+/// CPU bound, run it on a blocking thread.
+pub fn render(bytes: &[u8], format: image::ImageFormat) -> Result<Vec<EncodedVariant>, ImageError> {
+    let sniffed = image::guess_format(bytes)
+        .map_err(|_| ImageError::Invalid("the file is not a known image format".to_string()))?;
+    if sniffed != format {
+        return Err(ImageError::Invalid(format!(
+            "the file is {}, not {}",
+            sniffed.to_mime_type(),
+            format.to_mime_type()
+        )));
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+    let source = reader
+        .decode()
+        .map_err(|e| ImageError::Invalid(e.to_string()))?;
+
+    let mut variants = Vec::new();
+    for width in variant_widths(source.width()) {
+        let height =
+            ((source.height() as u64 * width as u64) / source.width() as u64).max(1) as u32;
+        let resized = if width == source.width() {
+            source.to_rgba8()
+        } else {
+            source
+                .resize_exact(width, height, FilterType::Lanczos3)
+                .to_rgba8()
+        };
+
+        variants.push(EncodedVariant {
+            format: ImageFormat::Webp,
+            width,
+            height,
+            bytes: encode_webp(&resized),
+        });
+        variants.push(EncodedVariant {
+            format: ImageFormat::Jpeg,
+            width,
+            height,
+            bytes: encode_jpeg(&resized)?,
+        });
+    }
+
+    Ok(variants)
+}
+
+/// The variant widths that fit the source, or just its own width if none does
+fn variant_widths(source_width: u32) -> Vec<u32> {
+    let widths: Vec<u32> = VARIANT_WIDTHS
+        .iter()
+        .copied()
+        .filter(|&width| width <= source_width)
+        .collect();
+    if widths.is_empty() {
+        vec![source_width]
+    } else {
+        widths
+    }
+}
+
+fn encode_webp(image: &RgbaImage) -> Vec<u8> {
+    webp::Encoder::from_rgba(image.as_raw(), image.width(), image.height())
+        .encode(WEBP_QUALITY)
+        .to_vec()
+}
+
+/// JPEG has no transparency, so transparent parts become white like the menu background
+fn encode_jpeg(image: &RgbaImage) -> Result<Vec<u8>, ImageError> {
+    let flattened = RgbImage::from_fn(image.width(), image.height(), |x, y| {
+        let [r, g, b, a] = image.get_pixel(x, y).0;
+        let blend = |c: u8| ((c as u16 * a as u16 + 255 * (255 - a as u16)) / 255) as u8;
+        image::Rgb([blend(r), blend(g), blend(b)])
+    });
+
+    let mut bytes = Vec::new();
+    DynamicImage::ImageRgb8(flattened)
+        .write_with_encoder(image::codecs::jpeg::JpegEncoder::new_with_quality(
+            &mut bytes,
+            JPEG_QUALITY,
+        ))
+        .map_err(|e| ImageError::Storage(format!("Failed to encode JPEG: {}", e)))?;
+    Ok(bytes)
+}
@@ -1,36 +1,56 @@
-use axum::Router;
+use axum::{routing::get, Router};
 use std::sync::Arc;
 use tokio::signal;
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
 use tower_http::trace::TraceLayer;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+mod cli;
 mod config;
 mod handlers;
+mod images;
+mod metrics;
 mod middleware;
+mod notification;
+mod payment;
+mod receipt;
 mod repository;
 mod routes;
 mod services;
+mod telemetry;
 mod utils;
 
 use config::{AppState, Config};
-use repository::db::create_db_client;
+use repository::db::Database;
+use services::health_service::Workers;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize tracing subscriber for structured logging
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "backend=debug,tower_http=debug".into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+    // Environment first, so it can configure tracing too
+    dotenv::dotenv().ok();
+
+    // Initialize tracing subscriber for structured logging, exporting spans over OTLP if configured
+    let telemetry = telemetry::init().map_err(|e| format!("Failed to set up tracing: {}", e))?;
+    if telemetry.is_exporting() {
+        tracing::info!("Exporting trace spans over OTLP");
+    }
+
+    // Metrics recorder, installed before anything records
+    let metrics_handle = metrics::install()
+        .map_err(|e| format!("Failed to set up metrics: {}", e))?;
+    tokio::spawn(metrics::run_upkeep(metrics_handle.clone()));
 
     // Load configuration from environment
-    dotenv::dotenv().ok();
     let config = Config::from_env()
         .map_err(|e| format!("Failed to load configuration: {}", e))?;
 
+    // `backend backup` and `backend restore` run once instead of the server
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        let result = cli::run(&args, &config).await;
+        telemetry.shutdown();
+        return result;
+    }
+
     tracing::info!(
         "Starting Royal Pizza Backend on {}:{}",
         config.server.host,
@@ -38,18 +58,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     // Create database connection
-    let db = create_db_client(&config.database).await?;
-    tracing::info!("Connected to SurrealDB");
-
-    // Seed database if enabled
-    let should_seed = std::env::var("DATABASE_SEED")
-        .unwrap_or_else(|_| "true".to_string())
-        .parse::<bool>()
-        .unwrap_or(true);
-
-    if should_seed {
+    let db = Database::connect(&config.database).await?;
+    tracing::info!("Connected to SurrealDB ({:?})", config.database.engine);
+
+    // Seed database if enabled; `schema` sets up an empty database without the
+    // standard menu, so a backup can be restored into it with the `fail` policy
+    let seed = std::env::var("DATABASE_SEED").unwrap_or_else(|_| "true".to_string());
+    let seed_schema_only = seed.eq_ignore_ascii_case("schema");
+    let should_seed = seed_schema_only || seed.parse::<bool>().unwrap_or(true);
+
+    if seed_schema_only {
+        tracing::info!("Database seeding limited to the schema via DATABASE_SEED=schema");
+        let client = db.client();
+        if repository::health_repo::query_schema_version(&client)
+            .await?
+            .is_none()
+        {
+            repository::seed::init_schema_only(&client).await?;
+        }
+    } else if should_seed {
         tracing::info!("Database seeding enabled");
-        match repository::seed::seed_database(&db, false).await {
+        match repository::seed::seed_database(&db.client(), false).await {
             Ok(_) => tracing::info!("Database initialization completed successfully"),
             Err(e) => {
                 tracing::error!("Failed to seed database: {}", e);
@@ -60,16 +89,80 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tracing::info!("Database seeding disabled via DATABASE_SEED=false");
     }
 
+    // The connection is replaced if SurrealDB goes away later on; embedded
+    // storage lives in this process and cannot go away
+    if !config.database.engine.is_embedded() {
+        tokio::spawn(db.clone().supervise());
+    }
+
+    // Background workers report their runs to the readiness check
+    let workers = Workers::default();
+
+    // Payment provider, with events it reports outside the webhook applied in the background
+    let (payments, payment_events) = payment::create_provider(&config.payment);
+    tokio::spawn(services::payment_service::process_payment_events(
+        db.clone(),
+        payments.clone(),
+        payment_events,
+        workers.register("payment_events", None),
+    ));
+
+    // Customer notifications are delivered from the outbox in the background
+    let notifiers = notification::create_notifiers(&config.notification)
+        .map_err(|e| format!("Failed to set up notifications: {}", e))?;
+    tokio::spawn(services::notification_service::run_outbox(
+        db.clone(),
+        notifiers.clone(),
+        workers.register("notification_outbox", Some(services::outbox::STALE_AFTER)),
+    ));
+
+    // Order events are posted to webhook subscribers in the background
+    tokio::spawn(services::webhook_service::run_deliveries(
+        db.clone(),
+        workers.register("webhook_deliveries", Some(services::outbox::STALE_AFTER)),
+    ));
+
+    // Backups are written to a local directory on a schedule, if one is configured
+    if config.backup.dir.is_some() {
+        tokio::spawn(services::backup_service::run_scheduled_backups(
+            db.clone(),
+            config.backup.clone(),
+            workers.register("scheduled_backups", Some(config.backup.interval * 2)),
+        ));
+    }
+
     // Build AppState with Arc-wrapped dependencies
-    let app_state = AppState::new(Arc::new(db), Arc::new(config.clone()));
+    let app_state = AppState::new(
+        db,
+        Arc::new(config.clone()),
+        payments,
+        notifiers,
+        images::create_store(&config.images),
+        metrics_handle,
+        workers,
+    );
 
     // Create Axum router with routes and middleware
     let app = Router::new()
-        .nest("/api", routes::api::create_router())
+        .nest("/api", routes::api::create_router(&app_state))
+        .route_layer(axum::middleware::from_fn(
+            middleware::metrics::track_requests,
+        ))
+        .route("/metrics", get(handlers::metrics_handler::metrics_handler))
         .layer(axum::middleware::from_fn(
             middleware::logging::log_request,
         ))
-        .layer(TraceLayer::new_for_http())
+        .layer(axum::middleware::from_fn(
+            middleware::request_id::scope_request_id,
+        ))
+        .layer(TraceLayer::new_for_http().make_span_with(middleware::request_id::make_span))
+        .layer(PropagateRequestIdLayer::new(
+            middleware::request_id::REQUEST_ID_HEADER,
+        ))
+        .layer(SetRequestIdLayer::new(
+            middleware::request_id::REQUEST_ID_HEADER,
+            MakeRequestUuid,
+        ))
         .layer(middleware::cors::create_cors_layer(
             &config.server.cors_allow_origin,
         ))
@@ -85,6 +178,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .await?;
 
     tracing::info!("Server shutdown complete");
+    telemetry.shutdown();
     Ok(())
 }
 
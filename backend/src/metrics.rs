@@ -0,0 +1,154 @@
+use metrics::{
+    counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram, Unit,
+};
+use metrics_exporter_prometheus::{BuildError, Matcher, PrometheusBuilder, PrometheusHandle};
+use shared::models::{Order, PaymentMethod};
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant};
+use tracing::Span;
+
+pub const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
+pub const HTTP_REQUEST_DURATION: &str = "http_request_duration_seconds";
+pub const DB_QUERY_DURATION: &str = "db_query_duration_seconds";
+pub const DB_UP: &str = "db_up";
+pub const DB_RECONNECTS_TOTAL: &str = "db_reconnects_total";
+pub const ORDERS_CREATED_TOTAL: &str = "orders_created_total";
+pub const ORDERS_CANCELLED_TOTAL: &str = "orders_cancelled_total";
+pub const ORDER_TOTAL_AMOUNT: &str = "order_total_amount";
+
+/// Buckets of the latency histograms, in seconds
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+/// Buckets of the order value histogram, in the shop's currency
+const ORDER_AMOUNT_BUCKETS: &[f64] = &[
+    5.0, 10.0, 15.0, 20.0, 30.0, 40.0, 50.0, 75.0, 100.0, 150.0, 250.0,
+];
+/// How often histogram samples are folded into the buckets
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Installs the global Prometheus recorder and describes the metrics
+///
+/// Metrics recorded before this are lost, so it runs first thing in `main`.
+pub fn install() -> Result<PrometheusHandle, BuildError> {
+    let handle = PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), LATENCY_BUCKETS)?
+        .set_buckets_for_metric(
+            Matcher::Full(ORDER_TOTAL_AMOUNT.to_string()),
+            ORDER_AMOUNT_BUCKETS,
+        )?
+        .install_recorder()?;
+
+    describe_counter!(
+        HTTP_REQUESTS_TOTAL,
+        "HTTP requests by method, route template and status"
+    );
+    describe_histogram!(
+        HTTP_REQUEST_DURATION,
+        Unit::Seconds,
+        "HTTP request latency by method, route template and status"
+    );
+    describe_histogram!(
+        DB_QUERY_DURATION,
+        Unit::Seconds,
+        "Database query latency by repository operation and outcome"
+    );
+    describe_gauge!(
+        DB_UP,
+        "Whether the database answered the last health probe (1) or not (0)"
+    );
+    describe_counter!(
+        DB_RECONNECTS_TOTAL,
+        "Times the database connection was replaced after being lost"
+    );
+    describe_counter!(ORDERS_CREATED_TOTAL, "Orders placed, by payment method");
+    describe_counter!(ORDERS_CANCELLED_TOTAL, "Orders cancelled");
+    describe_histogram!(ORDER_TOTAL_AMOUNT, "Total amount of placed orders");
+
+    Ok(handle)
+}
+
+/// Keeps the histograms of `handle` from growing between scrapes
+pub async fn run_upkeep(handle: PrometheusHandle) {
+    let mut interval = tokio::time::interval(UPKEEP_INTERVAL);
+    loop {
+        interval.tick().await;
+        handle.run_upkeep();
+    }
+}
+
+pub fn record_http_request(method: &str, route: String, status: u16, duration: Duration) {
+    let labels = [
+        ("method", method.to_string()),
+        ("route", route),
+        ("status", status.to_string()),
+    ];
+    counter!(HTTP_REQUESTS_TOTAL, &labels).increment(1);
+    histogram!(HTTP_REQUEST_DURATION, &labels).record(duration.as_secs_f64());
+}
+
+pub fn record_db_up(is_up: bool) {
+    gauge!(DB_UP).set(if is_up { 1.0 } else { 0.0 });
+}
+
+pub fn record_db_reconnect() {
+    counter!(DB_RECONNECTS_TOTAL).increment(1);
+}
+
+pub fn record_order_created(order: &Order) {
+    let payment_method = match order.payment_method {
+        PaymentMethod::PayAtPickup => "pay_at_pickup",
+        PaymentMethod::PayOnline => "pay_online",
+    };
+    counter!(ORDERS_CREATED_TOTAL, "payment_method" => payment_method).increment(1);
+    histogram!(ORDER_TOTAL_AMOUNT).record(order.total_amount);
+}
+
+pub fn record_order_cancelled() {
+    counter!(ORDERS_CANCELLED_TOTAL).increment(1);
+}
+
+/// Adds `.timed(operation)` to database queries to record their latency
+///
+/// The query also runs in a `db.query` span, a child of the request span.
+pub trait TimedQuery: IntoFuture + Sized {
+    fn timed(self, operation: &'static str) -> Timed<Self::IntoFuture> {
+        Timed {
+            future: Box::pin(self.into_future()),
+            operation,
+            started: Instant::now(),
+            span: tracing::info_span!("db.query", operation),
+        }
+    }
+}
+
+impl<Q: IntoFuture> TimedQuery for Q {}
+
+/// Query future that records its duration under `db_query_duration_seconds`
+pub struct Timed<F> {
+    future: Pin<Box<F>>,
+    operation: &'static str,
+    started: Instant,
+    span: Span,
+}
+
+impl<F, T, E> Future for Timed<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let output = {
+            let _entered = this.span.enter();
+            ready!(this.future.as_mut().poll(cx))
+        };
+        let outcome = if output.is_ok() { "ok" } else { "error" };
+        histogram!(DB_QUERY_DURATION, "operation" => this.operation, "outcome" => outcome)
+            .record(this.started.elapsed().as_secs_f64());
+        Poll::Ready(output)
+    }
+}
@@ -0,0 +1,54 @@
+use axum::{extract::Request, http::HeaderName, middleware::Next, response::Response};
+use shared::models::{Actor, ActorKind};
+use std::future::Future;
+
+/// Header in which an admin may name themselves for the audit log
+///
+/// The name is self-reported: the admin token is shared, so it cannot tell
+/// admins apart.
+pub const ADMIN_NAME_HEADER: HeaderName = HeaderName::from_static("x-admin-name");
+
+const MAX_ADMIN_NAME_LENGTH: usize = 64;
+
+tokio::task_local! {
+    static ACTOR: Actor;
+}
+
+/// Who the current request or task acts for, the backend itself outside of one
+pub fn current_actor() -> Actor {
+    ACTOR
+        .try_with(Clone::clone)
+        .unwrap_or_else(|_| Actor::new(ActorKind::System))
+}
+
+/// Runs `future` on behalf of `actor`, e.g. a command line restore
+pub async fn act_as<F: Future>(actor: Actor, future: F) -> F::Output {
+    ACTOR.scope(actor, future).await
+}
+
+/// Attributes changes made by public API requests to the customer
+pub async fn as_customer(request: Request, next: Next) -> Response {
+    act_as(Actor::new(ActorKind::Customer), next.run(request)).await
+}
+
+/// Attributes changes made by payment provider callbacks to the provider
+pub async fn as_payment_provider(request: Request, next: Next) -> Response {
+    act_as(Actor::new(ActorKind::PaymentProvider), next.run(request)).await
+}
+
+/// Attributes changes made by admin requests to the admin named in `X-Admin-Name`
+pub async fn as_admin(request: Request, next: Next) -> Response {
+    let name = request
+        .headers()
+        .get(ADMIN_NAME_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| name.chars().take(MAX_ADMIN_NAME_LENGTH).collect::<String>());
+
+    let actor = Actor {
+        kind: ActorKind::Admin,
+        name,
+    };
+    act_as(actor, next.run(request)).await
+}
@@ -0,0 +1,47 @@
+use crate::config::AppState;
+use crate::middleware::error::AppError;
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap},
+};
+
+/// Extractor that only lets requests with the admin bearer token through
+///
+/// Add it as a handler argument to protect an endpoint. Without a configured
+/// `ADMIN_API_TOKEN` every admin request is rejected.
+pub struct AdminAuth;
+
+impl FromRequestParts<AppState> for AdminAuth {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        check_admin_token(&parts.headers, state).map(|()| AdminAuth)
+    }
+}
+
+/// Whether the request carries the admin token, for endpoints that show admins more
+pub fn is_admin(headers: &HeaderMap, state: &AppState) -> bool {
+    check_admin_token(headers, state).is_ok()
+}
+
+fn check_admin_token(headers: &HeaderMap, state: &AppState) -> Result<(), AppError> {
+    let expected = state
+        .config
+        .admin
+        .api_token
+        .as_deref()
+        .ok_or_else(|| AppError::Unauthorized("Admin access is not configured".to_string()))?;
+
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match provided {
+        Some(token) if token == expected => Ok(()),
+        _ => Err(AppError::Unauthorized("Invalid admin token".to_string())),
+    }
+}
@@ -1,6 +1,9 @@
 use axum::http::{header, HeaderValue, Method};
 use tower_http::cors::{Any, CorsLayer};
 
+use crate::middleware::actor::ADMIN_NAME_HEADER;
+use crate::middleware::request_id::REQUEST_ID_HEADER;
+
 pub fn create_cors_layer(allow_origin: &str) -> CorsLayer {
     // In development, allow multiple origins for Docker and localhost
     if allow_origin.contains("localhost") || allow_origin.contains("127.0.0.1") {
@@ -15,6 +18,7 @@ pub fn create_cors_layer(allow_origin: &str) -> CorsLayer {
                 Method::PATCH,
             ])
             .allow_headers(Any)
+            .expose_headers([REQUEST_ID_HEADER, header::ETAG])
             .allow_credentials(false)
     } else {
         // Production: strict CORS
@@ -27,7 +31,13 @@ pub fn create_cors_layer(allow_origin: &str) -> CorsLayer {
                 Method::DELETE,
                 Method::OPTIONS,
             ])
-            .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION])
+            .allow_headers([
+                header::CONTENT_TYPE,
+                header::AUTHORIZATION,
+                header::IF_NONE_MATCH,
+                ADMIN_NAME_HEADER,
+            ])
+            .expose_headers([REQUEST_ID_HEADER, header::ETAG])
             .allow_credentials(false)
     }
 }
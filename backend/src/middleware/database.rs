@@ -0,0 +1,27 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::config::AppState;
+use crate::middleware::error::AppError;
+
+/// Answers `503 Service Unavailable` while the database connection is re-established
+///
+/// Requests fail fast with a `Retry-After` header instead of waiting on a
+/// dead connection. Health checks are left out so they can report the outage.
+pub async fn require_database(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !state.db.is_connected() {
+        return AppError::ServiceUnavailable(
+            "The database is temporarily unavailable, please try again shortly".to_string(),
+        )
+        .into_response();
+    }
+
+    next.run(request).await
+}
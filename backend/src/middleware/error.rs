@@ -1,15 +1,26 @@
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use shared::dto::ErrorResponse;
 
+use crate::middleware::request_id::current_request_id;
+
+/// Seconds clients are asked to wait before retrying while the database reconnects
+const RETRY_AFTER_SECONDS: &str = "5";
+
 #[derive(Debug)]
 pub enum AppError {
     NotFound(String),
+    Unauthorized(String),
     ValidationError(Vec<String>),
+    /// The request clashes with the current data, e.g. a restore into a non-empty database
+    Conflict(String),
+    TooManyRequests(String),
     DatabaseError(String),
+    /// The database connection is down and being re-established
+    ServiceUnavailable(String),
     InternalError(String),
 }
 
@@ -17,20 +28,44 @@ impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let (status, error_response) = match self {
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, ErrorResponse::new(msg)),
+            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, ErrorResponse::new(msg)),
             AppError::ValidationError(errors) => (
                 StatusCode::UNPROCESSABLE_ENTITY,
                 ErrorResponse::with_details("Validation failed".to_string(), errors),
             ),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, ErrorResponse::new(msg)),
+            AppError::TooManyRequests(msg) => {
+                (StatusCode::TOO_MANY_REQUESTS, ErrorResponse::new(msg))
+            }
             AppError::DatabaseError(msg) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 ErrorResponse::new(format!("Database error: {}", msg)),
             ),
+            AppError::ServiceUnavailable(msg) => {
+                (StatusCode::SERVICE_UNAVAILABLE, ErrorResponse::new(msg))
+            }
             AppError::InternalError(msg) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 ErrorResponse::new(format!("Internal error: {}", msg)),
             ),
         };
 
+        // Expected during an outage, the supervisor already logs it
+        if status == StatusCode::SERVICE_UNAVAILABLE {
+            let error_response = error_response.with_request_id(current_request_id());
+            return (
+                status,
+                [(header::RETRY_AFTER, RETRY_AFTER_SECONDS)],
+                Json(error_response),
+            )
+                .into_response();
+        }
+
+        if status.is_server_error() {
+            tracing::error!("{}", error_response.error);
+        }
+
+        let error_response = error_response.with_request_id(current_request_id());
         (status, Json(error_response)).into_response()
     }
 }
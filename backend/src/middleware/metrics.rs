@@ -0,0 +1,33 @@
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Instant;
+
+use crate::metrics::record_http_request;
+
+/// Records count and latency of requests by route template
+///
+/// Added with `route_layer`, so only requests matching a route are counted
+/// and `/api/orders/{id}` stays one series however many orders there are.
+pub async fn track_requests(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    record_http_request(
+        method.as_str(),
+        route,
+        response.status().as_u16(),
+        start.elapsed(),
+    );
+
+    response
+}
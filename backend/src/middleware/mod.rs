@@ -1,3 +1,8 @@
+pub mod actor;
+pub mod admin_auth;
 pub mod cors;
+pub mod database;
 pub mod error;
 pub mod logging;
+pub mod metrics;
+pub mod request_id;
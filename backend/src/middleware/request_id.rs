@@ -0,0 +1,40 @@
+use axum::{extract::Request, http::HeaderName, middleware::Next, response::Response};
+use tower_http::request_id::RequestId;
+use tracing::Span;
+
+/// Header carrying the request id, accepted from clients and returned on every response
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Id of the request being handled, for responses built outside the middleware
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Makes the request id set by `SetRequestIdLayer` available to `current_request_id`
+pub async fn scope_request_id(request: Request, next: Next) -> Response {
+    let id = request_id(&request);
+    REQUEST_ID.scope(id, next.run(request)).await
+}
+
+/// Span covering a request, so every log line and query span of it carries the id
+pub fn make_span(request: &Request) -> Span {
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        request_id = %request_id(request),
+    )
+}
+
+fn request_id(request: &Request) -> String {
+    request
+        .extensions()
+        .get::<RequestId>()
+        .and_then(|id| id.header_value().to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
@@ -0,0 +1,71 @@
+use super::{Message, Notifier, NotifyError, NotifyFuture};
+use crate::config::HttpSmsConfig;
+use serde_json::json;
+use std::time::Duration;
+
+/// How long the gateway may take to accept a message
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sends text messages through an HTTP SMS gateway
+///
+/// Messages are posted as JSON `{ "from", "to", "text" }` with the API key as
+/// bearer token, the format most gateways accept directly or through a small
+/// adapter. Client errors other than rate limiting are permanent.
+pub struct HttpSmsNotifier {
+    client: reqwest::Client,
+    url: String,
+    api_key: Option<String>,
+    sender: String,
+}
+
+impl HttpSmsNotifier {
+    pub fn new(config: &HttpSmsConfig) -> Result<Self, String> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| format!("Failed to create SMS gateway client: {}", e))?;
+
+        Ok(Self {
+            client,
+            url: config.url.clone(),
+            api_key: config.api_key.clone(),
+            sender: config.sender.clone(),
+        })
+    }
+}
+
+impl Notifier for HttpSmsNotifier {
+    fn send<'a>(&'a self, message: &'a Message) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            let mut request = self.client.post(&self.url).json(&json!({
+                "from": self.sender,
+                "to": message.recipient,
+                "text": message.body,
+            }));
+            if let Some(api_key) = &self.api_key {
+                request = request.bearer_auth(api_key);
+            }
+
+            let response = request
+                .send()
+                .await
+                .map_err(|e| NotifyError::Unavailable(e.to_string()))?;
+
+            let status = response.status();
+            if status.is_success() {
+                return Ok(());
+            }
+
+            let message = format!(
+                "SMS gateway responded {}: {}",
+                status,
+                response.text().await.unwrap_or_default()
+            );
+            if status.is_client_error() && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+                Err(NotifyError::Rejected(message))
+            } else {
+                Err(NotifyError::Unavailable(message))
+            }
+        })
+    }
+}
@@ -0,0 +1,55 @@
+use super::{Message, Notifier, NotifyError, NotifyFuture};
+use serde_json::json;
+use shared::models::NotificationChannel;
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+
+/// Writes notifications to a file or the log instead of sending them
+///
+/// Meant for local development: with a path every message is appended to the
+/// file as one JSON line, otherwise it is logged.
+pub struct LogNotifier {
+    channel: NotificationChannel,
+    path: Option<PathBuf>,
+}
+
+impl LogNotifier {
+    pub fn new(channel: NotificationChannel, path: Option<PathBuf>) -> Self {
+        Self { channel, path }
+    }
+}
+
+impl Notifier for LogNotifier {
+    fn send<'a>(&'a self, message: &'a Message) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            let Some(path) = &self.path else {
+                tracing::info!(
+                    "{} notification to {}: {} - {}",
+                    self.channel.as_str(),
+                    message.recipient,
+                    message.subject,
+                    message.body
+                );
+                return Ok(());
+            };
+
+            let line = json!({
+                "channel": self.channel,
+                "recipient": message.recipient,
+                "subject": message.subject,
+                "body": message.body,
+            })
+            .to_string();
+
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .await
+                .map_err(|e| NotifyError::Unavailable(e.to_string()))?;
+            file.write_all(format!("{}\n", line).as_bytes())
+                .await
+                .map_err(|e| NotifyError::Unavailable(e.to_string()))
+        })
+    }
+}
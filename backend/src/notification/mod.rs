@@ -0,0 +1,99 @@
+//! Customer notifications
+//!
+//! Messages are never sent while handling a request. They are written to the
+//! `notification` outbox table and delivered by a background worker, which
+//! retries with backoff, so notifications survive restarts and transport
+//! outages. Each channel has one transport: a real one, the log transport for
+//! local testing, or none.
+
+pub mod http_sms;
+pub mod log;
+pub mod smtp;
+pub mod templates;
+
+use crate::config::{EmailTransportConfig, NotificationConfig, SmsTransportConfig};
+use shared::models::{Notification, NotificationChannel};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Boxed future returned by transports, so they can be used as trait objects
+pub type NotifyFuture<'a> = Pin<Box<dyn Future<Output = Result<(), NotifyError>> + Send + 'a>>;
+
+#[derive(Debug)]
+pub enum NotifyError {
+    /// The transport could not deliver the message now; worth retrying
+    Unavailable(String),
+    /// The message will never be accepted, e.g. an invalid recipient
+    Rejected(String),
+}
+
+impl fmt::Display for NotifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotifyError::Unavailable(msg) => write!(f, "Transport unavailable: {}", msg),
+            NotifyError::Rejected(msg) => write!(f, "Message rejected: {}", msg),
+        }
+    }
+}
+
+/// Message handed to a transport; the channel is given by the transport
+#[derive(Debug, Clone)]
+pub struct Message {
+    /// Phone number in E.164 format or email address
+    pub recipient: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl From<&Notification> for Message {
+    fn from(notification: &Notification) -> Self {
+        Self {
+            recipient: notification.recipient.clone(),
+            subject: notification.subject.clone(),
+            body: notification.body.clone(),
+        }
+    }
+}
+
+/// Transport delivering messages of one channel
+pub trait Notifier: Send + Sync {
+    fn send<'a>(&'a self, message: &'a Message) -> NotifyFuture<'a>;
+}
+
+/// Configured transport per channel; `None` when the channel is disabled
+#[derive(Clone, Default)]
+pub struct Notifiers {
+    pub sms: Option<Arc<dyn Notifier>>,
+    pub email: Option<Arc<dyn Notifier>>,
+}
+
+impl Notifiers {
+    pub fn for_channel(&self, channel: NotificationChannel) -> Option<&dyn Notifier> {
+        match channel {
+            NotificationChannel::Sms => self.sms.as_deref(),
+            NotificationChannel::Email => self.email.as_deref(),
+        }
+    }
+}
+
+/// Builds the configured transports
+pub fn create_notifiers(config: &NotificationConfig) -> Result<Notifiers, String> {
+    let log = |channel| -> Arc<dyn Notifier> {
+        Arc::new(log::LogNotifier::new(channel, config.log_path.clone()))
+    };
+
+    let sms: Option<Arc<dyn Notifier>> = match &config.sms {
+        SmsTransportConfig::Disabled => None,
+        SmsTransportConfig::Log => Some(log(NotificationChannel::Sms)),
+        SmsTransportConfig::Http(http) => Some(Arc::new(http_sms::HttpSmsNotifier::new(http)?)),
+    };
+    let email: Option<Arc<dyn Notifier>> = match &config.email {
+        EmailTransportConfig::Disabled => None,
+        EmailTransportConfig::Log => Some(log(NotificationChannel::Email)),
+        EmailTransportConfig::Smtp(smtp) => Some(Arc::new(smtp::SmtpNotifier::new(smtp)?)),
+    };
+
+    Ok(Notifiers { sms, email })
+}
@@ -0,0 +1,57 @@
+use super::{Message, Notifier, NotifyError, NotifyFuture};
+use crate::config::SmtpConfig;
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+
+/// Sends emails through an SMTP relay using STARTTLS
+pub struct SmtpNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpNotifier {
+    pub fn new(config: &SmtpConfig) -> Result<Self, String> {
+        let from = config
+            .from
+            .parse::<Mailbox>()
+            .map_err(|e| format!("Invalid SMTP_FROM address: {}", e))?;
+
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+            .map_err(|e| format!("Invalid SMTP_HOST: {}", e))?
+            .port(config.port);
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+impl Notifier for SmtpNotifier {
+    fn send<'a>(&'a self, message: &'a Message) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            let to = message
+                .recipient
+                .parse::<Mailbox>()
+                .map_err(|e| NotifyError::Rejected(format!("Invalid recipient: {}", e)))?;
+
+            let email = lettre::Message::builder()
+                .from(self.from.clone())
+                .to(to)
+                .subject(message.subject.clone())
+                .header(ContentType::TEXT_PLAIN)
+                .body(message.body.clone())
+                .map_err(|e| NotifyError::Rejected(e.to_string()))?;
+
+            match self.transport.send(email).await {
+                Ok(_) => Ok(()),
+                Err(e) if e.is_permanent() => Err(NotifyError::Rejected(e.to_string())),
+                Err(e) => Err(NotifyError::Unavailable(e.to_string())),
+            }
+        })
+    }
+}
@@ -0,0 +1,64 @@
+use crate::utils::money::format_currency;
+use shared::models::{NotificationChannel, Order, OrderStatus};
+
+/// Name messages are signed with
+const SHOP_NAME: &str = "Royal Pizza";
+
+/// Subject and body of the message sent when an order reaches its status
+///
+/// Only the statuses a customer acts on are announced; `None` for the rest.
+/// Text messages are kept to a single SMS segment where possible.
+pub fn render(order: &Order, channel: NotificationChannel) -> Option<(String, String)> {
+    let pickup = order.pickup_time.format("%H:%M");
+    let number = &order.order_number;
+
+    let (subject, short, long) = match order.status {
+        OrderStatus::Confirmed => (
+            format!("Your order {} is confirmed", number),
+            format!(
+                "{}: order {} is confirmed. Pickup at {}.",
+                SHOP_NAME, number, pickup
+            ),
+            format!(
+                "We received your order {} and will have it ready for pickup at {}.",
+                number, pickup
+            ),
+        ),
+        OrderStatus::Ready => (
+            format!("Your order {} is ready", number),
+            format!("{}: order {} is ready for pickup!", SHOP_NAME, number),
+            format!(
+                "Your order {} is ready. Come pick it up while it is hot!",
+                number
+            ),
+        ),
+        OrderStatus::Cancelled => {
+            let refund = if order.refunded_amount > 0.0 {
+                format!(
+                    " {} has been refunded.",
+                    format_currency(order.refunded_amount)
+                )
+            } else {
+                String::new()
+            };
+            (
+                format!("Your order {} was cancelled", number),
+                format!("{}: order {} was cancelled.{}", SHOP_NAME, number, refund),
+                format!(
+                    "Your order {} was cancelled.{} Contact us if you did not expect this.",
+                    number, refund
+                ),
+            )
+        }
+        _ => return None,
+    };
+
+    let body = match channel {
+        NotificationChannel::Sms => short,
+        NotificationChannel::Email => {
+            format!("Hi {},\n\n{}\n\n{}\n", order.customer.name, long, SHOP_NAME)
+        }
+    };
+
+    Some((subject, body))
+}
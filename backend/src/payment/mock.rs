@@ -0,0 +1,329 @@
+use super::{
+    verify_signature, PaymentError, PaymentEvent, PaymentFuture, PaymentOutcome, PaymentProvider,
+    PaymentRequest, PaymentSession, RefundRequest,
+};
+use axum::http::HeaderMap;
+use chrono::Utc;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// What the mock provider does with new payments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum MockOutcome {
+    /// Report success after the configured delay
+    Succeed,
+    /// Report a declined payment after the configured delay
+    Fail,
+    /// Refuse to start payments or refunds at all
+    Unavailable,
+    /// Report nothing; events are posted to the webhook by hand
+    Manual,
+}
+
+impl FromStr for MockOutcome {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "succeed" => Ok(MockOutcome::Succeed),
+            "fail" => Ok(MockOutcome::Fail),
+            "unavailable" => Ok(MockOutcome::Unavailable),
+            "manual" => Ok(MockOutcome::Manual),
+            other => Err(format!(
+                "unknown mock outcome '{}', expected succeed, fail, unavailable or manual",
+                other
+            )),
+        }
+    }
+}
+
+/// Local stand-in for a payment provider, for development and tests
+///
+/// Payments never leave the process. Depending on the configured outcome the
+/// mock reports success or failure on its event channel after a delay, the
+/// same way a real provider would call the webhook some time after checkout.
+pub struct MockPaymentProvider {
+    outcome: MockOutcome,
+    delay: Duration,
+    /// Secret webhook calls must be signed with
+    webhook_secret: Option<String>,
+    events: mpsc::UnboundedSender<PaymentEvent>,
+    /// Payments started by this instance that have not been reported yet,
+    /// mapped to their order ids; entries are removed once the outcome is out
+    payments: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl MockPaymentProvider {
+    pub fn new(
+        outcome: MockOutcome,
+        delay: Duration,
+        webhook_secret: Option<String>,
+        events: mpsc::UnboundedSender<PaymentEvent>,
+    ) -> Self {
+        Self {
+            outcome,
+            delay,
+            webhook_secret,
+            events,
+            payments: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl PaymentProvider for MockPaymentProvider {
+    fn create_payment(&self, request: PaymentRequest) -> PaymentFuture<'_, PaymentSession> {
+        Box::pin(async move {
+            let reported = match self.outcome {
+                MockOutcome::Succeed => Some(PaymentOutcome::Succeeded),
+                MockOutcome::Fail => Some(PaymentOutcome::Failed),
+                MockOutcome::Manual => None,
+                MockOutcome::Unavailable => {
+                    return Err(PaymentError::Unavailable(
+                        "mock provider configured as unavailable".to_string(),
+                    ));
+                }
+            };
+
+            let payment_id = format!("mock_{}", Uuid::new_v4().simple());
+            self.payments
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .insert(payment_id.clone(), request.order_id.clone());
+
+            tracing::info!(
+                "Mock payment {} started for order {} over {:.2}",
+                payment_id,
+                request.order_number,
+                request.amount
+            );
+
+            if let Some(outcome) = reported {
+                let event = PaymentEvent {
+                    payment_id: payment_id.clone(),
+                    order_id: request.order_id,
+                    outcome,
+                };
+                let events = self.events.clone();
+                let payments = Arc::clone(&self.payments);
+                let delay = self.delay;
+                tokio::spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    payments
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .remove(&event.payment_id);
+                    // The receiver only goes away on shutdown
+                    let _ = events.send(event);
+                });
+            }
+
+            Ok(PaymentSession {
+                payment_id,
+                checkout_url: None,
+            })
+        })
+    }
+
+    fn refund(&self, request: RefundRequest) -> PaymentFuture<'_, String> {
+        Box::pin(async move {
+            if self.outcome == MockOutcome::Unavailable {
+                return Err(PaymentError::Unavailable(
+                    "mock provider configured as unavailable".to_string(),
+                ));
+            }
+
+            let refund_id = format!("mock_refund_{}", Uuid::new_v4().simple());
+            tracing::info!(
+                "Mock refund {} of {:.2} for payment {} of order {}",
+                refund_id,
+                request.amount,
+                request.payment_id,
+                request.order_id
+            );
+
+            Ok(refund_id)
+        })
+    }
+
+    fn parse_webhook(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<PaymentEvent, PaymentError> {
+        let secret = self.webhook_secret.as_deref().ok_or_else(|| {
+            PaymentError::InvalidWebhook("no webhook secret configured".to_string())
+        })?;
+        verify_signature(secret, headers, body, Utc::now().timestamp())?;
+
+        let event: PaymentEvent = serde_json::from_slice(body)
+            .map_err(|e| PaymentError::InvalidWebhook(e.to_string()))?;
+
+        let mut payments = self
+            .payments
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match payments.get(&event.payment_id) {
+            Some(order_id) if *order_id == event.order_id => {
+                // Every outcome is final, so the payment cannot be reported twice
+                payments.remove(&event.payment_id);
+                Ok(event)
+            }
+            Some(_) => Err(PaymentError::InvalidWebhook(format!(
+                "payment {} belongs to another order",
+                event.payment_id
+            ))),
+            None => Err(PaymentError::InvalidWebhook(format!(
+                "unknown payment {}",
+                event.payment_id
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payment::tests::signed;
+
+    const SECRET: &str = "whsec_test";
+
+    fn provider(
+        outcome: MockOutcome,
+    ) -> (MockPaymentProvider, mpsc::UnboundedReceiver<PaymentEvent>) {
+        let (sender, events) = mpsc::unbounded_channel();
+        let provider =
+            MockPaymentProvider::new(outcome, Duration::ZERO, Some(SECRET.to_string()), sender);
+        (provider, events)
+    }
+
+    fn request() -> PaymentRequest {
+        PaymentRequest {
+            order_id: "order-1".to_string(),
+            order_number: "RP-0042".to_string(),
+            amount: 41.0,
+        }
+    }
+
+    fn refund_request() -> RefundRequest {
+        RefundRequest {
+            payment_id: "mock_1".to_string(),
+            order_id: "order-1".to_string(),
+            amount: 12.5,
+        }
+    }
+
+    fn webhook_body(payment_id: &str, order_id: &str) -> Vec<u8> {
+        format!(
+            r#"{{"payment_id":"{}","order_id":"{}","outcome":"Succeeded"}}"#,
+            payment_id, order_id
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn test_outcome_from_str() {
+        assert_eq!("Succeed".parse::<MockOutcome>(), Ok(MockOutcome::Succeed));
+        assert_eq!("fail".parse::<MockOutcome>(), Ok(MockOutcome::Fail));
+        assert_eq!(
+            "unavailable".parse::<MockOutcome>(),
+            Ok(MockOutcome::Unavailable)
+        );
+        assert_eq!("MANUAL".parse::<MockOutcome>(), Ok(MockOutcome::Manual));
+        assert!("later".parse::<MockOutcome>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_succeed_reports_success() {
+        let (provider, mut events) = provider(MockOutcome::Succeed);
+
+        let session = provider.create_payment(request()).await.unwrap();
+        let event = events.recv().await.unwrap();
+
+        assert_eq!(event.payment_id, session.payment_id);
+        assert_eq!(event.order_id, "order-1");
+        assert_eq!(event.outcome, PaymentOutcome::Succeeded);
+        assert!(provider.payments.lock().unwrap().is_empty());
+        assert!(provider.refund(refund_request()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fail_reports_failure() {
+        let (provider, mut events) = provider(MockOutcome::Fail);
+
+        let session = provider.create_payment(request()).await.unwrap();
+        let event = events.recv().await.unwrap();
+
+        assert_eq!(event.payment_id, session.payment_id);
+        assert_eq!(event.outcome, PaymentOutcome::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_refuses_payments_and_refunds() {
+        let (provider, mut events) = provider(MockOutcome::Unavailable);
+
+        assert!(matches!(
+            provider.create_payment(request()).await,
+            Err(PaymentError::Unavailable(_))
+        ));
+        assert!(matches!(
+            provider.refund(refund_request()).await,
+            Err(PaymentError::Unavailable(_))
+        ));
+        drop(provider);
+        assert!(events.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_manual_waits_for_signed_webhook() {
+        let (provider, mut events) = provider(MockOutcome::Manual);
+
+        let session = provider.create_payment(request()).await.unwrap();
+        assert!(events.try_recv().is_err());
+
+        let body = webhook_body(&session.payment_id, "order-1");
+        let headers = signed(SECRET, Utc::now().timestamp(), &body);
+        let event = provider.parse_webhook(&headers, &body).unwrap();
+        assert_eq!(event.payment_id, session.payment_id);
+        assert_eq!(event.outcome, PaymentOutcome::Succeeded);
+
+        // The outcome is final, a second report is refused
+        assert!(provider.payments.lock().unwrap().is_empty());
+        assert!(provider.parse_webhook(&headers, &body).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_rejects_unknown_or_mismatched_payments() {
+        let (provider, _events) = provider(MockOutcome::Manual);
+        let session = provider.create_payment(request()).await.unwrap();
+        let now = Utc::now().timestamp();
+
+        let body = webhook_body("mock_unknown", "order-1");
+        assert!(provider
+            .parse_webhook(&signed(SECRET, now, &body), &body)
+            .is_err());
+
+        let body = webhook_body(&session.payment_id, "order-2");
+        assert!(provider
+            .parse_webhook(&signed(SECRET, now, &body), &body)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_rejects_calls_without_configured_secret() {
+        let (sender, _events) = mpsc::unbounded_channel();
+        let provider = MockPaymentProvider::new(MockOutcome::Manual, Duration::ZERO, None, sender);
+        let session = provider.create_payment(request()).await.unwrap();
+
+        let body = webhook_body(&session.payment_id, "order-1");
+        let headers = signed(SECRET, Utc::now().timestamp(), &body);
+        assert!(matches!(
+            provider.parse_webhook(&headers, &body),
+            Err(PaymentError::InvalidWebhook(_))
+        ));
+    }
+}
@@ -0,0 +1,231 @@
+//! Online payment providers
+//!
+//! Creating a payment only starts it. The outcome arrives later as a
+//! [`PaymentEvent`], either through the payment webhook endpoint or, for
+//! providers that deliver events in-process like the mock, through the event
+//! channel returned by [`create_provider`]. Refunds complete synchronously.
+
+pub mod mock;
+
+use crate::config::PaymentConfig;
+use crate::middleware::error::AppError;
+use axum::http::HeaderMap;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Header with the signature of a webhook call, `t=<unix time>,v1=<hex HMAC-SHA256>`
+pub const SIGNATURE_HEADER: &str = "X-Payment-Signature";
+
+/// How far the signed timestamp may be from the current time, in seconds
+const SIGNATURE_TOLERANCE: i64 = 300;
+
+/// Boxed future returned by provider calls, so providers can be used as trait objects
+pub type PaymentFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, PaymentError>> + Send + 'a>>;
+
+/// Receiving end of events delivered by the provider itself
+pub type PaymentEvents = mpsc::UnboundedReceiver<PaymentEvent>;
+
+/// Amount to collect for an order
+#[derive(Debug, Clone)]
+pub struct PaymentRequest {
+    pub order_id: String,
+    pub order_number: String,
+    pub amount: f64,
+}
+
+/// A payment started at the provider
+#[derive(Debug, Clone)]
+pub struct PaymentSession {
+    pub payment_id: String,
+    /// Hosted checkout page, if the provider has one
+    pub checkout_url: Option<String>,
+}
+
+/// Money to return for a completed payment
+#[derive(Debug, Clone)]
+pub struct RefundRequest {
+    pub payment_id: String,
+    pub order_id: String,
+    pub amount: f64,
+}
+
+/// Final result of a payment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum PaymentOutcome {
+    Succeeded,
+    Failed,
+}
+
+/// Notification from the provider about a payment
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaymentEvent {
+    pub payment_id: String,
+    pub order_id: String,
+    pub outcome: PaymentOutcome,
+}
+
+#[derive(Debug)]
+pub enum PaymentError {
+    /// The provider could not be reached or refused the request
+    Unavailable(String),
+    /// A webhook call could not be parsed or verified
+    InvalidWebhook(String),
+}
+
+impl fmt::Display for PaymentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaymentError::Unavailable(msg) => write!(f, "Payment provider unavailable: {}", msg),
+            PaymentError::InvalidWebhook(msg) => write!(f, "Invalid payment webhook: {}", msg),
+        }
+    }
+}
+
+impl From<PaymentError> for AppError {
+    fn from(err: PaymentError) -> Self {
+        match err {
+            PaymentError::Unavailable(_) => AppError::InternalError(err.to_string()),
+            PaymentError::InvalidWebhook(_) => AppError::ValidationError(vec![err.to_string()]),
+        }
+    }
+}
+
+/// Payment service provider such as a card processor
+pub trait PaymentProvider: Send + Sync {
+    /// Starts collecting the amount of an order
+    fn create_payment(&self, request: PaymentRequest) -> PaymentFuture<'_, PaymentSession>;
+
+    /// Returns part or all of a payment, yielding the provider's refund reference
+    fn refund(&self, request: RefundRequest) -> PaymentFuture<'_, String>;
+
+    /// Verifies and decodes a call to the payment webhook
+    fn parse_webhook(&self, headers: &HeaderMap, body: &[u8])
+        -> Result<PaymentEvent, PaymentError>;
+}
+
+/// Builds the configured provider and the channel of events it delivers itself
+pub fn create_provider(config: &PaymentConfig) -> (Arc<dyn PaymentProvider>, PaymentEvents) {
+    let (sender, events) = mpsc::unbounded_channel();
+    let provider = mock::MockPaymentProvider::new(
+        config.mock_outcome,
+        config.mock_delay,
+        config.webhook_secret.clone(),
+        sender,
+    );
+
+    (Arc::new(provider), events)
+}
+
+/// Checks the signature header of a webhook call against the shared secret
+///
+/// Uses the scheme of our outgoing webhooks: an HMAC-SHA256 over
+/// `<timestamp>.<body>`. Calls signed more than five minutes before or after
+/// `now` are rejected, so a captured call cannot be replayed later.
+pub fn verify_signature(
+    secret: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+    now: i64,
+) -> Result<(), PaymentError> {
+    let header = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| {
+            PaymentError::InvalidWebhook(format!("missing {} header", SIGNATURE_HEADER))
+        })?;
+
+    let mut timestamp = None;
+    let mut signature = None;
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signature = decode_hex(value),
+            _ => {}
+        }
+    }
+    let (Some(timestamp), Some(signature)) = (timestamp, signature) else {
+        return Err(PaymentError::InvalidWebhook(format!(
+            "malformed {} header",
+            SIGNATURE_HEADER
+        )));
+    };
+
+    if (now - timestamp).abs() > SIGNATURE_TOLERANCE {
+        return Err(PaymentError::InvalidWebhook(
+            "signature timestamp outside the allowed window".to_string(),
+        ));
+    }
+
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.", timestamp).as_bytes());
+    mac.update(body);
+    // verify_slice compares in constant time
+    mac.verify_slice(&signature)
+        .map_err(|_| PaymentError::InvalidWebhook("signature mismatch".to_string()))
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) || !value.is_ascii() {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "whsec_test";
+    const BODY: &[u8] = br#"{"payment_id":"mock_1","order_id":"order-1","outcome":"Succeeded"}"#;
+
+    pub(super) fn signed(secret: &str, timestamp: i64, body: &[u8]) -> HeaderMap {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{}.", timestamp).as_bytes());
+        mac.update(body);
+        let value = format!("t={},v1={:x}", timestamp, mac.finalize().into_bytes());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_verify_signature_accepts_signed_body() {
+        let headers = signed(SECRET, 1_000, BODY);
+        assert!(verify_signature(SECRET, &headers, BODY, 1_060).is_ok());
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_other_secret_or_body() {
+        let headers = signed("whsec_other", 1_000, BODY);
+        assert!(verify_signature(SECRET, &headers, BODY, 1_000).is_err());
+
+        let headers = signed(SECRET, 1_000, BODY);
+        assert!(verify_signature(SECRET, &headers, b"{}", 1_000).is_err());
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_stale_timestamp() {
+        let headers = signed(SECRET, 1_000, BODY);
+        assert!(verify_signature(SECRET, &headers, BODY, 1_000 + SIGNATURE_TOLERANCE + 1).is_err());
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_missing_or_malformed_header() {
+        assert!(verify_signature(SECRET, &HeaderMap::new(), BODY, 1_000).is_err());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, "t=1000,v1=zz".parse().unwrap());
+        assert!(verify_signature(SECRET, &headers, BODY, 1_000).is_err());
+    }
+}
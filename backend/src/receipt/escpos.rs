@@ -0,0 +1,56 @@
+use super::{to_ascii, Align, PrintedLine};
+
+const ESC: u8 = 0x1B;
+const GS: u8 = 0x1D;
+const LF: u8 = 0x0A;
+
+/// ESC @: reset the printer to its power-on state
+const INITIALIZE: [u8; 2] = [ESC, b'@'];
+
+/// ESC d 4: feed paper so the last line clears the cutter
+const FEED: [u8; 3] = [ESC, b'd', 4];
+
+/// GS V 1: partial cut
+const CUT: [u8; 3] = [GS, b'V', 1];
+
+/// ESC/POS command stream for 80mm thermal printers
+///
+/// Style commands are only emitted when the style changes, which keeps the
+/// output stable for byte comparisons. Text is sent as ASCII, since code pages
+/// differ between printers.
+pub fn render(lines: &[PrintedLine]) -> Vec<u8> {
+    let mut out = INITIALIZE.to_vec();
+    let mut align = Align::Left;
+    let mut bold = false;
+    let mut large = false;
+
+    for line in lines {
+        if line.style.align != align {
+            align = line.style.align;
+            out.extend_from_slice(&[ESC, b'a', align_code(align)]);
+        }
+        if line.style.bold != bold {
+            bold = line.style.bold;
+            out.extend_from_slice(&[ESC, b'E', u8::from(bold)]);
+        }
+        if line.style.large != large {
+            large = line.style.large;
+            // GS ! n: upper nibble is width, lower nibble height multiplier
+            out.extend_from_slice(&[GS, b'!', if large { 0x11 } else { 0x00 }]);
+        }
+
+        out.extend_from_slice(to_ascii(&line.text).as_bytes());
+        out.push(LF);
+    }
+
+    out.extend_from_slice(&FEED);
+    out.extend_from_slice(&CUT);
+    out
+}
+
+fn align_code(align: Align) -> u8 {
+    match align {
+        Align::Left => 0,
+        Align::Center => 1,
+    }
+}
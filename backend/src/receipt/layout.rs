@@ -0,0 +1,145 @@
+use super::{Align, PrintedLine, ReceiptLine, Style, LINE_WIDTH};
+
+/// Wraps and pads receipt lines to physical lines
+pub fn lay_out(lines: &[ReceiptLine]) -> Vec<PrintedLine> {
+    let mut printed = Vec::new();
+
+    for line in lines {
+        match line {
+            ReceiptLine::Text { text, style } => {
+                for chunk in wrap(text, style.width()) {
+                    printed.push(PrintedLine {
+                        text: chunk,
+                        style: *style,
+                    });
+                }
+            }
+            ReceiptLine::Columns { left, right, style } => {
+                let width = style.width();
+                let left_width = width.saturating_sub(right.chars().count() + 1);
+                let mut left_lines = wrap(left, left_width);
+                let last = left_lines.pop().unwrap_or_default();
+
+                for chunk in left_lines {
+                    printed.push(PrintedLine {
+                        text: chunk,
+                        style: Style {
+                            align: Align::Left,
+                            ..*style
+                        },
+                    });
+                }
+                printed.push(PrintedLine {
+                    text: format!("{:<left_width$} {}", last, right),
+                    style: Style {
+                        align: Align::Left,
+                        ..*style
+                    },
+                });
+            }
+            ReceiptLine::Separator => printed.push(PrintedLine {
+                text: "-".repeat(LINE_WIDTH),
+                style: Style::NORMAL,
+            }),
+            ReceiptLine::Blank => printed.push(PrintedLine {
+                text: String::new(),
+                style: Style::NORMAL,
+            }),
+        }
+    }
+
+    printed
+}
+
+/// Greedy word wrap; words longer than the width are split
+///
+/// Widths are counted in characters, so names with accents line up like any
+/// other text.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+
+    for word in text.split_whitespace() {
+        let mut chars: Vec<char> = word.chars().collect();
+        while chars.len() > width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+                current_len = 0;
+            }
+            let rest = chars.split_off(width);
+            lines.push(chars.into_iter().collect());
+            chars = rest;
+        }
+
+        let word: String = chars.iter().collect();
+        if current.is_empty() {
+            current = word;
+            current_len = chars.len();
+        } else if current_len + 1 + chars.len() <= width {
+            current.push(' ');
+            current.push_str(&word);
+            current_len += 1 + chars.len();
+        } else {
+            lines.push(std::mem::replace(&mut current, word));
+            current_len = chars.len();
+        }
+    }
+
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_splits_words_longer_than_width() {
+        assert_eq!(
+            wrap("extra anchovies", 4),
+            vec!["extr", "a", "anch", "ovie", "s"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_counts_characters_not_bytes() {
+        assert_eq!(
+            wrap("Crème brûlée calzone", 12),
+            vec!["Crème brûlée", "calzone"]
+        );
+        assert_eq!(wrap("Überfüllung", 5), vec!["Überf", "üllun", "g"]);
+    }
+
+    #[test]
+    fn test_wrap_keeps_one_line_for_empty_text() {
+        assert_eq!(wrap("   ", 10), vec![""]);
+    }
+
+    #[test]
+    fn test_columns_wrap_left_text_above_right_text() {
+        let printed = lay_out(&[ReceiptLine::Columns {
+            left: "1 x Quattro Formaggi with extra mozzarella - Large".to_string(),
+            right: "$18.00".to_string(),
+            style: Style::NORMAL.centered(),
+        }]);
+
+        assert_eq!(
+            printed,
+            vec![
+                PrintedLine {
+                    text: "1 x Quattro Formaggi with extra".to_string(),
+                    style: Style::NORMAL,
+                },
+                PrintedLine {
+                    text: "mozzarella - Large                  $18.00".to_string(),
+                    style: Style::NORMAL,
+                },
+            ]
+        );
+    }
+}
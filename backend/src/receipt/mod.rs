@@ -0,0 +1,353 @@
+//! Printable receipts and kitchen tickets
+//!
+//! A receipt is first described as a list of format-agnostic [`ReceiptLine`]s,
+//! then laid out to a fixed character width and handed to a renderer. All
+//! renderers are pure functions of the order, so their output can be compared
+//! byte for byte without a printer attached.
+
+pub mod escpos;
+pub mod layout;
+pub mod pdf;
+pub mod text;
+pub mod ticket;
+
+use chrono_tz::Tz;
+use serde::Deserialize;
+use shared::models::Order;
+use std::collections::HashMap;
+
+/// Renders one copy of an order in the requested format
+///
+/// `pizza_names` maps menu pizza ids to display names; unknown ids fall back
+/// to the id itself so a receipt can still be printed for retired pizzas.
+/// Times are printed in `timezone`, the shop's local time.
+pub fn render_order(
+    order: &Order,
+    pizza_names: &HashMap<String, String>,
+    copy: ReceiptCopy,
+    format: ReceiptFormat,
+    timezone: Tz,
+) -> Vec<u8> {
+    let lines = match copy {
+        ReceiptCopy::Customer => ticket::customer_receipt(order, pizza_names, timezone),
+        ReceiptCopy::Kitchen => ticket::kitchen_ticket(order, pizza_names, timezone),
+    };
+
+    format.render(&layout::lay_out(&lines))
+}
+
+/// Characters per line in normal print (80mm paper, font A)
+pub const LINE_WIDTH: usize = 42;
+
+/// Characters per line in double width print
+pub const LARGE_LINE_WIDTH: usize = LINE_WIDTH / 2;
+
+/// Output format of a receipt
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReceiptFormat {
+    Pdf,
+    Escpos,
+    #[default]
+    Txt,
+}
+
+impl ReceiptFormat {
+    /// MIME type of the rendered output
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ReceiptFormat::Pdf => "application/pdf",
+            ReceiptFormat::Escpos => "application/octet-stream",
+            ReceiptFormat::Txt => "text/plain; charset=utf-8",
+        }
+    }
+
+    /// File extension of the rendered output
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReceiptFormat::Pdf => "pdf",
+            ReceiptFormat::Escpos => "bin",
+            ReceiptFormat::Txt => "txt",
+        }
+    }
+
+    /// Render laid out lines in this format
+    pub fn render(&self, lines: &[PrintedLine]) -> Vec<u8> {
+        match self {
+            ReceiptFormat::Pdf => pdf::render(lines),
+            ReceiptFormat::Escpos => escpos::render(lines),
+            ReceiptFormat::Txt => text::render(lines),
+        }
+    }
+}
+
+/// Which copy of the ticket to print
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReceiptCopy {
+    #[default]
+    Customer,
+    Kitchen,
+}
+
+impl ReceiptCopy {
+    /// Short name used in file names
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReceiptCopy::Customer => "receipt",
+            ReceiptCopy::Kitchen => "kitchen",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+}
+
+/// Text styling shared by all renderers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub align: Align,
+    pub bold: bool,
+    pub large: bool,
+}
+
+impl Style {
+    pub const NORMAL: Style = Style {
+        align: Align::Left,
+        bold: false,
+        large: false,
+    };
+
+    pub fn bold(self) -> Self {
+        Style { bold: true, ..self }
+    }
+
+    pub fn large(self) -> Self {
+        Style {
+            large: true,
+            ..self
+        }
+    }
+
+    pub fn centered(self) -> Self {
+        Style {
+            align: Align::Center,
+            ..self
+        }
+    }
+
+    /// Characters available per line for this style
+    pub fn width(&self) -> usize {
+        if self.large {
+            LARGE_LINE_WIDTH
+        } else {
+            LINE_WIDTH
+        }
+    }
+}
+
+/// Replaces characters printers and the built-in PDF fonts cannot show
+///
+/// Only the ESC/POS and PDF renderers need this; plain text stays UTF-8.
+fn to_ascii(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii() && !c.is_ascii_control() {
+                c
+            } else {
+                '?'
+            }
+        })
+        .collect()
+}
+
+/// Logical content of a receipt, before wrapping
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReceiptLine {
+    /// Free text, wrapped to the line width
+    Text {
+        text: String,
+        style: Style,
+    },
+    /// Label on the left and amount on the right of the same line
+    Columns {
+        left: String,
+        right: String,
+        style: Style,
+    },
+    Separator,
+    Blank,
+}
+
+/// A single physical line, already wrapped to the width of its style
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrintedLine {
+    pub text: String,
+    pub style: Style,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use shared::models::{
+        CustomPizza, CustomerInfo, NotificationConsent, OrderItem, OrderItemType, OrderStatus,
+        PaymentMethod, PaymentStatus, PizzaSize, TaxBreakdown, TaxCategory, TaxRateTotal,
+    };
+
+    fn order() -> Order {
+        let mut margherita = OrderItem::new(
+            "item-1".to_string(),
+            OrderItemType::StandardPizza {
+                pizza_id: "margherita".to_string(),
+                size: PizzaSize::Medium,
+            },
+            2,
+            12.5,
+        );
+        margherita.product_name = Some("Margherita".to_string());
+        margherita.size_label = Some("Medium".to_string());
+
+        let custom = OrderItem::new(
+            "item-2".to_string(),
+            OrderItemType::CustomPizza {
+                custom: CustomPizza {
+                    instructions: "Half mushrooms, half peppers, well done and cut into squares"
+                        .to_string(),
+                    size: PizzaSize::Large,
+                },
+            },
+            1,
+            16.0,
+        );
+
+        Order {
+            id: "order-1".to_string(),
+            order_number: "RP-0042".to_string(),
+            customer: CustomerInfo {
+                name: "Zoë Example".to_string(),
+                phone: "555 0100".to_string(),
+                normalized_phone: None,
+            },
+            items: vec![margherita, custom],
+            pickup_time: Utc.with_ymd_and_hms(2026, 3, 14, 12, 30, 0).unwrap(),
+            status: OrderStatus::Confirmed,
+            total_amount: 41.0,
+            tax_breakdown: Some(TaxBreakdown {
+                prices_include_tax: true,
+                rates: vec![TaxRateTotal {
+                    category: TaxCategory::TakeawayFood,
+                    rate: 0.07,
+                    net: 38.32,
+                    tax: 2.68,
+                    gross: 41.0,
+                }],
+                net_total: 38.32,
+                tax_total: 2.68,
+                gross_total: 41.0,
+            }),
+            payment_method: PaymentMethod::PayAtPickup,
+            payment_status: PaymentStatus::Unpaid,
+            payment_id: None,
+            refunded_amount: 0.0,
+            notifications: NotificationConsent::default(),
+            created_at: Utc.with_ymd_and_hms(2026, 3, 14, 11, 5, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_customer_receipt_text() {
+        let rendered = render_order(
+            &order(),
+            &HashMap::new(),
+            ReceiptCopy::Customer,
+            ReceiptFormat::Txt,
+            Tz::UTC,
+        );
+
+        let expected = [
+            "               ROYAL PIZZA",
+            "             Customer Receipt",
+            "",
+            "Order                              RP-0042",
+            "Date                  2026-03-14 11:05 UTC",
+            "Pickup                2026-03-14 12:30 UTC",
+            "Customer                       Zoë Example",
+            "------------------------------------------",
+            "2 x Margherita - Medium             $25.00",
+            "@ $12.50",
+            "1 x Custom Pizza - Large            $16.00",
+            "Note: Half mushrooms, half peppers, well",
+            "done and cut into squares",
+            "------------------------------------------",
+            "Tax Takeaway Food 7%                 $2.68",
+            "Net                                 $38.32",
+            "Tax                                  $2.68",
+            "TOTAL          $41.00",
+            "Payment             Pay at Pickup (Unpaid)",
+            "Prices include tax.",
+            "",
+            "        Thank you for your order!",
+        ];
+
+        assert_eq!(
+            String::from_utf8(rendered).unwrap(),
+            expected.join("\n") + "\n"
+        );
+    }
+
+    #[test]
+    fn test_kitchen_ticket_escpos() {
+        let rendered = render_order(
+            &order(),
+            &HashMap::new(),
+            ReceiptCopy::Kitchen,
+            ReceiptFormat::Escpos,
+            Tz::UTC,
+        );
+
+        let expected: Vec<u8> = [
+            &b"\x1b@"[..],
+            b"\x1ba\x01\x1bE\x01KITCHEN\n",
+            b"\x1d!\x11RP-0042\n",
+            b"\x1ba\x00\x1bE\x00\x1d!\x00\n",
+            b"\x1bE\x01\x1d!\x11PICKUP 12:30\n",
+            b"\x1bE\x00\x1d!\x002026-03-14 12:30 UTC\n",
+            b"Customer                       Zo? Example\n",
+            b"------------------------------------------\n",
+            b"\x1bE\x01\x1d!\x112 x Margherita -\n",
+            b"Medium\n",
+            b"\x1bE\x00\x1d!\x00\n",
+            b"\x1bE\x01\x1d!\x111 x Custom Pizza -\n",
+            b"Large\n",
+            b"\x1bE\x00Half mushrooms, half\n",
+            b"peppers, well done\n",
+            b"and cut into squares\n",
+            b"\x1d!\x00\n",
+            b"------------------------------------------\n",
+            b"\x1bE\x01Items                                    3\n",
+            b"\x1bd\x04\x1dV\x01",
+        ]
+        .concat();
+
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn test_kitchen_ticket_uses_shop_time_zone() {
+        let rendered = render_order(
+            &order(),
+            &HashMap::new(),
+            ReceiptCopy::Kitchen,
+            ReceiptFormat::Txt,
+            Tz::Europe__Amsterdam,
+        );
+        let rendered = String::from_utf8(rendered).unwrap();
+
+        assert!(rendered.contains("\nPICKUP 13:30\n"));
+        assert!(rendered.contains("\n2026-03-14 13:30 CET\n"));
+    }
+}
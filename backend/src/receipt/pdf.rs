@@ -0,0 +1,113 @@
+use super::{to_ascii, Align, PrintedLine, LINE_WIDTH};
+
+/// 80mm receipt roll in PDF points
+const PAGE_WIDTH: f64 = 226.8;
+const MARGIN: f64 = 12.0;
+
+/// Courier glyphs are 0.6 em wide, so this fits LINE_WIDTH between the margins
+const FONT_SIZE: f64 = (PAGE_WIDTH - 2.0 * MARGIN) / (LINE_WIDTH as f64 * 0.6);
+const LARGE_FONT_SIZE: f64 = FONT_SIZE * 2.0;
+const LINE_SPACING: f64 = 1.2;
+
+/// Single page PDF using the built-in Courier fonts
+///
+/// The page is as tall as the receipt, like a strip torn from a roll. No
+/// fonts are embedded, which keeps the output small and deterministic.
+pub fn render(lines: &[PrintedLine]) -> Vec<u8> {
+    let height: f64 = lines.iter().map(line_height).sum::<f64>() + 2.0 * MARGIN;
+
+    let mut content = String::new();
+    let mut y = height - MARGIN;
+    for line in lines {
+        let size = font_size(line);
+        y -= line_height(line);
+
+        if line.text.is_empty() {
+            continue;
+        }
+
+        // The built-in fonts only cover ASCII without an embedded encoding
+        let text = to_ascii(&line.text);
+        let text_width = text.len() as f64 * size * 0.6;
+        let x = match line.style.align {
+            Align::Left => MARGIN,
+            Align::Center => (PAGE_WIDTH - text_width) / 2.0,
+        };
+        let font = if line.style.bold { "F2" } else { "F1" };
+
+        content.push_str(&format!(
+            "BT /{} {:.2} Tf {:.2} {:.2} Td ({}) Tj ET\n",
+            font,
+            size,
+            x,
+            // Baseline sits above the bottom of the line box
+            y + size * 0.25,
+            escape(&text)
+        ));
+    }
+
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] \
+             /Resources << /Font << /F1 4 0 R /F2 5 0 R >> >> /Contents 6 0 R >>",
+            PAGE_WIDTH, height
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier-Bold >>".to_string(),
+        format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ),
+    ];
+
+    let mut out = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).as_bytes());
+    }
+
+    let xref_offset = out.len();
+    out.extend_from_slice(format!("xref\n0 {}\n", objects.len() + 1).as_bytes());
+    out.extend_from_slice(b"0000000000 65535 f \n");
+    for offset in offsets {
+        out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    out.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+
+    out
+}
+
+fn font_size(line: &PrintedLine) -> f64 {
+    if line.style.large {
+        LARGE_FONT_SIZE
+    } else {
+        FONT_SIZE
+    }
+}
+
+fn line_height(line: &PrintedLine) -> f64 {
+    font_size(line) * LINE_SPACING
+}
+
+/// Escapes the characters with a meaning inside PDF string literals
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '(' | ')' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,20 @@
+use super::{Align, PrintedLine, LINE_WIDTH};
+
+/// Plain text rendering, one line per printed line
+///
+/// Large lines cannot be enlarged in plain text, so they are only centered
+/// within the normal line width like any other line.
+pub fn render(lines: &[PrintedLine]) -> Vec<u8> {
+    let mut out = String::new();
+
+    for line in lines {
+        let text = match line.style.align {
+            Align::Left => line.text.clone(),
+            Align::Center => format!("{:^LINE_WIDTH$}", line.text),
+        };
+        out.push_str(text.trim_end());
+        out.push('\n');
+    }
+
+    out.into_bytes()
+}
@@ -0,0 +1,210 @@
+use super::{ReceiptLine, Style};
+use crate::utils::money::{format_currency, format_percent};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use shared::models::{Order, OrderItem, OrderItemType};
+use std::collections::HashMap;
+
+const SHOP_NAME: &str = "ROYAL PIZZA";
+
+/// Customer copy: items with prices, tax breakdown and total
+pub fn customer_receipt(
+    order: &Order,
+    pizza_names: &HashMap<String, String>,
+    timezone: Tz,
+) -> Vec<ReceiptLine> {
+    let mut lines = vec![
+        text(SHOP_NAME, Style::NORMAL.large().bold().centered()),
+        text("Customer Receipt", Style::NORMAL.centered()),
+        ReceiptLine::Blank,
+        columns("Order", &order.order_number, Style::NORMAL),
+        columns(
+            "Date",
+            &format_datetime(&order.created_at, timezone),
+            Style::NORMAL,
+        ),
+        columns(
+            "Pickup",
+            &format_datetime(&order.pickup_time, timezone),
+            Style::NORMAL,
+        ),
+        columns("Customer", &order.customer.name, Style::NORMAL),
+        ReceiptLine::Separator,
+    ];
+
+    for item in &order.items {
+        lines.push(columns(
+            &format!("{} x {}", item.quantity, item_name(item, pizza_names)),
+            &format_currency(item.subtotal),
+            Style::NORMAL,
+        ));
+        if item.quantity > 1 {
+            lines.push(text(
+                &format!("@ {}", format_currency(item.unit_price)),
+                Style::NORMAL,
+            ));
+        }
+        if let OrderItemType::CustomPizza { custom } = &item.item_type {
+            lines.push(text(
+                &format!("Note: {}", custom.instructions),
+                Style::NORMAL,
+            ));
+        }
+    }
+
+    lines.push(ReceiptLine::Separator);
+
+    if let Some(breakdown) = &order.tax_breakdown {
+        for rate in &breakdown.rates {
+            lines.push(columns(
+                &format!(
+                    "Tax {} {}",
+                    rate.category.as_str(),
+                    format_percent(rate.rate)
+                ),
+                &format_currency(rate.tax),
+                Style::NORMAL,
+            ));
+        }
+        lines.push(columns(
+            "Net",
+            &format_currency(breakdown.net_total),
+            Style::NORMAL,
+        ));
+        lines.push(columns(
+            "Tax",
+            &format_currency(breakdown.tax_total),
+            Style::NORMAL,
+        ));
+    }
+
+    lines.push(columns(
+        "TOTAL",
+        &format_currency(order.total_amount),
+        Style::NORMAL.large().bold(),
+    ));
+
+    if order.refunded_amount > 0.0 {
+        lines.push(columns(
+            "Refunded",
+            &format!("-{}", format_currency(order.refunded_amount)),
+            Style::NORMAL,
+        ));
+    }
+    lines.push(columns(
+        "Payment",
+        &format!(
+            "{} ({})",
+            order.payment_method.as_str(),
+            order.payment_status.as_str()
+        ),
+        Style::NORMAL,
+    ));
+
+    if let Some(breakdown) = &order.tax_breakdown {
+        let note = if breakdown.prices_include_tax {
+            "Prices include tax."
+        } else {
+            "Tax added to prices."
+        };
+        lines.push(text(note, Style::NORMAL));
+    }
+
+    lines.push(ReceiptLine::Blank);
+    lines.push(text("Thank you for your order!", Style::NORMAL.centered()));
+
+    lines
+}
+
+/// Kitchen copy: large items and instructions, no prices
+pub fn kitchen_ticket(
+    order: &Order,
+    pizza_names: &HashMap<String, String>,
+    timezone: Tz,
+) -> Vec<ReceiptLine> {
+    let mut lines = vec![
+        text("KITCHEN", Style::NORMAL.bold().centered()),
+        text(&order.order_number, Style::NORMAL.large().bold().centered()),
+        ReceiptLine::Blank,
+        text(
+            &format!(
+                "PICKUP {}",
+                order.pickup_time.with_timezone(&timezone).format("%H:%M")
+            ),
+            Style::NORMAL.large().bold(),
+        ),
+        text(
+            &format_datetime(&order.pickup_time, timezone),
+            Style::NORMAL,
+        ),
+        columns("Customer", &order.customer.name, Style::NORMAL),
+        ReceiptLine::Separator,
+    ];
+
+    for item in &order.items {
+        lines.push(text(
+            &format!("{} x {}", item.quantity, item_name(item, pizza_names)),
+            Style::NORMAL.large().bold(),
+        ));
+        if let OrderItemType::CustomPizza { custom } = &item.item_type {
+            lines.push(text(&custom.instructions, Style::NORMAL.large()));
+        }
+        lines.push(ReceiptLine::Blank);
+    }
+
+    lines.push(ReceiptLine::Separator);
+    lines.push(columns(
+        "Items",
+        &order
+            .items
+            .iter()
+            .map(|item| item.quantity)
+            .sum::<u32>()
+            .to_string(),
+        Style::NORMAL.bold(),
+    ));
+
+    lines
+}
+
+/// Display name of an order item including its size
+///
+/// Uses the name the item was ordered under, or the current menu name for
+/// older orders.
+fn item_name(item: &OrderItem, pizza_names: &HashMap<String, String>) -> String {
+    if let Some(name) = item.ordered_name() {
+        return name;
+    }
+
+    match &item.item_type {
+        OrderItemType::StandardPizza { pizza_id, size } => {
+            let name = pizza_names.get(pizza_id).unwrap_or(pizza_id);
+            format!("{} - {}", name, size.as_str())
+        }
+        OrderItemType::CustomPizza { custom } => {
+            format!("Custom Pizza - {}", custom.size.as_str())
+        }
+    }
+}
+
+/// Date and time in the shop's time zone, labelled with the zone, e.g. `2026-03-14 13:30 CET`
+fn format_datetime(dt: &DateTime<Utc>, timezone: Tz) -> String {
+    dt.with_timezone(&timezone)
+        .format("%Y-%m-%d %H:%M %Z")
+        .to_string()
+}
+
+fn text(text: &str, style: Style) -> ReceiptLine {
+    ReceiptLine::Text {
+        text: text.to_string(),
+        style,
+    }
+}
+
+fn columns(left: &str, right: &str, style: Style) -> ReceiptLine {
+    ReceiptLine::Columns {
+        left: left.to_string(),
+        right: right.to_string(),
+        style,
+    }
+}
@@ -0,0 +1,103 @@
+use crate::metrics::TimedQuery;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use shared::models::AuditEvent;
+use surrealdb::engine::any::Any;
+use surrealdb::sql::{self, Value};
+use surrealdb::Surreal;
+
+/// Conditions for reading the audit log; unset fields do not restrict it
+///
+/// `from` is inclusive and `to` exclusive.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+struct AuditBindings {
+    entity_type: Option<String>,
+    entity_id: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    limit: u32,
+}
+
+/// Appends an event to the audit log
+pub async fn create_audit_event_in_db(
+    db: &Surreal<Any>,
+    event: &AuditEvent,
+) -> Result<(), surrealdb::Error> {
+    db.query("CREATE audit_event CONTENT $event RETURN NONE")
+        .bind((
+            "event",
+            audit_event_content(event).map_err(|e| surrealdb::Error::Db(*e))?,
+        ))
+        .timed("audit_repo::create_audit_event_in_db")
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+/// Events matching the filter, newest first
+pub async fn query_audit_events(
+    db: &Surreal<Any>,
+    filter: &AuditFilter,
+    limit: u32,
+) -> Result<Vec<AuditEvent>, surrealdb::Error> {
+    let mut conditions = Vec::new();
+    if filter.entity_type.is_some() {
+        conditions.push("entity_type = $entity_type");
+    }
+    if filter.entity_id.is_some() {
+        conditions.push("entity_id = $entity_id");
+    }
+    if filter.from.is_some() {
+        conditions.push("created_at >= <datetime> $from");
+    }
+    if filter.to.is_some() {
+        conditions.push("created_at < <datetime> $to");
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let mut result = db
+        .query(format!(
+            "SELECT meta::id(id) AS id, * FROM audit_event {} ORDER BY created_at DESC LIMIT $limit",
+            where_clause
+        ))
+        .bind(AuditBindings {
+            entity_type: filter.entity_type.clone(),
+            entity_id: filter.entity_id.clone(),
+            from: filter.from.map(|time| time.to_rfc3339()),
+            to: filter.to.map(|time| time.to_rfc3339()),
+            limit,
+        })
+        .timed("audit_repo::query_audit_events")
+        .await?;
+
+    let events: Vec<AuditEvent> = result.take(0)?;
+    Ok(events)
+}
+
+/// Converts an audit event into record content with a native SurrealDB datetime
+fn audit_event_content(event: &AuditEvent) -> Result<Value, Box<surrealdb::err::Error>> {
+    let mut content = sql::to_value(event.clone()).map_err(Box::new)?;
+
+    if let Value::Object(fields) = &mut content {
+        fields.insert(
+            "created_at".to_string(),
+            Value::Datetime(event.created_at.into()),
+        );
+    }
+
+    Ok(content)
+}
@@ -0,0 +1,215 @@
+use crate::metrics::TimedQuery;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use surrealdb::engine::any::Any;
+use surrealdb::sql::{Thing, Value};
+use surrealdb::Surreal;
+
+/// A table included in backups, with the fields JSON cannot tell the type of
+pub struct BackupTable {
+    pub name: &'static str,
+    /// Stored as datetimes, archived as RFC 3339 strings
+    pub datetime_fields: &'static [&'static str],
+    /// Links to records of another table, archived as the record key
+    pub record_fields: &'static [(&'static str, &'static str)],
+    /// Records can only be added, so existing ones are kept even on overwrite
+    pub append_only: bool,
+}
+
+/// Tables with the menu, orders and what belongs to them, in restore order
+///
+/// Notification and webhook queues and phone verifications are left out:
+/// they are short-lived, and restoring the queues would send messages again.
+pub const BACKUP_TABLES: &[BackupTable] = &[
+    BackupTable {
+        name: "pizza",
+        datetime_fields: &["created_at", "updated_at"],
+        record_fields: &[],
+        append_only: false,
+    },
+    BackupTable {
+        name: "price_history",
+        datetime_fields: &["valid_from"],
+        record_fields: &[],
+        append_only: false,
+    },
+    BackupTable {
+        name: "customer",
+        datetime_fields: &["last_order_at", "created_at", "updated_at"],
+        record_fields: &[],
+        append_only: false,
+    },
+    BackupTable {
+        name: "order",
+        datetime_fields: &["pickup_time", "created_at", "updated_at"],
+        record_fields: &[("customer_ref", "customer")],
+        append_only: false,
+    },
+    BackupTable {
+        name: "refund",
+        datetime_fields: &["created_at"],
+        record_fields: &[],
+        append_only: false,
+    },
+    BackupTable {
+        name: "webhook",
+        datetime_fields: &["created_at", "updated_at"],
+        record_fields: &[],
+        append_only: false,
+    },
+    BackupTable {
+        name: "audit_event",
+        datetime_fields: &["created_at"],
+        record_fields: &[],
+        append_only: true,
+    },
+];
+
+pub fn find_backup_table(name: &str) -> Option<&'static BackupTable> {
+    BACKUP_TABLES.iter().find(|table| table.name == name)
+}
+
+/// All records of a table as JSON, keyed by their record key
+pub async fn query_table_records(
+    db: &Surreal<Any>,
+    table: &'static BackupTable,
+) -> Result<Vec<serde_json::Value>, surrealdb::Error> {
+    let links: String = table
+        .record_fields
+        .iter()
+        .map(|(field, _)| format!("IF {field} THEN meta::id({field}) END AS {field}, "))
+        .collect();
+
+    let mut result = db
+        .query(format!(
+            "SELECT meta::id(id) AS id, {}* FROM type::table($table)",
+            links
+        ))
+        .bind(("table", table.name))
+        .timed("backup_repo::query_table_records")
+        .await?;
+
+    result.take(0)
+}
+
+/// Keys of all records in a table
+pub async fn query_record_ids(
+    db: &Surreal<Any>,
+    table: &'static BackupTable,
+) -> Result<Vec<String>, surrealdb::Error> {
+    let mut result = db
+        .query("SELECT VALUE meta::id(id) FROM type::table($table)")
+        .bind(("table", table.name))
+        .timed("backup_repo::query_record_ids")
+        .await?;
+
+    result.take(0)
+}
+
+/// Replaces existing records by the given contents in one transaction
+///
+/// A failed insert rolls the delete back, so no record is lost halfway.
+pub async fn replace_records(
+    db: &Surreal<Any>,
+    table: &'static BackupTable,
+    ids: Vec<String>,
+    records: Vec<Value>,
+) -> Result<(), surrealdb::Error> {
+    // The table name comes from BACKUP_TABLES, never from the archive
+    db.query("BEGIN TRANSACTION")
+        .query("DELETE type::table($table) WHERE meta::id(id) IN $ids")
+        .query(format!("INSERT INTO {} $records RETURN NONE", table.name))
+        .query("COMMIT TRANSACTION")
+        .bind(("table", table.name))
+        .bind(("ids", ids))
+        .bind(("records", Value::from(records)))
+        .timed("backup_repo::replace_records")
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+/// Inserts records made by `record_content`, keeping their keys
+pub async fn insert_records(
+    db: &Surreal<Any>,
+    table: &'static BackupTable,
+    records: Vec<Value>,
+) -> Result<(), surrealdb::Error> {
+    // The table name comes from BACKUP_TABLES, never from the archive
+    db.query(format!("INSERT INTO {} $records RETURN NONE", table.name))
+        .bind(("records", Value::from(records)))
+        .timed("backup_repo::insert_records")
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+/// Turns an archived record back into record content with native datetimes and links
+///
+/// Returns the record key along with the content.
+pub fn record_content(
+    table: &BackupTable,
+    record: serde_json::Value,
+) -> Result<(String, Value), String> {
+    let serde_json::Value::Object(fields) = record else {
+        return Err(format!("{} record is not an object", table.name));
+    };
+
+    let id = match fields.get("id") {
+        Some(serde_json::Value::String(id)) if !id.is_empty() => id.clone(),
+        _ => return Err(format!("{} record has no id", table.name)),
+    };
+
+    let mut content = BTreeMap::new();
+    for (field, value) in fields {
+        let value = if field == "id" {
+            Value::Thing(Thing::from((table.name, id.as_str())))
+        } else if value.is_null() {
+            // Unset optional fields are NONE, which schemafull tables accept and NULL is not
+            continue;
+        } else if table.datetime_fields.contains(&field.as_str()) {
+            let datetime = value
+                .as_str()
+                .and_then(|value| value.parse::<DateTime<Utc>>().ok())
+                .ok_or_else(|| format!("{} record {} has an invalid {}", table.name, id, field))?;
+            Value::Datetime(datetime.into())
+        } else if let Some((_, linked_table)) = table
+            .record_fields
+            .iter()
+            .find(|(record_field, _)| *record_field == field)
+        {
+            let key = value
+                .as_str()
+                .ok_or_else(|| format!("{} record {} has an invalid {}", table.name, id, field))?;
+            Value::Thing(Thing::from((*linked_table, key)))
+        } else {
+            json_value(value)
+        };
+        content.insert(field, value);
+    }
+
+    Ok((id, Value::from(content)))
+}
+
+fn json_value(value: serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::None,
+        serde_json::Value::Bool(value) => Value::from(value),
+        serde_json::Value::Number(number) => match number.as_i64() {
+            Some(value) => Value::from(value),
+            None => Value::from(number.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(value) => Value::from(value),
+        serde_json::Value::Array(values) => {
+            Value::from(values.into_iter().map(json_value).collect::<Vec<_>>())
+        }
+        serde_json::Value::Object(fields) => Value::from(
+            fields
+                .into_iter()
+                .map(|(field, value)| (field, json_value(value)))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+    }
+}
@@ -0,0 +1,90 @@
+use crate::metrics::TimedQuery;
+use shared::models::{Customer, Order};
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+
+/// Creates the customer on their first order, or records another order
+///
+/// The record key is the normalized phone number, so concurrent first orders
+/// of the same customer end up on a single record.
+pub async fn upsert_customer_for_order(
+    db: &Surreal<Any>,
+    phone: &str,
+    name: &str,
+) -> Result<Customer, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "LET $upserted = (UPSERT type::thing('customer', $phone) SET phone = $phone, \
+             name = $name, order_count = (order_count ?? 0) + 1, last_order_at = time::now(), \
+             created_at = created_at ?? time::now(), updated_at = time::now() RETURN AFTER)",
+        )
+        .query("SELECT meta::id(id) AS id, * FROM $upserted")
+        .bind(("phone", phone.to_string()))
+        .bind(("name", name.to_string()))
+        .timed("customer_repo::upsert_customer_for_order")
+        .await?;
+
+    let customers: Vec<Customer> = result.take(1)?;
+
+    customers.into_iter().next().ok_or_else(|| {
+        surrealdb::Error::Api(surrealdb::error::Api::Query(
+            "Failed to retrieve upserted customer".to_string(),
+        ))
+    })
+}
+
+pub async fn query_customer_by_id(
+    db: &Surreal<Any>,
+    id: &str,
+) -> Result<Option<Customer>, surrealdb::Error> {
+    let mut result = db
+        .query("SELECT meta::id(id) AS id, * FROM type::thing('customer', $id)")
+        .bind(("id", id.to_string()))
+        .timed("customer_repo::query_customer_by_id")
+        .await?;
+
+    let customers: Vec<Customer> = result.take(0)?;
+    Ok(customers.into_iter().next())
+}
+
+/// Customers whose phone or lowercased name contains the search text
+pub async fn search_customers(
+    db: &Surreal<Any>,
+    search: &str,
+    limit: usize,
+) -> Result<Vec<Customer>, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "SELECT meta::id(id) AS id, * FROM customer \
+             WHERE phone CONTAINS $search OR string::lowercase(name) CONTAINS $search \
+             ORDER BY last_order_at DESC LIMIT $limit",
+        )
+        .bind(("search", search.to_string()))
+        .bind(("limit", limit))
+        .timed("customer_repo::search_customers")
+        .await?;
+
+    let customers: Vec<Customer> = result.take(0)?;
+    Ok(customers)
+}
+
+/// Most recent orders linked to a customer, newest first
+pub async fn query_orders_for_customer(
+    db: &Surreal<Any>,
+    customer_id: &str,
+    limit: usize,
+) -> Result<Vec<Order>, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "SELECT meta::id(id) AS id, * FROM order \
+             WHERE customer_ref = type::thing('customer', $customer_id) \
+             ORDER BY created_at DESC LIMIT $limit",
+        )
+        .bind(("customer_id", customer_id.to_string()))
+        .bind(("limit", limit))
+        .timed("customer_repo::query_orders_for_customer")
+        .await?;
+
+    let orders: Vec<Order> = result.take(0)?;
+    Ok(orders)
+}
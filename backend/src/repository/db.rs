@@ -1,18 +1,192 @@
 use crate::config::DatabaseConfig;
-use surrealdb::engine::remote::ws::{Client, Ws};
+use crate::metrics::{record_db_reconnect, record_db_up};
+use crate::repository::health_repo;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use surrealdb::engine::any::{self, Any};
 use surrealdb::opt::auth::Root;
 use surrealdb::Surreal;
 use tracing::{info, warn};
 
-pub async fn create_db_client(config: &DatabaseConfig) -> Result<Surreal<Client>, surrealdb::Error> {
-    const MAX_RETRIES: u32 = 10;
+/// How often the supervisor checks the connection
+const PING_INTERVAL: Duration = Duration::from_secs(5);
+/// How long a ping may take before the connection counts as lost
+const PING_TIMEOUT: Duration = Duration::from_secs(3);
+/// Delay before the first reconnection attempt, doubled after every failure
+const RECONNECT_BASE_DELAY: Duration = Duration::from_millis(500);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Database connection that is replaced when SurrealDB goes away
+///
+/// Hand out `client()` per request or per worker run rather than keeping a
+/// client around, so work picks up the new connection after a reconnect.
+/// Embedded storage lives in the process and is never reconnected.
+#[derive(Clone)]
+pub struct Database {
+    inner: Arc<DatabaseInner>,
+}
+
+struct DatabaseInner {
+    config: DatabaseConfig,
+    client: RwLock<Surreal<Any>>,
+    status: RwLock<ConnectionStatus>,
+}
+
+/// What the supervisor last saw of the connection
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionStatus {
+    pub state: ConnectionState,
+    /// When the current state began
+    pub since: DateTime<Utc>,
+    /// Failed reconnection attempts since the connection was lost
+    pub reconnect_attempts: u32,
+    pub reconnects: u64,
+    #[serde(skip)]
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    Connected,
+    Reconnecting,
+}
+
+impl Database {
+    /// Connects at startup, retrying for a few seconds while a SurrealDB server comes up
+    pub async fn connect(config: &DatabaseConfig) -> Result<Self, surrealdb::Error> {
+        let client = create_db_client(config).await?;
+
+        Ok(Self {
+            inner: Arc::new(DatabaseInner {
+                config: config.clone(),
+                client: RwLock::new(client),
+                status: RwLock::new(ConnectionStatus {
+                    state: ConnectionState::Connected,
+                    since: Utc::now(),
+                    reconnect_attempts: 0,
+                    reconnects: 0,
+                    last_error: None,
+                }),
+            }),
+        })
+    }
+
+    /// The current connection
+    pub fn client(&self) -> Surreal<Any> {
+        self.inner
+            .client
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    pub fn status(&self) -> ConnectionStatus {
+        self.inner
+            .status
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.status().state == ConnectionState::Connected
+    }
+
+    fn update_status(&self, update: impl FnOnce(&mut ConnectionStatus)) {
+        update(
+            &mut self
+                .inner
+                .status
+                .write()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
+    }
+
+    /// Watches the connection and replaces it when it stops answering
+    ///
+    /// A new connection signs in and selects the namespace and database again
+    /// before it is handed out. Runs for the lifetime of the process.
+    pub async fn supervise(self) {
+        let mut interval = tokio::time::interval(PING_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let error =
+                match tokio::time::timeout(PING_TIMEOUT, health_repo::ping(&self.client())).await {
+                    Ok(Ok(())) => {
+                        record_db_up(true);
+                        continue;
+                    }
+                    Ok(Err(e)) => e.to_string(),
+                    Err(_) => format!("no answer within {:?}", PING_TIMEOUT),
+                };
+
+            warn!("Lost the SurrealDB connection: {}", error);
+            record_db_up(false);
+            self.update_status(|status| {
+                status.state = ConnectionState::Reconnecting;
+                status.since = Utc::now();
+                status.reconnect_attempts = 0;
+                status.last_error = Some(error);
+            });
+
+            self.reconnect().await;
+            interval.reset();
+        }
+    }
+
+    /// Connects again with exponential backoff until it succeeds
+    async fn reconnect(&self) {
+        let mut delay = RECONNECT_BASE_DELAY;
+        loop {
+            tokio::time::sleep(delay).await;
+
+            match try_connect(&self.inner.config).await {
+                Ok(client) => {
+                    *self
+                        .inner
+                        .client
+                        .write()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner()) = client;
+                    self.update_status(|status| {
+                        status.state = ConnectionState::Connected;
+                        status.since = Utc::now();
+                        status.reconnects += 1;
+                    });
+                    record_db_up(true);
+                    record_db_reconnect();
+                    info!("Reconnected to SurrealDB");
+                    return;
+                }
+                Err(e) => {
+                    delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+                    warn!(
+                        "Reconnecting to SurrealDB failed, retrying in {:?}: {}",
+                        delay, e
+                    );
+                    self.update_status(|status| {
+                        status.reconnect_attempts += 1;
+                        status.last_error = Some(e.to_string());
+                    });
+                }
+            }
+        }
+    }
+}
+
+pub async fn create_db_client(config: &DatabaseConfig) -> Result<Surreal<Any>, surrealdb::Error> {
     const RETRY_DELAY_MS: u64 = 500;
 
+    // Embedded storage has no server to wait for
+    let max_retries = if config.engine.is_embedded() { 1 } else { 10 };
     let mut last_error = None;
 
-    for attempt in 1..=MAX_RETRIES {
+    for attempt in 1..=max_retries {
         let url = config.url.clone();
-        info!("Attempting to connect to SurrealDB [{}] (attempt {}/{})",url , attempt, MAX_RETRIES);
+        info!("Attempting to connect to SurrealDB [{}] (attempt {}/{})",url , attempt, max_retries);
 
         match try_connect(config).await {
             Ok(db) => {
@@ -23,7 +197,7 @@ pub async fn create_db_client(config: &DatabaseConfig) -> Result<Surreal<Client>
                 warn!("Connection attempt {} failed: {}", attempt, e);
                 last_error = Some(e);
 
-                if attempt < MAX_RETRIES {
+                if attempt < max_retries {
                     info!("Retrying in {}ms...", RETRY_DELAY_MS);
                     tokio::time::sleep(tokio::time::Duration::from_millis(RETRY_DELAY_MS)).await;
                 }
@@ -34,16 +208,18 @@ pub async fn create_db_client(config: &DatabaseConfig) -> Result<Surreal<Client>
     Err(last_error.unwrap())
 }
 
-async fn try_connect(config: &DatabaseConfig) -> Result<Surreal<Client>, surrealdb::Error> {
-    // Connect to SurrealDB via WebSocket
-    let db = Surreal::new::<Ws>(&config.url).await?;
+async fn try_connect(config: &DatabaseConfig) -> Result<Surreal<Any>, surrealdb::Error> {
+    // Connect over WebSocket or open embedded storage, depending on the URL scheme
+    let db = any::connect(config.url.as_str()).await?;
 
-    // Sign in with Root credentials
-    db.signin(Root {
-        username: &config.username,
-        password: &config.password,
-    })
-    .await?;
+    // Sign in with Root credentials; embedded storage runs without authentication
+    if !config.engine.is_embedded() {
+        db.signin(Root {
+            username: &config.username,
+            password: &config.password,
+        })
+        .await?;
+    }
 
     // Use namespace and database
     db.use_ns(&config.namespace).use_db(&config.name).await?;
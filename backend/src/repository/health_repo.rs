@@ -0,0 +1,41 @@
+use crate::metrics::TimedQuery;
+use serde::Deserialize;
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+
+#[derive(Debug, Deserialize)]
+struct SchemaVersion {
+    version: u32,
+}
+
+/// Round trip that needs a working session: connection, credentials and database
+pub async fn ping(db: &Surreal<Any>) -> Result<(), surrealdb::Error> {
+    db.query("INFO FOR DB")
+        .timed("health_repo::ping")
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+/// Schema version recorded when `schema.surql` was last applied, if any
+pub async fn query_schema_version(db: &Surreal<Any>) -> Result<Option<u32>, surrealdb::Error> {
+    let mut result = db
+        .query("SELECT version FROM schema_version:current")
+        .timed("health_repo::query_schema_version")
+        .await?;
+
+    let versions: Vec<SchemaVersion> = result.take(0)?;
+    Ok(versions.into_iter().next().map(|row| row.version))
+}
+
+/// Number of pizzas on the menu, available or not
+pub async fn count_pizzas(db: &Surreal<Any>) -> Result<usize, surrealdb::Error> {
+    let mut result = db
+        .query("SELECT count() AS count FROM pizza GROUP ALL")
+        .timed("health_repo::count_pizzas")
+        .await?;
+
+    let count: Option<usize> = result.take((0, "count"))?;
+    Ok(count.unwrap_or(0))
+}
@@ -1,4 +1,13 @@
+pub mod audit_repo;
+pub mod backup_repo;
+pub mod customer_repo;
 pub mod db;
+pub mod health_repo;
+pub mod notification_repo;
 pub mod order_repo;
 pub mod pizza_repo;
+pub mod refund_repo;
+pub mod report_repo;
 pub mod seed;
+pub mod webhook_repo;
+pub mod verification_repo;
@@ -0,0 +1,130 @@
+use crate::metrics::TimedQuery;
+use chrono::{DateTime, Utc};
+use shared::models::{Notification, NotificationStatus};
+use std::time::Duration;
+use surrealdb::engine::any::Any;
+use surrealdb::sql::{self, Value};
+use surrealdb::Surreal;
+
+pub async fn create_notification_in_db(
+    db: &Surreal<Any>,
+    notification: &Notification,
+) -> Result<Notification, surrealdb::Error> {
+    let mut result = db
+        .query("LET $created = (CREATE notification CONTENT $notification)")
+        .query("SELECT meta::id(id) AS id, * FROM $created")
+        .bind((
+            "notification",
+            notification_content(notification).map_err(|e| surrealdb::Error::Db(*e))?,
+        ))
+        .timed("notification_repo::create_notification_in_db")
+        .await?;
+
+    let notifications: Vec<Notification> = result.take(1)?;
+
+    notifications.into_iter().next().ok_or_else(|| {
+        surrealdb::Error::Api(surrealdb::error::Api::Query(
+            "Failed to retrieve created notification".to_string(),
+        ))
+    })
+}
+
+/// Claims pending notifications that are due, oldest first
+///
+/// Claimed notifications are pushed back by `lease`, so a worker that dies
+/// mid-delivery does not lose them; they are retried once the lease expires.
+pub async fn claim_due_notifications(
+    db: &Surreal<Any>,
+    limit: u32,
+    lease: Duration,
+) -> Result<Vec<Notification>, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "LET $due = (SELECT id, next_attempt_at FROM notification \
+             WHERE status = 'Pending' AND next_attempt_at <= time::now() \
+             ORDER BY next_attempt_at LIMIT $limit)",
+        )
+        .query(
+            "LET $claimed = (UPDATE $due.id \
+             SET next_attempt_at = time::now() + <duration> $lease RETURN AFTER)",
+        )
+        .query("SELECT meta::id(id) AS id, * FROM $claimed ORDER BY created_at")
+        .bind(("limit", limit))
+        .bind(("lease", format!("{}s", lease.as_secs())))
+        .timed("notification_repo::claim_due_notifications")
+        .await?;
+
+    let notifications: Vec<Notification> = result.take(2)?;
+    Ok(notifications)
+}
+
+pub async fn mark_notification_sent(
+    db: &Surreal<Any>,
+    id: &str,
+    attempts: u32,
+) -> Result<(), surrealdb::Error> {
+    db.query(
+        "UPDATE type::thing('notification', $id) \
+         SET status = 'Sent', attempts = $attempts, last_error = NONE, sent_at = time::now()",
+    )
+    .bind(("id", id.to_string()))
+    .bind(("attempts", attempts))
+    .timed("notification_repo::mark_notification_sent")
+    .await?
+    .check()?;
+
+    Ok(())
+}
+
+/// Records a failed delivery attempt
+///
+/// With `retry_at` the notification stays pending until then, otherwise it is
+/// marked as failed for good.
+pub async fn record_failed_attempt(
+    db: &Surreal<Any>,
+    id: &str,
+    attempts: u32,
+    error: &str,
+    retry_at: Option<DateTime<Utc>>,
+) -> Result<(), surrealdb::Error> {
+    let status = match retry_at {
+        Some(_) => NotificationStatus::Pending,
+        None => NotificationStatus::Failed,
+    };
+
+    db.query(
+        "UPDATE type::thing('notification', $id) \
+         SET status = $status, attempts = $attempts, last_error = $error, \
+         next_attempt_at = IF $retry_at THEN <datetime> $retry_at ELSE next_attempt_at END",
+    )
+    .bind(("id", id.to_string()))
+    .bind(("status", status))
+    .bind(("attempts", attempts))
+    .bind(("error", error.to_string()))
+    .bind(("retry_at", retry_at.map(|at| at.to_rfc3339())))
+    .timed("notification_repo::record_failed_attempt")
+    .await?
+    .check()?;
+
+    Ok(())
+}
+
+/// Converts a notification into record content with native SurrealDB datetimes
+fn notification_content(notification: &Notification) -> Result<Value, Box<surrealdb::err::Error>> {
+    let mut content = sql::to_value(notification.clone()).map_err(Box::new)?;
+
+    if let Value::Object(fields) = &mut content {
+        fields.insert(
+            "next_attempt_at".to_string(),
+            Value::Datetime(notification.next_attempt_at.into()),
+        );
+        fields.insert(
+            "created_at".to_string(),
+            Value::Datetime(notification.created_at.into()),
+        );
+        fields.remove("sent_at");
+        fields.remove("last_error");
+    }
+
+    Ok(content)
+}
@@ -1,21 +1,29 @@
-use shared::models::Order;
-use surrealdb::engine::remote::ws::Client;
+use crate::metrics::TimedQuery;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use shared::dto::{OrderSort, SortDirection};
+use shared::models::{Order, OrderStatus, PaymentStatus};
+use surrealdb::engine::any::Any;
+use surrealdb::sql::{self, Value};
 use surrealdb::Surreal;
 
+/// Stores a new order, linked to its customer record when there is one
 pub async fn create_order_in_db(
-    db: &Surreal<Client>,
+    db: &Surreal<Any>,
     order: &Order,
+    customer_id: Option<&str>,
 ) -> Result<Order, surrealdb::Error> {
-    let order_clone = order.clone();
     // Use the query interface to create and select with meta::id()
     let mut result = db
-        .query("CREATE order CONTENT $order")
-        .query("SELECT meta::id(id) AS id, * FROM $parent")
-        .bind(("order", order_clone))
+        .query("LET $created = (CREATE order CONTENT $order)")
+        .query("SELECT meta::id(id) AS id, * FROM $created")
+        .bind((
+            "order",
+            order_content(order, customer_id).map_err(|e| surrealdb::Error::Db(*e))?,
+        ))
+        .timed("order_repo::create_order_in_db")
         .await?;
 
-    // Skip the first result (CREATE returns the Thing)
-    let _: Option<surrealdb::sql::Thing> = result.take(0)?;
     // Take the second result (SELECT with string id)
     let orders: Vec<Order> = result.take(1)?;
 
@@ -27,34 +35,263 @@ pub async fn create_order_in_db(
 }
 
 pub async fn query_order_by_id(
-    db: &Surreal<Client>,
+    db: &Surreal<Any>,
     id: &str,
 ) -> Result<Option<Order>, surrealdb::Error> {
     let mut result = db
-        .query("SELECT meta::id(id) AS id, * FROM order WHERE id = $id")
+        .query("SELECT meta::id(id) AS id, * FROM type::thing('order', $id)")
         .bind(("id", id.to_string()))
+        .timed("order_repo::query_order_by_id")
+        .await?;
+
+    let orders: Vec<Order> = result.take(0)?;
+    Ok(orders.into_iter().next())
+}
+
+pub async fn query_order_by_number(
+    db: &Surreal<Any>,
+    order_number: &str,
+) -> Result<Option<Order>, surrealdb::Error> {
+    let mut result = db
+        .query("SELECT meta::id(id) AS id, * FROM order WHERE order_number = $order_number")
+        .bind(("order_number", order_number.to_string()))
+        .timed("order_repo::query_order_by_number")
+        .await?;
+
+    let orders: Vec<Order> = result.take(0)?;
+    Ok(orders.into_iter().next())
+}
+
+/// Conditions for listing orders; unset fields do not restrict the list
+///
+/// Range starts are inclusive and range ends exclusive.
+#[derive(Debug, Clone, Default)]
+pub struct OrderFilter {
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    pub pickup_from: Option<DateTime<Utc>>,
+    pub pickup_to: Option<DateTime<Utc>>,
+    /// Orders in any of these statuses; empty means every status
+    pub statuses: Vec<OrderStatus>,
+    /// Lowercase text matched against order number, customer name and phone
+    pub search: Option<String>,
+}
+
+/// Sort order and position of a page of orders
+#[derive(Debug, Clone)]
+pub struct OrderPage {
+    pub sort: OrderSort,
+    pub direction: SortDirection,
+    /// Sort value and id of the last order of the previous page
+    pub after: Option<(DateTime<Utc>, String)>,
+    pub limit: usize,
+}
+
+/// One page of orders matching the filter
+///
+/// Orders with equal sort values are ordered by id, so keyset pagination
+/// neither skips nor repeats orders.
+pub async fn query_orders(
+    db: &Surreal<Any>,
+    filter: &OrderFilter,
+    page: &OrderPage,
+) -> Result<Vec<Order>, surrealdb::Error> {
+    let sort_field = match page.sort {
+        OrderSort::CreatedAt => "created_at",
+        OrderSort::PickupTime => "pickup_time",
+    };
+    let (direction, comparison) = match page.direction {
+        SortDirection::Asc => ("ASC", ">"),
+        SortDirection::Desc => ("DESC", "<"),
+    };
+
+    let mut conditions = Vec::new();
+    if filter.created_from.is_some() {
+        conditions.push("created_at >= <datetime> $created_from".to_string());
+    }
+    if filter.created_to.is_some() {
+        conditions.push("created_at < <datetime> $created_to".to_string());
+    }
+    if filter.pickup_from.is_some() {
+        conditions.push("pickup_time >= <datetime> $pickup_from".to_string());
+    }
+    if filter.pickup_to.is_some() {
+        conditions.push("pickup_time < <datetime> $pickup_to".to_string());
+    }
+    if !filter.statuses.is_empty() {
+        conditions.push("status INSIDE $statuses".to_string());
+    }
+    if filter.search.is_some() {
+        conditions.push(
+            "(string::lowercase(order_number) CONTAINS $search \
+             OR string::lowercase(customer.name) CONTAINS $search \
+             OR customer.phone CONTAINS $search \
+             OR (customer.normalized_phone ?? '') CONTAINS $search)"
+                .to_string(),
+        );
+    }
+    if page.after.is_some() {
+        conditions.push(format!(
+            "({field} {cmp} <datetime> $after_value OR ({field} = <datetime> $after_value \
+             AND id {cmp} type::thing('order', $after_id)))",
+            field = sort_field,
+            cmp = comparison
+        ));
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let mut result = db
+        .query(format!(
+            "SELECT meta::id(id) AS id, * FROM order {} ORDER BY {} {}, id {} LIMIT $limit",
+            where_clause, sort_field, direction, direction
+        ))
+        .bind(OrderListBindings {
+            created_from: filter.created_from.map(|time| time.to_rfc3339()),
+            created_to: filter.created_to.map(|time| time.to_rfc3339()),
+            pickup_from: filter.pickup_from.map(|time| time.to_rfc3339()),
+            pickup_to: filter.pickup_to.map(|time| time.to_rfc3339()),
+            statuses: filter.statuses.clone(),
+            search: filter.search.clone(),
+            after_value: page.after.as_ref().map(|(value, _)| value.to_rfc3339()),
+            after_id: page.after.as_ref().map(|(_, id)| id.clone()),
+            limit: page.limit,
+        })
+        .timed("order_repo::query_orders")
         .await?;
 
     let orders: Vec<Order> = result.take(0)?;
+    Ok(orders)
+}
+
+#[derive(Serialize)]
+struct OrderListBindings {
+    created_from: Option<String>,
+    created_to: Option<String>,
+    pickup_from: Option<String>,
+    pickup_to: Option<String>,
+    statuses: Vec<OrderStatus>,
+    search: Option<String>,
+    after_value: Option<String>,
+    after_id: Option<String>,
+    limit: usize,
+}
+
+pub async fn update_order_payment(
+    db: &Surreal<Any>,
+    id: &str,
+    payment_status: PaymentStatus,
+    payment_id: Option<String>,
+    status: OrderStatus,
+) -> Result<Option<Order>, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "LET $updated = (UPDATE type::thing('order', $id) SET payment_status = $payment_status, \
+             payment_id = $payment_id, status = $status, updated_at = time::now() RETURN AFTER)",
+        )
+        .query("SELECT meta::id(id) AS id, * FROM $updated")
+        .bind(("id", id.to_string()))
+        .bind(("payment_status", payment_status))
+        .bind(("payment_id", payment_id))
+        .bind(("status", status))
+        .timed("order_repo::update_order_payment")
+        .await?;
+
+    let orders: Vec<Order> = result.take(1)?;
+    Ok(orders.into_iter().next())
+}
+
+pub async fn update_order_status(
+    db: &Surreal<Any>,
+    id: &str,
+    status: OrderStatus,
+) -> Result<Option<Order>, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "LET $updated = (UPDATE type::thing('order', $id) SET status = $status, \
+             updated_at = time::now() RETURN AFTER)",
+        )
+        .query("SELECT meta::id(id) AS id, * FROM $updated")
+        .bind(("id", id.to_string()))
+        .bind(("status", status))
+        .timed("order_repo::update_order_status")
+        .await?;
+
+    let orders: Vec<Order> = result.take(1)?;
     Ok(orders.into_iter().next())
 }
 
+/// Stores the provider reference only, so it cannot overwrite an outcome that arrived first
+pub async fn update_order_payment_id(
+    db: &Surreal<Any>,
+    id: &str,
+    payment_id: &str,
+) -> Result<(), surrealdb::Error> {
+    db.query(
+        "UPDATE type::thing('order', $id) SET payment_id = $payment_id, updated_at = time::now()",
+    )
+    .bind(("id", id.to_string()))
+    .bind(("payment_id", payment_id.to_string()))
+    .timed("order_repo::update_order_payment_id")
+    .await?
+    .check()?;
+
+    Ok(())
+}
+
 pub async fn count_orders_for_date(
-    db: &Surreal<Client>,
+    db: &Surreal<Any>,
     date_prefix: &str,
 ) -> Result<usize, surrealdb::Error> {
     let mut result = db
-        .query("SELECT count() FROM order WHERE order_number LIKE $pattern GROUP ALL")
-        .bind(("pattern", format!("RP-{}-___", date_prefix)))
+        .query("SELECT count() FROM order WHERE string::starts_with(order_number, $prefix) GROUP ALL")
+        .bind(("prefix", format!("RP-{}-", date_prefix)))
+        .timed("order_repo::count_orders_for_date")
         .await?;
 
     let counts: Vec<serde_json::Value> = result.take(0)?;
 
-    if let Some(count_obj) = counts.first() {
-        if let Some(count) = count_obj.get("count").and_then(|v| v.as_u64()) {
-            return Ok(count as usize);
-        }
+    if let Some(count) = counts
+        .first()
+        .and_then(|count_obj| count_obj.get("count"))
+        .and_then(|v| v.as_u64())
+    {
+        return Ok(count as usize);
     }
 
     Ok(0)
 }
+
+/// Converts an order into record content with native SurrealDB datetimes
+///
+/// chrono serializes datetimes as strings, which SCHEMAFULL `datetime` fields reject.
+/// The customer is stored as a record link so it can be traversed in queries.
+fn order_content(
+    order: &Order,
+    customer_id: Option<&str>,
+) -> Result<Value, Box<surrealdb::err::Error>> {
+    let mut content = sql::to_value(order.clone()).map_err(Box::new)?;
+
+    if let Value::Object(fields) = &mut content {
+        fields.insert(
+            "pickup_time".to_string(),
+            Value::Datetime(order.pickup_time.into()),
+        );
+        fields.insert(
+            "created_at".to_string(),
+            Value::Datetime(order.created_at.into()),
+        );
+        if let Some(customer_id) = customer_id {
+            fields.insert(
+                "customer_ref".to_string(),
+                Value::Thing(("customer", customer_id).into()),
+            );
+        }
+    }
+
+    Ok(content)
+}
@@ -1,12 +1,23 @@
-use shared::models::Pizza;
-use surrealdb::engine::remote::ws::Client;
+use crate::metrics::TimedQuery;
+use shared::models::{Pizza, PizzaImage, PizzaPrice, PriceHistoryEntry};
+use surrealdb::engine::any::Any;
 use surrealdb::Surreal;
 
-pub async fn query_all_available_pizzas(
-    db: &Surreal<Client>,
-) -> Result<Vec<Pizza>, surrealdb::Error> {
+pub async fn query_all_available_pizzas(db: &Surreal<Any>) -> Result<Vec<Pizza>, surrealdb::Error> {
     let mut result = db
         .query("SELECT meta::id(id) AS id, * FROM pizza WHERE is_available = true")
+        .timed("pizza_repo::query_all_available_pizzas")
+        .await?;
+
+    let pizzas: Vec<Pizza> = result.take(0)?;
+    Ok(pizzas)
+}
+
+/// All pizzas including unavailable ones, for editing the menu
+pub async fn query_all_pizzas(db: &Surreal<Any>) -> Result<Vec<Pizza>, surrealdb::Error> {
+    let mut result = db
+        .query("SELECT meta::id(id) AS id, * FROM pizza ORDER BY name")
+        .timed("pizza_repo::query_all_pizzas")
         .await?;
 
     let pizzas: Vec<Pizza> = result.take(0)?;
@@ -14,14 +25,98 @@ pub async fn query_all_available_pizzas(
 }
 
 pub async fn query_pizza_by_id(
-    db: &Surreal<Client>,
+    db: &Surreal<Any>,
     id: &str,
 ) -> Result<Option<Pizza>, surrealdb::Error> {
     let mut result = db
-        .query("SELECT meta::id(id) AS id, * FROM pizza WHERE id = $id")
+        .query("SELECT meta::id(id) AS id, * FROM type::thing('pizza', $id)")
         .bind(("id", id.to_string()))
+        .timed("pizza_repo::query_pizza_by_id")
         .await?;
 
     let pizzas: Vec<Pizza> = result.take(0)?;
     Ok(pizzas.into_iter().next())
 }
+
+/// Updates a pizza and adds a price history entry when its name or prices change
+///
+/// The first change of a pizza without history also records what it was
+/// before, from when it was added to the menu.
+pub async fn update_pizza(
+    db: &Surreal<Any>,
+    id: &str,
+    name: String,
+    description: String,
+    price: PizzaPrice,
+    is_available: bool,
+) -> Result<Option<Pizza>, surrealdb::Error> {
+    let mut result = db
+        .query("LET $before = (SELECT * FROM ONLY type::thing('pizza', $id))")
+        .query(
+            "LET $updated = (UPDATE type::thing('pizza', $id) SET name = $name, \
+             description = $description, price = $price, is_available = $is_available, \
+             updated_at = time::now() RETURN AFTER)",
+        )
+        .query(
+            "IF $before AND ($before.name != $name OR $before.price != $price) { \
+                 IF count(SELECT id FROM price_history WHERE pizza_id = $id LIMIT 1) = 0 { \
+                     CREATE price_history CONTENT { pizza_id: $id, name: $before.name, \
+                     price: $before.price, valid_from: $before.created_at }; \
+                 }; \
+                 CREATE price_history CONTENT { pizza_id: $id, name: $name, price: $price, \
+                 valid_from: time::now() }; \
+             }",
+        )
+        .query("SELECT meta::id(id) AS id, * FROM $updated")
+        .bind(("id", id.to_string()))
+        .bind(("name", name))
+        .bind(("description", description))
+        .bind(("price", price))
+        .bind(("is_available", is_available))
+        .timed("pizza_repo::update_pizza")
+        .await?;
+
+    let pizzas: Vec<Pizza> = result.take(3)?;
+    Ok(pizzas.into_iter().next())
+}
+
+/// Replaces the uploaded image of a pizza, or removes it with `None`
+pub async fn update_pizza_image(
+    db: &Surreal<Any>,
+    id: &str,
+    image: Option<PizzaImage>,
+    image_url: Option<String>,
+) -> Result<Option<Pizza>, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "LET $updated = (UPDATE type::thing('pizza', $id) SET image = $image, \
+             image_url = $image_url, updated_at = time::now() RETURN AFTER)",
+        )
+        .query("SELECT meta::id(id) AS id, * FROM $updated")
+        .bind(("id", id.to_string()))
+        .bind(("image", image))
+        .bind(("image_url", image_url))
+        .timed("pizza_repo::update_pizza_image")
+        .await?;
+
+    let pizzas: Vec<Pizza> = result.take(1)?;
+    Ok(pizzas.into_iter().next())
+}
+
+/// Price history of a pizza, oldest first
+pub async fn query_price_history(
+    db: &Surreal<Any>,
+    pizza_id: &str,
+) -> Result<Vec<PriceHistoryEntry>, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "SELECT meta::id(id) AS id, * FROM price_history \
+             WHERE pizza_id = $pizza_id ORDER BY valid_from",
+        )
+        .bind(("pizza_id", pizza_id.to_string()))
+        .timed("pizza_repo::query_price_history")
+        .await?;
+
+    let entries: Vec<PriceHistoryEntry> = result.take(0)?;
+    Ok(entries)
+}
@@ -0,0 +1,123 @@
+use crate::metrics::TimedQuery;
+use shared::models::{Order, PaymentStatus, Refund};
+use surrealdb::engine::any::Any;
+use surrealdb::sql::{self, Value};
+use surrealdb::Surreal;
+
+/// Records a refund and adds its amount to the order in one transaction
+///
+/// Nothing is written when the order's refunded amount is no longer
+/// `expected_refunded_amount`, so two refunds computed from the same state
+/// cannot both be recorded. Returns the updated order, or `None` in that case.
+pub async fn create_refund_for_order(
+    db: &Surreal<Any>,
+    refund: &Refund,
+    expected_refunded_amount: f64,
+    refunded_amount: f64,
+    payment_status: PaymentStatus,
+) -> Result<Option<Order>, surrealdb::Error> {
+    let mut result = db
+        .query("BEGIN TRANSACTION")
+        .query(
+            "LET $updated = (UPDATE type::thing('order', $order_id) SET refunded_amount = $refunded_amount, \
+             payment_status = $payment_status, updated_at = time::now() \
+             WHERE (refunded_amount ?? 0) = $expected_refunded_amount RETURN AFTER)",
+        )
+        .query("IF array::len($updated) > 0 { CREATE refund CONTENT $refund }")
+        .query("SELECT meta::id(id) AS id, * FROM $updated")
+        .query("COMMIT TRANSACTION")
+        .bind(("order_id", refund.order_id.clone()))
+        .bind(("expected_refunded_amount", expected_refunded_amount))
+        .bind(("refunded_amount", refunded_amount))
+        .bind(("payment_status", payment_status))
+        .bind((
+            "refund",
+            refund_content(refund).map_err(|e| surrealdb::Error::Db(*e))?,
+        ))
+        .timed("refund_repo::create_refund_for_order")
+        .await?;
+
+    let orders: Vec<Order> = result.take(2)?;
+    Ok(orders.into_iter().next())
+}
+
+/// Stores the provider's reference once it accepted the refund
+pub async fn update_refund_provider_id(
+    db: &Surreal<Any>,
+    id: &str,
+    provider_refund_id: &str,
+) -> Result<Option<Refund>, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "LET $updated = (UPDATE type::thing('refund', $id) SET provider_refund_id = $provider_refund_id \
+             RETURN AFTER)",
+        )
+        .query("SELECT meta::id(id) AS id, * FROM $updated")
+        .bind(("id", id.to_string()))
+        .bind(("provider_refund_id", provider_refund_id.to_string()))
+        .timed("refund_repo::update_refund_provider_id")
+        .await?;
+
+    let refunds: Vec<Refund> = result.take(1)?;
+    Ok(refunds.into_iter().next())
+}
+
+/// Removes a refund the provider declined and takes its amount off the order again
+///
+/// The amount is subtracted rather than restored, since other refunds may
+/// have been recorded in the meantime.
+pub async fn delete_refund_for_order(
+    db: &Surreal<Any>,
+    refund: &Refund,
+) -> Result<(), surrealdb::Error> {
+    db.query("BEGIN TRANSACTION")
+        .query("DELETE type::thing('refund', $id)")
+        .query(
+            "LET $refunded_amount = (SELECT VALUE math::fixed((refunded_amount ?? 0) - $amount, 2) \
+             FROM ONLY type::thing('order', $order_id))",
+        )
+        .query(
+            "UPDATE type::thing('order', $order_id) SET refunded_amount = $refunded_amount, \
+             payment_status = IF $refunded_amount > 0 { $partially_refunded } ELSE { $paid }, \
+             updated_at = time::now()",
+        )
+        .query("COMMIT TRANSACTION")
+        .bind(("id", refund.id.clone()))
+        .bind(("order_id", refund.order_id.clone()))
+        .bind(("amount", refund.amount))
+        .bind(("partially_refunded", PaymentStatus::PartiallyRefunded))
+        .bind(("paid", PaymentStatus::Paid))
+        .timed("refund_repo::delete_refund_for_order")
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+pub async fn query_refunds_for_order(
+    db: &Surreal<Any>,
+    order_id: &str,
+) -> Result<Vec<Refund>, surrealdb::Error> {
+    let mut result = db
+        .query("SELECT meta::id(id) AS id, * FROM refund WHERE order_id = $order_id ORDER BY created_at")
+        .bind(("order_id", order_id.to_string()))
+        .timed("refund_repo::query_refunds_for_order")
+        .await?;
+
+    let refunds: Vec<Refund> = result.take(0)?;
+    Ok(refunds)
+}
+
+/// Converts a refund into record content with a native SurrealDB datetime
+fn refund_content(refund: &Refund) -> Result<Value, Box<surrealdb::err::Error>> {
+    let mut content = sql::to_value(refund.clone()).map_err(Box::new)?;
+
+    if let Value::Object(fields) = &mut content {
+        fields.insert(
+            "created_at".to_string(),
+            Value::Datetime(refund.created_at.into()),
+        );
+    }
+
+    Ok(content)
+}
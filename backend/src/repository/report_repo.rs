@@ -0,0 +1,180 @@
+use crate::metrics::TimedQuery;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::models::{OrderStatus, PizzaSize};
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+
+/// Orders included in a report
+#[derive(Debug, Clone)]
+pub struct ReportFilter {
+    /// Inclusive start of the range
+    pub from: DateTime<Utc>,
+    /// Exclusive end of the range
+    pub to: DateTime<Utc>,
+    pub statuses: Vec<OrderStatus>,
+}
+
+/// Order totals of one time bucket
+#[derive(Debug, Clone, Deserialize)]
+pub struct PeriodTotals {
+    pub period: String,
+    pub order_count: u64,
+    pub gross_revenue: f64,
+    pub refunded_amount: f64,
+}
+
+/// Order totals of a whole range
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RangeTotals {
+    pub order_count: u64,
+    pub gross_revenue: f64,
+    pub refunded_amount: f64,
+}
+
+/// Units and revenue of one pizza in one size
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemTotals {
+    pub pizza_id: Option<String>,
+    /// Name the pizza was last sold under, unset for orders from before names were kept
+    #[serde(default)]
+    pub product_name: Option<String>,
+    pub size: PizzaSize,
+    pub quantity: u64,
+    pub revenue: f64,
+}
+
+/// Units and revenue of one kind of pizza
+#[derive(Debug, Clone, Deserialize)]
+pub struct KindTotals {
+    pub kind: String,
+    pub quantity: u64,
+    pub revenue: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StatusTotals {
+    pub status: OrderStatus,
+    pub count: u64,
+}
+
+/// Date range condition shared by all report queries
+const ORDER_RANGE: &str = "created_at >= <datetime> $from AND created_at < <datetime> $to";
+
+pub async fn query_sales_by_period(
+    db: &Surreal<Any>,
+    filter: &ReportFilter,
+    period_format: &str,
+) -> Result<Vec<PeriodTotals>, surrealdb::Error> {
+    let mut result = db
+        .query(format!(
+            "SELECT time::format(created_at, $period_format) AS period, count() AS order_count, \
+             math::sum(total_amount) AS gross_revenue, \
+             math::sum(refunded_amount ?? 0) AS refunded_amount \
+             FROM order WHERE {} AND status INSIDE $statuses \
+             GROUP BY period ORDER BY period",
+            ORDER_RANGE
+        ))
+        .bind(("period_format", period_format.to_string()))
+        .bind(range_bindings(filter))
+        .timed("report_repo::query_sales_by_period")
+        .await?;
+
+    let periods: Vec<PeriodTotals> = result.take(0)?;
+    Ok(periods)
+}
+
+pub async fn query_range_totals(
+    db: &Surreal<Any>,
+    filter: &ReportFilter,
+) -> Result<RangeTotals, surrealdb::Error> {
+    let mut result = db
+        .query(format!(
+            "SELECT count() AS order_count, math::sum(total_amount) AS gross_revenue, \
+             math::sum(refunded_amount ?? 0) AS refunded_amount \
+             FROM order WHERE {} AND status INSIDE $statuses GROUP ALL",
+            ORDER_RANGE
+        ))
+        .bind(range_bindings(filter))
+        .timed("report_repo::query_range_totals")
+        .await?;
+
+    let totals: Vec<RangeTotals> = result.take(0)?;
+    Ok(totals.into_iter().next().unwrap_or_default())
+}
+
+/// Order counts per status in the range, ignoring the status filter
+pub async fn query_status_counts(
+    db: &Surreal<Any>,
+    filter: &ReportFilter,
+) -> Result<Vec<StatusTotals>, surrealdb::Error> {
+    let mut result = db
+        .query(format!(
+            "SELECT status, count() AS count FROM order WHERE {} GROUP BY status ORDER BY status",
+            ORDER_RANGE
+        ))
+        .bind(range_bindings(filter))
+        .timed("report_repo::query_status_counts")
+        .await?;
+
+    let counts: Vec<StatusTotals> = result.take(0)?;
+    Ok(counts)
+}
+
+/// Item totals per pizza and size, and per kind of pizza
+///
+/// Revenue is what was charged for a line, including tax whether prices
+/// include it or not, less what was refunded of that line.
+pub async fn query_item_sales(
+    db: &Surreal<Any>,
+    filter: &ReportFilter,
+) -> Result<(Vec<ItemTotals>, Vec<KindTotals>), surrealdb::Error> {
+    let mut result = db
+        .query(format!(
+            "LET $orders = (SELECT meta::id(id) AS id, items, created_at FROM order \
+             WHERE {} AND status INSIDE $statuses ORDER BY created_at)",
+            ORDER_RANGE
+        ))
+        .query(
+            "LET $refund_lines = array::flatten((SELECT VALUE lines FROM refund \
+             WHERE order_id INSIDE $orders.id))",
+        )
+        .query(
+            "LET $items = (SELECT *, (tax.gross ?? subtotal) - math::sum((SELECT VALUE amount \
+             FROM $refund_lines WHERE order_item_id = $parent.id)) AS revenue \
+             FROM array::flatten($orders.items))",
+        )
+        .query(
+            "SELECT item_type.type AS kind, item_type.pizza_id AS pizza_id, \
+             (item_type.size ?? item_type.custom.size) AS size, \
+             array::last(product_name) AS product_name, \
+             math::sum(quantity) AS quantity, math::sum(revenue) AS revenue \
+             FROM $items GROUP BY kind, pizza_id, size ORDER BY revenue DESC",
+        )
+        .query(
+            "SELECT item_type.type AS kind, math::sum(quantity) AS quantity, \
+             math::sum(revenue) AS revenue FROM $items GROUP BY kind ORDER BY kind",
+        )
+        .bind(range_bindings(filter))
+        .timed("report_repo::query_item_sales")
+        .await?;
+
+    let items: Vec<ItemTotals> = result.take(3)?;
+    let kinds: Vec<KindTotals> = result.take(4)?;
+    Ok((items, kinds))
+}
+
+fn range_bindings(filter: &ReportFilter) -> RangeBindings {
+    RangeBindings {
+        from: filter.from.to_rfc3339(),
+        to: filter.to.to_rfc3339(),
+        statuses: filter.statuses.clone(),
+    }
+}
+
+#[derive(Serialize)]
+struct RangeBindings {
+    from: String,
+    to: String,
+    statuses: Vec<OrderStatus>,
+}
@@ -1,7 +1,10 @@
-use surrealdb::engine::remote::ws::Client;
+use surrealdb::engine::any::Any;
 use surrealdb::Surreal;
 use tracing::{info, warn};
 
+/// Version of `database/schema.surql`, recorded in `schema_version:current` when applied
+pub const SCHEMA_VERSION: u32 = 4;
+
 /// Seeds the database with schema and initial data
 ///
 /// This function reads the schema.surql and init.surql files and executes them
@@ -14,7 +17,7 @@ use tracing::{info, warn};
 /// # Returns
 /// Result indicating success or failure of seeding operation
 pub async fn seed_database(
-    db: &Surreal<Client>,
+    db: &Surreal<Any>,
     force_reseed: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     info!("Starting database seeding...");
@@ -43,6 +46,7 @@ pub async fn seed_database(
     info!("Executing schema definition...");
     let schema_sql = include_str!("../../../database/schema.surql");
     execute_sql_script(db, schema_sql, "schema").await?;
+    record_schema_version(db).await?;
 
     // Execute initialization data
     info!("Executing initialization data...");
@@ -65,7 +69,7 @@ pub async fn seed_database(
 
 /// Executes a SQL script and handles errors
 async fn execute_sql_script(
-    db: &Surreal<Client>,
+    db: &Surreal<Any>,
     sql: &str,
     script_name: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -81,15 +85,23 @@ async fn execute_sql_script(
     }
 }
 
+/// Remembers which schema version the database was set up with
+async fn record_schema_version(db: &Surreal<Any>) -> Result<(), Box<dyn std::error::Error>> {
+    db.query("UPSERT schema_version:current SET version = $version, applied_at = time::now()")
+        .bind(("version", SCHEMA_VERSION))
+        .await?
+        .check()?;
+    info!("Recorded schema version {}", SCHEMA_VERSION);
+    Ok(())
+}
+
 /// Initialize database with schema only (no seed data)
 /// Useful for production environments where data should be loaded separately
-#[allow(dead_code)]
-pub async fn init_schema_only(
-    db: &Surreal<Client>,
-) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn init_schema_only(db: &Surreal<Any>) -> Result<(), Box<dyn std::error::Error>> {
     info!("Initializing database schema...");
     let schema_sql = include_str!("../../../database/schema.surql");
     execute_sql_script(db, schema_sql, "schema").await?;
+    record_schema_version(db).await?;
     info!("Schema initialization completed");
     Ok(())
 }
@@ -0,0 +1,133 @@
+use crate::metrics::TimedQuery;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+
+/// Verification state of a phone number, keyed by the number in E.164 format
+///
+/// Codes and tokens are stored as SHA-256 hashes only.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PhoneVerification {
+    pub code_hash: Option<String>,
+    pub code_expires_at: Option<DateTime<Utc>>,
+    /// Codes entered for the current code
+    pub attempts: u32,
+    pub token_hash: Option<String>,
+    pub token_expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+struct CodeBindings {
+    phone: String,
+    code_hash: String,
+    expires_at: String,
+    resend_interval_seconds: i64,
+}
+
+#[derive(Serialize)]
+struct TokenBindings {
+    phone: String,
+    token_hash: String,
+    expires_at: String,
+}
+
+pub async fn query_verification(
+    db: &Surreal<Any>,
+    phone: &str,
+) -> Result<Option<PhoneVerification>, surrealdb::Error> {
+    let mut result = db
+        .query("SELECT meta::id(id) AS id, * FROM type::thing('phone_verification', $phone)")
+        .bind(("phone", phone.to_string()))
+        .timed("verification_repo::query_verification")
+        .await?;
+
+    let verifications: Vec<PhoneVerification> = result.take(0)?;
+    Ok(verifications.into_iter().next())
+}
+
+/// Stores a newly sent code, replacing the previous one
+///
+/// Nothing is stored when a code was sent to the number less than
+/// `resend_interval_seconds` ago; returns whether the code was stored.
+pub async fn save_code(
+    db: &Surreal<Any>,
+    phone: &str,
+    code_hash: &str,
+    expires_at: DateTime<Utc>,
+    resend_interval_seconds: i64,
+) -> Result<bool, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "UPSERT type::thing('phone_verification', $phone) SET \
+             code_hash = $code_hash, \
+             code_expires_at = <datetime> $expires_at, \
+             attempts = 0, \
+             sent_at = time::now() \
+             WHERE sent_at = NONE \
+             OR sent_at <= time::now() - duration::from::secs($resend_interval_seconds) \
+             RETURN AFTER",
+        )
+        .bind(CodeBindings {
+            phone: phone.to_string(),
+            code_hash: code_hash.to_string(),
+            expires_at: expires_at.to_rfc3339(),
+            resend_interval_seconds,
+        })
+        .timed("verification_repo::save_code")
+        .await?;
+
+    let saved: Vec<PhoneVerification> = result.take(0)?;
+    Ok(!saved.is_empty())
+}
+
+/// Counts an attempt at entering the current code
+///
+/// The attempt is only counted while fewer than `max_attempts` were made, so
+/// concurrent guesses cannot exceed the limit. Returns the verification after
+/// counting, or `None` when no attempts are left or no code was sent.
+pub async fn consume_attempt(
+    db: &Surreal<Any>,
+    phone: &str,
+    max_attempts: u32,
+) -> Result<Option<PhoneVerification>, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "UPDATE type::thing('phone_verification', $phone) SET attempts += 1 \
+             WHERE attempts < $max_attempts \
+             RETURN AFTER",
+        )
+        .bind(("phone", phone.to_string()))
+        .bind(("max_attempts", max_attempts))
+        .timed("verification_repo::consume_attempt")
+        .await?;
+
+    let verifications: Vec<PhoneVerification> = result.take(0)?;
+    Ok(verifications.into_iter().next())
+}
+
+/// Stores the token issued for a confirmed code; the code cannot be used again
+pub async fn save_token(
+    db: &Surreal<Any>,
+    phone: &str,
+    token_hash: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<(), surrealdb::Error> {
+    db.query(
+        "UPDATE type::thing('phone_verification', $phone) SET \
+         code_hash = NONE, \
+         code_expires_at = NONE, \
+         token_hash = $token_hash, \
+         token_expires_at = <datetime> $expires_at",
+    )
+    .bind(TokenBindings {
+        phone: phone.to_string(),
+        token_hash: token_hash.to_string(),
+        expires_at: expires_at.to_rfc3339(),
+    })
+    .timed("verification_repo::save_token")
+    .await?
+    .check()?;
+
+    Ok(())
+}
@@ -0,0 +1,296 @@
+use crate::metrics::TimedQuery;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use shared::models::{WebhookDelivery, WebhookDeliveryStatus, WebhookEndpoint, WebhookEvent};
+use std::time::Duration;
+use surrealdb::engine::any::Any;
+use surrealdb::sql::{self, Value};
+use surrealdb::Surreal;
+
+#[derive(Serialize)]
+struct WebhookBindings {
+    id: String,
+    url: String,
+    events: Vec<WebhookEvent>,
+    description: Option<String>,
+    is_active: bool,
+}
+
+#[derive(Serialize)]
+struct FailedAttemptBindings {
+    id: String,
+    status: WebhookDeliveryStatus,
+    attempts: u32,
+    status_code: Option<u16>,
+    error: String,
+    retry_at: Option<String>,
+}
+
+pub async fn create_webhook_in_db(
+    db: &Surreal<Any>,
+    webhook: &WebhookEndpoint,
+) -> Result<WebhookEndpoint, surrealdb::Error> {
+    let mut result = db
+        .query("LET $created = (CREATE webhook CONTENT $webhook)")
+        .query("SELECT meta::id(id) AS id, * FROM $created")
+        .bind((
+            "webhook",
+            webhook_content(webhook).map_err(|e| surrealdb::Error::Db(*e))?,
+        ))
+        .timed("webhook_repo::create_webhook_in_db")
+        .await?;
+
+    let webhooks: Vec<WebhookEndpoint> = result.take(1)?;
+
+    webhooks.into_iter().next().ok_or_else(|| {
+        surrealdb::Error::Api(surrealdb::error::Api::Query(
+            "Failed to retrieve created webhook".to_string(),
+        ))
+    })
+}
+
+pub async fn query_webhooks(db: &Surreal<Any>) -> Result<Vec<WebhookEndpoint>, surrealdb::Error> {
+    let mut result = db
+        .query("SELECT meta::id(id) AS id, * FROM webhook ORDER BY created_at")
+        .timed("webhook_repo::query_webhooks")
+        .await?;
+
+    let webhooks: Vec<WebhookEndpoint> = result.take(0)?;
+    Ok(webhooks)
+}
+
+pub async fn query_webhook_by_id(
+    db: &Surreal<Any>,
+    id: &str,
+) -> Result<Option<WebhookEndpoint>, surrealdb::Error> {
+    let mut result = db
+        .query("SELECT meta::id(id) AS id, * FROM type::thing('webhook', $id)")
+        .bind(("id", id.to_string()))
+        .timed("webhook_repo::query_webhook_by_id")
+        .await?;
+
+    let webhooks: Vec<WebhookEndpoint> = result.take(0)?;
+    Ok(webhooks.into_iter().next())
+}
+
+pub async fn update_webhook(
+    db: &Surreal<Any>,
+    id: &str,
+    url: String,
+    events: Vec<WebhookEvent>,
+    description: Option<String>,
+    is_active: bool,
+) -> Result<Option<WebhookEndpoint>, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "LET $updated = (UPDATE type::thing('webhook', $id) SET url = $url, \
+             events = $events, description = $description, is_active = $is_active, \
+             updated_at = time::now() RETURN AFTER)",
+        )
+        .query("SELECT meta::id(id) AS id, * FROM $updated")
+        .bind(WebhookBindings {
+            id: id.to_string(),
+            url,
+            events,
+            description,
+            is_active,
+        })
+        .timed("webhook_repo::update_webhook")
+        .await?;
+
+    let webhooks: Vec<WebhookEndpoint> = result.take(1)?;
+    Ok(webhooks.into_iter().next())
+}
+
+/// Deletes a webhook endpoint; its delivery log is kept
+pub async fn delete_webhook(db: &Surreal<Any>, id: &str) -> Result<(), surrealdb::Error> {
+    db.query("DELETE type::thing('webhook', $id)")
+        .bind(("id", id.to_string()))
+        .timed("webhook_repo::delete_webhook")
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+pub async fn create_delivery_in_db(
+    db: &Surreal<Any>,
+    delivery: &WebhookDelivery,
+) -> Result<(), surrealdb::Error> {
+    db.query("CREATE webhook_delivery CONTENT $delivery")
+        .bind((
+            "delivery",
+            delivery_content(delivery).map_err(|e| surrealdb::Error::Db(*e))?,
+        ))
+        .timed("webhook_repo::create_delivery_in_db")
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+/// Claims pending deliveries that are due, oldest first
+///
+/// Claimed deliveries are pushed back by `lease`, so they are retried if the
+/// worker dies mid-delivery.
+pub async fn claim_due_deliveries(
+    db: &Surreal<Any>,
+    limit: u32,
+    lease: Duration,
+) -> Result<Vec<WebhookDelivery>, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "LET $due = (SELECT id, next_attempt_at FROM webhook_delivery \
+             WHERE status = 'Pending' AND next_attempt_at <= time::now() \
+             ORDER BY next_attempt_at LIMIT $limit)",
+        )
+        .query(
+            "LET $claimed = (UPDATE $due.id \
+             SET next_attempt_at = time::now() + <duration> $lease RETURN AFTER)",
+        )
+        .query("SELECT meta::id(id) AS id, * FROM $claimed ORDER BY created_at")
+        .bind(("limit", limit))
+        .bind(("lease", format!("{}s", lease.as_secs())))
+        .timed("webhook_repo::claim_due_deliveries")
+        .await?;
+
+    let deliveries: Vec<WebhookDelivery> = result.take(2)?;
+    Ok(deliveries)
+}
+
+pub async fn mark_delivery_delivered(
+    db: &Surreal<Any>,
+    id: &str,
+    attempts: u32,
+    status_code: u16,
+) -> Result<(), surrealdb::Error> {
+    db.query(
+        "UPDATE type::thing('webhook_delivery', $id) \
+         SET status = 'Delivered', attempts = $attempts, last_status_code = $status_code, \
+         last_error = NONE, delivered_at = time::now()",
+    )
+    .bind(("id", id.to_string()))
+    .bind(("attempts", attempts))
+    .bind(("status_code", status_code))
+    .timed("webhook_repo::mark_delivery_delivered")
+    .await?
+    .check()?;
+
+    Ok(())
+}
+
+/// Records a failed attempt
+///
+/// With `retry_at` the delivery stays pending until then, otherwise it moves
+/// to the dead letters.
+pub async fn record_failed_attempt(
+    db: &Surreal<Any>,
+    id: &str,
+    attempts: u32,
+    status_code: Option<u16>,
+    error: &str,
+    retry_at: Option<DateTime<Utc>>,
+) -> Result<(), surrealdb::Error> {
+    let status = match retry_at {
+        Some(_) => WebhookDeliveryStatus::Pending,
+        None => WebhookDeliveryStatus::DeadLetter,
+    };
+
+    db.query(
+        "UPDATE type::thing('webhook_delivery', $id) \
+         SET status = $status, attempts = $attempts, last_status_code = $status_code, \
+         last_error = $error, \
+         next_attempt_at = IF $retry_at THEN <datetime> $retry_at ELSE next_attempt_at END",
+    )
+    .bind(FailedAttemptBindings {
+        id: id.to_string(),
+        status,
+        attempts,
+        status_code,
+        error: error.to_string(),
+        retry_at: retry_at.map(|at| at.to_rfc3339()),
+    })
+    .timed("webhook_repo::record_failed_attempt")
+    .await?
+    .check()?;
+
+    Ok(())
+}
+
+/// Moves a dead letter back into the queue for an immediate attempt
+pub async fn requeue_delivery(
+    db: &Surreal<Any>,
+    id: &str,
+) -> Result<Option<WebhookDelivery>, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "LET $updated = (UPDATE type::thing('webhook_delivery', $id) \
+             SET status = 'Pending', attempts = 0, next_attempt_at = time::now() \
+             WHERE status = 'DeadLetter' RETURN AFTER)",
+        )
+        .query("SELECT meta::id(id) AS id, * FROM $updated")
+        .bind(("id", id.to_string()))
+        .timed("webhook_repo::requeue_delivery")
+        .await?;
+
+    let deliveries: Vec<WebhookDelivery> = result.take(1)?;
+    Ok(deliveries.into_iter().next())
+}
+
+/// Most recent deliveries to an endpoint, optionally of one status
+pub async fn query_deliveries(
+    db: &Surreal<Any>,
+    endpoint_id: &str,
+    status: Option<WebhookDeliveryStatus>,
+    limit: u32,
+) -> Result<Vec<WebhookDelivery>, surrealdb::Error> {
+    let mut result = db
+        .query(
+            "SELECT meta::id(id) AS id, * FROM webhook_delivery \
+             WHERE endpoint_id = $endpoint_id AND ($status = NONE OR status = $status) \
+             ORDER BY created_at DESC LIMIT $limit",
+        )
+        .bind(("endpoint_id", endpoint_id.to_string()))
+        .bind(("status", status))
+        .bind(("limit", limit))
+        .timed("webhook_repo::query_deliveries")
+        .await?;
+
+    let deliveries: Vec<WebhookDelivery> = result.take(0)?;
+    Ok(deliveries)
+}
+
+/// Converts a webhook endpoint into record content with a native SurrealDB datetime
+fn webhook_content(webhook: &WebhookEndpoint) -> Result<Value, Box<surrealdb::err::Error>> {
+    let mut content = sql::to_value(webhook.clone()).map_err(Box::new)?;
+
+    if let Value::Object(fields) = &mut content {
+        fields.insert(
+            "created_at".to_string(),
+            Value::Datetime(webhook.created_at.into()),
+        );
+    }
+
+    Ok(content)
+}
+
+/// Converts a delivery into record content with native SurrealDB datetimes
+fn delivery_content(delivery: &WebhookDelivery) -> Result<Value, Box<surrealdb::err::Error>> {
+    let mut content = sql::to_value(delivery.clone()).map_err(Box::new)?;
+
+    if let Value::Object(fields) = &mut content {
+        fields.insert(
+            "next_attempt_at".to_string(),
+            Value::Datetime(delivery.next_attempt_at.into()),
+        );
+        fields.insert(
+            "created_at".to_string(),
+            Value::Datetime(delivery.created_at.into()),
+        );
+        fields.remove("last_status_code");
+        fields.remove("last_error");
+        fields.remove("delivered_at");
+    }
+
+    Ok(content)
+}
@@ -1,18 +1,139 @@
 use crate::config::AppState;
-use crate::handlers::{health_handler, order_handler, pizza_handler};
+use crate::handlers::{
+    audit_handler, backup_handler, customer_handler, health_handler, image_handler, order_handler,
+    payment_handler, pizza_handler, quote_handler, receipt_handler, refund_handler, report_handler,
+    verification_handler, webhook_handler,
+};
+use crate::middleware::actor::{as_admin, as_customer, as_payment_provider};
+use crate::middleware::database::require_database;
 use axum::{
-    routing::{get, post},
+    extract::DefaultBodyLimit,
+    middleware::{from_fn, from_fn_with_state},
+    routing::{get, post, put},
     Router,
 };
 
-pub fn create_router() -> Router<AppState> {
+pub fn create_router(state: &AppState) -> Router<AppState> {
     Router::new()
-        // Health check
-        .route("/health", get(health_handler::health_check))
         // Pizza routes
         .route("/pizzas", get(pizza_handler::get_all_pizzas))
         .route("/pizzas/{id}", get(pizza_handler::get_pizza_by_id))
+        // Pricing routes
+        .route("/quote", post(quote_handler::create_quote_handler))
         // Order routes
         .route("/orders", post(order_handler::create_order_handler))
         .route("/orders/{id}", get(order_handler::get_order_by_id_handler))
+        .route(
+            "/orders/{id}/receipt",
+            get(receipt_handler::get_receipt_handler),
+        )
+        // Returning customers
+        .route(
+            "/customers/lookup",
+            post(customer_handler::lookup_customer_handler),
+        )
+        // Phone verification
+        .route(
+            "/verify/phone",
+            post(verification_handler::send_code_handler),
+        )
+        .route(
+            "/verify/confirm",
+            post(verification_handler::confirm_code_handler),
+        )
+        // Changes above are audited as made by the customer
+        .route_layer(from_fn(as_customer))
+        // Payment routes
+        .route(
+            "/payments/webhook",
+            post(payment_handler::payment_webhook_handler).layer(from_fn(as_payment_provider)),
+        )
+        // Admin routes, protected by the admin token
+        .nest("/admin", create_admin_router())
+        // Everything above needs the database
+        .route_layer(from_fn_with_state(state.clone(), require_database))
+        // Health checks, answered during a database outage too
+        .route("/health", get(health_handler::readiness_check))
+        .route("/health/live", get(health_handler::liveness_check))
+        .route("/health/ready", get(health_handler::readiness_check))
+        // Uploaded pizza images, kept outside the database
+        .route("/images/{*key}", get(image_handler::get_image_handler))
+}
+
+fn create_admin_router() -> Router<AppState> {
+    Router::new()
+        .route("/orders", get(order_handler::list_orders_handler))
+        .route(
+            "/orders/{id}/status",
+            put(order_handler::update_order_status_handler),
+        )
+        .route(
+            "/orders/{id}/refunds",
+            get(refund_handler::get_refunds_handler).post(refund_handler::create_refund_handler),
+        )
+        // Customers
+        .route(
+            "/customers",
+            get(customer_handler::search_customers_handler),
+        )
+        .route(
+            "/customers/{id}",
+            get(customer_handler::get_customer_handler),
+        )
+        // Menu editing
+        .route("/pizzas", get(pizza_handler::get_menu_handler))
+        .route("/pizzas/{id}", put(pizza_handler::update_pizza_handler))
+        .route(
+            "/pizzas/{id}/prices",
+            get(pizza_handler::get_price_history_handler),
+        )
+        .route(
+            "/pizzas/{id}/image",
+            put(image_handler::upload_pizza_image_handler)
+                .delete(image_handler::delete_pizza_image_handler)
+                .layer(DefaultBodyLimit::max(image_handler::MAX_IMAGE_SIZE)),
+        )
+        // Webhooks
+        .route(
+            "/webhooks",
+            get(webhook_handler::list_webhooks_handler)
+                .post(webhook_handler::create_webhook_handler),
+        )
+        .route(
+            "/webhooks/{id}",
+            put(webhook_handler::update_webhook_handler)
+                .delete(webhook_handler::delete_webhook_handler),
+        )
+        .route(
+            "/webhooks/{id}/deliveries",
+            get(webhook_handler::get_deliveries_handler),
+        )
+        .route(
+            "/webhooks/deliveries/{id}/retry",
+            post(webhook_handler::retry_delivery_handler),
+        )
+        // Backup and restore
+        .route("/backup", get(backup_handler::create_backup_handler))
+        .route(
+            "/restore",
+            post(backup_handler::restore_backup_handler)
+                .layer(DefaultBodyLimit::max(backup_handler::MAX_ARCHIVE_SIZE)),
+        )
+        // Reports
+        .route(
+            "/reports/sales",
+            get(report_handler::get_sales_report_handler),
+        )
+        .route(
+            "/reports/items",
+            get(report_handler::get_item_sales_report_handler),
+        )
+        .route(
+            "/reports/summary",
+            get(report_handler::get_sales_summary_handler),
+        )
+        // Audit log
+        .route("/audit", get(audit_handler::get_audit_events_handler))
+        // Changes are audited as made by the admin named in X-Admin-Name
+        .route_layer(from_fn(as_admin))
 }
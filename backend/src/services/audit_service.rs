@@ -0,0 +1,139 @@
+use crate::middleware::actor::current_actor;
+use crate::middleware::error::AppError;
+use crate::middleware::request_id::current_request_id;
+use crate::repository::audit_repo::{self, AuditFilter};
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use shared::dto::GetAuditEventsResponse;
+use shared::models::{AuditAction, AuditEvent, FieldChange};
+use std::collections::{BTreeMap, BTreeSet};
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+use uuid::Uuid;
+
+/// Fields that change with every write and say nothing on their own
+const IGNORED_FIELDS: &[&str] = &["id", "updated_at"];
+/// Fields whose values are never written to the audit log, only that they changed
+const REDACTED_FIELDS: &[&str] = &["secret"];
+const REDACTED: &str = "[redacted]";
+/// Events listed when the client does not ask for a number
+const DEFAULT_EVENT_LIMIT: u32 = 100;
+const MAX_EVENT_LIMIT: u32 = 1000;
+
+/// Records a change to an entity in the audit log
+///
+/// `before` is `None` for created and `after` for deleted entities; only the
+/// fields that differ are kept. The actor and request id are taken from the
+/// current request. The change itself is already saved, so failures are only
+/// logged.
+pub async fn record<T: Serialize>(
+    db: &Surreal<Any>,
+    action: AuditAction,
+    entity_id: &str,
+    before: Option<&T>,
+    after: Option<&T>,
+) {
+    let changes = diff(fields(before), fields(after));
+    if changes.is_empty() {
+        return;
+    }
+
+    let event = AuditEvent {
+        id: Uuid::new_v4().to_string(),
+        actor: current_actor(),
+        action,
+        entity_type: action.entity_type().to_string(),
+        entity_id: entity_id.to_string(),
+        changes,
+        request_id: current_request_id(),
+        created_at: Utc::now(),
+    };
+
+    if let Err(e) = audit_repo::create_audit_event_in_db(db, &event).await {
+        tracing::error!(
+            "Failed to record {} of {} in the audit log: {}",
+            action.as_str(),
+            entity_id,
+            e
+        );
+    }
+}
+
+/// Audit log entries matching the filter, newest first
+pub async fn query_events(
+    db: &Surreal<Any>,
+    filter: AuditFilter,
+    limit: Option<u32>,
+) -> Result<GetAuditEventsResponse, AppError> {
+    if matches!((filter.from, filter.to), (Some(from), Some(to)) if from >= to) {
+        return Err(AppError::ValidationError(vec![
+            "from must be before to".to_string()
+        ]));
+    }
+    if filter.entity_id.is_some() && filter.entity_type.is_none() {
+        return Err(AppError::ValidationError(vec![
+            "entity_id needs an entity_type".to_string(),
+        ]));
+    }
+
+    let limit = limit
+        .unwrap_or(DEFAULT_EVENT_LIMIT)
+        .clamp(1, MAX_EVENT_LIMIT);
+    let events = audit_repo::query_audit_events(db, &filter, limit).await?;
+    Ok(GetAuditEventsResponse { events })
+}
+
+fn fields<T: Serialize>(entity: Option<&T>) -> Map<String, Value> {
+    match entity.map(serde_json::to_value) {
+        Some(Ok(Value::Object(fields))) => fields,
+        Some(Ok(_)) | None => Map::new(),
+        Some(Err(e)) => {
+            tracing::error!("Failed to encode an audited entity: {}", e);
+            Map::new()
+        }
+    }
+}
+
+/// Top-level fields that differ, with `null` for a field one side does not have
+fn diff(
+    mut before: Map<String, Value>,
+    mut after: Map<String, Value>,
+) -> BTreeMap<String, FieldChange> {
+    let names: BTreeSet<String> = before.keys().chain(after.keys()).cloned().collect();
+
+    let mut changes = BTreeMap::new();
+    for name in names {
+        if IGNORED_FIELDS.contains(&name.as_str()) {
+            continue;
+        }
+        let old = before.remove(&name).unwrap_or(Value::Null);
+        let new = after.remove(&name).unwrap_or(Value::Null);
+        if old == new {
+            continue;
+        }
+
+        let change = if REDACTED_FIELDS.contains(&name.as_str()) {
+            FieldChange {
+                before: redact(old),
+                after: redact(new),
+            }
+        } else {
+            FieldChange {
+                before: old,
+                after: new,
+            }
+        };
+        changes.insert(name, change);
+    }
+
+    changes
+}
+
+fn redact(value: Value) -> Value {
+    if value.is_null() {
+        value
+    } else {
+        Value::String(REDACTED.to_string())
+    }
+}
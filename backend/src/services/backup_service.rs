@@ -0,0 +1,292 @@
+use crate::config::BackupConfig;
+use crate::middleware::error::AppError;
+use crate::repository::backup_repo::{self, BACKUP_TABLES};
+use crate::repository::db::Database;
+use crate::repository::{health_repo, seed::SCHEMA_VERSION};
+use crate::services::audit_service;
+use crate::services::health_service::WorkerHeartbeat;
+use crate::services::pizza_service::MenuCache;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::dto::{RestoreConflictPolicy, RestoreResponse, RestoredTable};
+use shared::models::AuditAction;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+
+/// Version of the archive layout, bumped when it changes incompatibly
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+/// Records inserted per query during a restore
+const INSERT_BATCH_SIZE: usize = 500;
+const ARCHIVE_PREFIX: &str = "royalpizza-backup-";
+const ARCHIVE_SUFFIX: &str = ".json";
+
+/// Everything needed to rebuild the menu and orders in another database
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupArchive {
+    pub format_version: u32,
+    /// Schema version of the database the archive was made from
+    pub schema_version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Backend version that made the archive
+    pub app_version: String,
+    /// Records per table, with record keys as `id` and datetimes as RFC 3339
+    pub tables: BTreeMap<String, Vec<serde_json::Value>>,
+}
+
+impl BackupArchive {
+    /// File name of the archive, ordered by creation time
+    pub fn file_name(&self) -> String {
+        format!(
+            "{}{}{}",
+            ARCHIVE_PREFIX,
+            self.created_at.format("%Y%m%dT%H%M%SZ"),
+            ARCHIVE_SUFFIX
+        )
+    }
+}
+
+pub async fn create_backup(db: &Surreal<Any>) -> Result<BackupArchive, AppError> {
+    let mut tables = BTreeMap::new();
+    for table in BACKUP_TABLES {
+        let records = backup_repo::query_table_records(db, table).await?;
+        tables.insert(table.name.to_string(), records);
+    }
+
+    Ok(BackupArchive {
+        format_version: ARCHIVE_FORMAT_VERSION,
+        schema_version: SCHEMA_VERSION,
+        created_at: Utc::now(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        tables,
+    })
+}
+
+/// What a restore did, as recorded in the audit log under the archive name
+#[derive(Serialize)]
+struct RestoreAudit<'a> {
+    on_conflict: RestoreConflictPolicy,
+    tables: &'a [RestoredTable],
+}
+
+/// Restores an archive made by `create_backup`
+///
+/// The whole archive is checked before anything is written: its format and
+/// schema version must match this backend, and with `Fail` every restored
+/// table must be empty. `Overwrite` deletes and reinserts existing records in
+/// one transaction per batch, so a failed insert keeps the old ones. Audit log
+/// entries are never replaced, `Overwrite` skips the ones that exist.
+pub async fn restore_backup(
+    db: &Surreal<Any>,
+    menu_cache: &MenuCache,
+    archive: BackupArchive,
+    policy: RestoreConflictPolicy,
+) -> Result<RestoreResponse, AppError> {
+    if archive.format_version != ARCHIVE_FORMAT_VERSION {
+        return Err(AppError::ValidationError(vec![format!(
+            "Archive format version {} is not supported, expected {}",
+            archive.format_version, ARCHIVE_FORMAT_VERSION
+        )]));
+    }
+    if archive.schema_version != SCHEMA_VERSION {
+        return Err(AppError::ValidationError(vec![format!(
+            "Archive was made with schema version {}, this backend uses {}",
+            archive.schema_version, SCHEMA_VERSION
+        )]));
+    }
+
+    let applied_version = health_repo::query_schema_version(db).await?;
+    if applied_version != Some(SCHEMA_VERSION) {
+        return Err(AppError::Conflict(format!(
+            "The database schema is not at version {}, apply database/schema.surql first",
+            SCHEMA_VERSION
+        )));
+    }
+
+    let archive_name = archive.file_name();
+    let mut archived = archive.tables;
+    let mut errors: Vec<String> = archived
+        .keys()
+        .filter(|name| backup_repo::find_backup_table(name).is_none())
+        .map(|name| format!("Unknown table in archive: {}", name))
+        .collect();
+
+    // Convert every record up front, so a bad one does not leave a half restore
+    let mut tables = Vec::new();
+    for table in BACKUP_TABLES {
+        let mut records = Vec::new();
+        for record in archived.remove(table.name).unwrap_or_default() {
+            match backup_repo::record_content(table, record) {
+                Ok(record) => records.push(record),
+                Err(error) => errors.push(error),
+            }
+        }
+        let existing: HashSet<String> = backup_repo::query_record_ids(db, table)
+            .await?
+            .into_iter()
+            .collect();
+        tables.push((table, records, existing));
+    }
+    if !errors.is_empty() {
+        return Err(AppError::ValidationError(errors));
+    }
+
+    if policy == RestoreConflictPolicy::Fail {
+        let non_empty: Vec<String> = tables
+            .iter()
+            .filter(|(_, _, existing)| !existing.is_empty())
+            .map(|(table, _, existing)| format!("{} ({} records)", table.name, existing.len()))
+            .collect();
+        if !non_empty.is_empty() {
+            return Err(AppError::Conflict(format!(
+                "Restore needs empty tables, but these have records: {}. Start a new \
+                 installation with DATABASE_SEED=schema to leave out the standard menu, \
+                 or restore with skip or overwrite",
+                non_empty.join(", ")
+            )));
+        }
+    }
+
+    let mut restored_tables = Vec::new();
+    for (table, records, existing) in tables {
+        let (conflicting, new): (Vec<_>, Vec<_>) = records
+            .into_iter()
+            .partition(|(id, _)| existing.contains(id));
+
+        let mut summary = RestoredTable {
+            table: table.name.to_string(),
+            restored: new.len(),
+            skipped: 0,
+            replaced: 0,
+        };
+        let mut records: Vec<_> = new.into_iter().map(|(_, content)| content).collect();
+
+        match policy {
+            RestoreConflictPolicy::Fail => {}
+            RestoreConflictPolicy::Skip => summary.skipped = conflicting.len(),
+            RestoreConflictPolicy::Overwrite if table.append_only => {
+                summary.skipped = conflicting.len()
+            }
+            RestoreConflictPolicy::Overwrite => {
+                summary.replaced = conflicting.len();
+                summary.restored += conflicting.len();
+                for batch in conflicting.chunks(INSERT_BATCH_SIZE) {
+                    let (ids, contents): (Vec<_>, Vec<_>) = batch.iter().cloned().unzip();
+                    backup_repo::replace_records(db, table, ids, contents).await?;
+                }
+            }
+        }
+
+        while !records.is_empty() {
+            let rest = records.split_off(records.len().min(INSERT_BATCH_SIZE));
+            backup_repo::insert_records(db, table, records).await?;
+            records = rest;
+        }
+
+        tracing::info!(
+            "Restored {} {} records ({} skipped, {} replaced)",
+            summary.restored,
+            table.name,
+            summary.skipped,
+            summary.replaced
+        );
+        restored_tables.push(summary);
+    }
+
+    // The restored menu replaces whatever was cached
+    menu_cache.invalidate();
+
+    let restored = RestoreResponse {
+        tables: restored_tables,
+    };
+    audit_service::record(
+        db,
+        AuditAction::BackupRestored,
+        &archive_name,
+        None,
+        Some(&RestoreAudit {
+            on_conflict: policy,
+            tables: &restored.tables,
+        }),
+    )
+    .await;
+
+    Ok(restored)
+}
+
+/// Writes a backup to `dir` and returns the path of the archive
+///
+/// The archive is written under a temporary name and renamed when complete,
+/// so an interrupted backup never looks like a usable one.
+pub async fn write_backup(db: &Surreal<Any>, dir: &Path) -> Result<PathBuf, AppError> {
+    let archive = create_backup(db).await?;
+    let json = serde_json::to_vec(&archive)
+        .map_err(|e| AppError::InternalError(format!("Failed to encode backup: {}", e)))?;
+
+    let path = dir.join(archive.file_name());
+    let partial = path.with_extension("json.partial");
+    let write = async {
+        tokio::fs::create_dir_all(dir).await?;
+        tokio::fs::write(&partial, &json).await?;
+        tokio::fs::rename(&partial, &path).await
+    };
+    write.await.map_err(|e| {
+        AppError::InternalError(format!(
+            "Failed to write backup to {}: {}",
+            dir.display(),
+            e
+        ))
+    })?;
+
+    Ok(path)
+}
+
+/// Deletes the oldest archives in `dir` beyond the newest `keep`
+async fn prune_backups(dir: &Path, keep: usize) -> std::io::Result<usize> {
+    let mut archives = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with(ARCHIVE_PREFIX) && name.ends_with(ARCHIVE_SUFFIX) {
+            archives.push(entry.path());
+        }
+    }
+
+    // Names sort by creation time
+    archives.sort();
+    let excess = archives.len().saturating_sub(keep);
+    for path in &archives[..excess] {
+        tokio::fs::remove_file(path).await?;
+    }
+
+    Ok(excess)
+}
+
+/// Writes a backup every `config.interval` and keeps the newest `config.retention`
+pub async fn run_scheduled_backups(db: Database, config: BackupConfig, heartbeat: WorkerHeartbeat) {
+    let Some(dir) = config.dir else {
+        return;
+    };
+
+    let mut interval = tokio::time::interval(config.interval);
+    loop {
+        interval.tick().await;
+
+        match write_backup(&db.client(), &dir).await {
+            Ok(path) => tracing::info!("Wrote backup {}", path.display()),
+            Err(e) => {
+                tracing::error!("Scheduled backup failed: {:?}", e);
+                heartbeat.failed(format!("{:?}", e));
+                continue;
+            }
+        }
+
+        match prune_backups(&dir, config.retention).await {
+            Ok(0) => {}
+            Ok(deleted) => tracing::info!("Deleted {} old backups", deleted),
+            Err(e) => tracing::warn!("Failed to delete old backups: {}", e),
+        }
+        heartbeat.ran();
+    }
+}
@@ -0,0 +1,103 @@
+use crate::middleware::error::AppError;
+use crate::repository::{customer_repo, order_repo};
+use crate::services::verification_service;
+use shared::dto::{CustomerHistoryResponse, CustomerLookupRequest, GetCustomersResponse};
+use shared::models::Order;
+use shared::validation::{normalize_phone_number, PhoneRegion};
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+
+/// Previous orders shown to a customer or an admin
+const HISTORY_ORDER_LIMIT: usize = 20;
+/// Most customers returned by an admin search
+const SEARCH_RESULT_LIMIT: usize = 50;
+
+/// Records an order on the customer's profile, creating it on the first order
+///
+/// The order stands even if the profile cannot be updated, so failures are
+/// only logged.
+pub async fn record_order(db: &Surreal<Any>, customer_id: &str, order: &Order) {
+    if let Err(e) =
+        customer_repo::upsert_customer_for_order(db, customer_id, order.customer.name.trim()).await
+    {
+        tracing::warn!(
+            "Failed to record order {} for customer {}: {}",
+            order.order_number,
+            customer_id,
+            e
+        );
+    }
+}
+
+/// Previous orders of a returning customer
+///
+/// The phone number must be verified and match the one of the given order.
+/// Every mismatch returns the same not found error, so the endpoint cannot be
+/// used to find out which phone numbers have ordered before.
+pub async fn lookup_customer_history(
+    db: &Surreal<Any>,
+    phone_region: &PhoneRegion,
+    request: &CustomerLookupRequest,
+) -> Result<CustomerHistoryResponse, AppError> {
+    let not_found =
+        || AppError::NotFound("No orders found for this phone and order number".to_string());
+
+    let phone = normalize_phone_number(&request.phone, phone_region)
+        .map_err(|e| AppError::ValidationError(vec![e]))?;
+    verification_service::check_token(db, &phone, request.verification_token.as_deref()).await?;
+
+    let order = order_repo::query_order_by_number(db, request.order_number.trim())
+        .await?
+        .ok_or_else(not_found)?;
+
+    // Orders placed before phones were normalized only have the raw number
+    let order_phone = order
+        .customer
+        .normalized_phone
+        .clone()
+        .or_else(|| normalize_phone_number(&order.customer.phone, phone_region).ok());
+    if order_phone.as_deref() != Some(phone.as_str()) {
+        return Err(not_found());
+    }
+
+    let customer = customer_repo::query_customer_by_id(db, &phone)
+        .await?
+        .ok_or_else(not_found)?;
+    let orders =
+        customer_repo::query_orders_for_customer(db, &customer.id, HISTORY_ORDER_LIMIT).await?;
+
+    Ok(CustomerHistoryResponse { customer, orders })
+}
+
+/// Customers matching a name, a complete phone number or part of one
+pub async fn search_customers(
+    db: &Surreal<Any>,
+    phone_region: &PhoneRegion,
+    search: Option<&str>,
+) -> Result<GetCustomersResponse, AppError> {
+    let search = search.map(str::trim).unwrap_or_default();
+    // Phones are stored in E.164, so a formatted number still matches
+    let search = match normalize_phone_number(search, phone_region) {
+        Ok(phone) => phone,
+        Err(_) if !search.chars().any(char::is_alphabetic) => {
+            search.chars().filter(char::is_ascii_digit).collect()
+        }
+        Err(_) => search.to_lowercase(),
+    };
+
+    let customers = customer_repo::search_customers(db, &search, SEARCH_RESULT_LIMIT).await?;
+    Ok(GetCustomersResponse { customers })
+}
+
+pub async fn get_customer_history(
+    db: &Surreal<Any>,
+    id: &str,
+) -> Result<CustomerHistoryResponse, AppError> {
+    let customer = customer_repo::query_customer_by_id(db, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Customer with id {} not found", id)))?;
+    let orders =
+        customer_repo::query_orders_for_customer(db, &customer.id, HISTORY_ORDER_LIMIT).await?;
+
+    Ok(CustomerHistoryResponse { customer, orders })
+}
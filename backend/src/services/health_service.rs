@@ -0,0 +1,330 @@
+use crate::metrics::record_db_up;
+use crate::repository::db::{ConnectionState, ConnectionStatus, Database};
+use crate::repository::{health_repo, seed::SCHEMA_VERSION};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Background workers and how their last runs went, shared with the health check
+#[derive(Clone, Default)]
+pub struct Workers {
+    states: Arc<Mutex<BTreeMap<&'static str, WorkerState>>>,
+}
+
+struct WorkerState {
+    started_at: DateTime<Utc>,
+    last_run_at: Option<DateTime<Utc>>,
+    last_error: Option<(DateTime<Utc>, String)>,
+    /// How long the worker may go without completing a run, for polling workers
+    stale_after: Option<Duration>,
+    is_running: bool,
+}
+
+impl Workers {
+    /// Adds a worker to the health report; it reports through the returned heartbeat
+    pub fn register(&self, name: &'static str, stale_after: Option<Duration>) -> WorkerHeartbeat {
+        self.lock().insert(
+            name,
+            WorkerState {
+                started_at: Utc::now(),
+                last_run_at: None,
+                last_error: None,
+                stale_after,
+                is_running: true,
+            },
+        );
+        WorkerHeartbeat {
+            name,
+            workers: self.clone(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<&'static str, WorkerState>> {
+        // A panic while holding the lock leaves the states usable
+        self.states
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn update(&self, name: &'static str, update: impl FnOnce(&mut WorkerState)) {
+        if let Some(state) = self.lock().get_mut(name) {
+            update(state);
+        }
+    }
+
+    fn report(&self, include_errors: bool) -> Vec<WorkerReport> {
+        let now = Utc::now();
+        self.lock()
+            .iter()
+            .map(|(name, state)| {
+                let last_activity = state.last_run_at.unwrap_or(state.started_at);
+                let is_stale = state.stale_after.is_some_and(|stale_after| {
+                    (now - last_activity).to_std().unwrap_or_default() > stale_after
+                });
+                let is_failing = match (&state.last_error, state.last_run_at) {
+                    (Some((failed_at, _)), Some(run_at)) => *failed_at > run_at,
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                };
+                let status = if !state.is_running {
+                    WorkerStatus::Stopped
+                } else if is_stale {
+                    WorkerStatus::Stalled
+                } else if is_failing {
+                    WorkerStatus::Failing
+                } else {
+                    WorkerStatus::Running
+                };
+
+                WorkerReport {
+                    name,
+                    status,
+                    last_run_at: state.last_run_at,
+                    last_error_at: state.last_error.as_ref().map(|(at, _)| *at),
+                    last_error: state
+                        .last_error
+                        .as_ref()
+                        .filter(|_| include_errors)
+                        .map(|(_, error)| error.clone()),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Handle a background worker reports its runs with; dropping it marks the worker stopped
+pub struct WorkerHeartbeat {
+    name: &'static str,
+    workers: Workers,
+}
+
+impl WorkerHeartbeat {
+    /// Records a completed run
+    pub fn ran(&self) {
+        self.workers
+            .update(self.name, |state| state.last_run_at = Some(Utc::now()));
+    }
+
+    /// Records a run that could not do its work
+    pub fn failed(&self, error: impl Display) {
+        let error = error.to_string();
+        self.workers.update(self.name, |state| {
+            state.last_error = Some((Utc::now(), error))
+        });
+    }
+}
+
+impl Drop for WorkerHeartbeat {
+    fn drop(&mut self) {
+        self.workers
+            .update(self.name, |state| state.is_running = false);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkerStatus {
+    Running,
+    /// Its last run failed
+    Failing,
+    /// No run completed for longer than expected
+    Stalled,
+    /// The worker exited
+    Stopped,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkerReport {
+    pub name: &'static str,
+    pub status: WorkerStatus,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_error_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadinessStatus {
+    /// Everything works
+    Ready,
+    /// Orders can be taken, but a background worker or the schema needs attention
+    Degraded,
+    /// The database is unreachable, reconnecting or has no menu
+    Unavailable,
+}
+
+/// Version of the running binary
+#[derive(Debug, Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_sha: &'static str,
+    pub build_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Liveness {
+    pub status: &'static str,
+    pub uptime_seconds: i64,
+    pub build: BuildInfo,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Readiness {
+    pub status: ReadinessStatus,
+    pub database: DatabaseCheck,
+    pub schema: SchemaCheck,
+    pub seed: SeedCheck,
+    pub workers: Vec<WorkerReport>,
+    pub uptime_seconds: i64,
+    pub build: BuildInfo,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DatabaseCheck {
+    pub is_connected: bool,
+    pub latency_ms: f64,
+    /// Connection state as seen by the supervisor, with its reconnect counts
+    pub connection: ConnectionStatus,
+    /// Only shown to admins, other callers could learn about the setup from it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SchemaCheck {
+    pub expected_version: u32,
+    /// Version recorded when the schema was applied, unknown without a database
+    pub applied_version: Option<u32>,
+    pub is_current: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeedCheck {
+    /// Pizzas on the menu, unknown without a database
+    pub pizza_count: Option<usize>,
+    pub is_seeded: bool,
+}
+
+pub fn build_info() -> BuildInfo {
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        git_sha: env!("GIT_SHA"),
+        build_time: env!("BUILD_TIMESTAMP")
+            .parse::<i64>()
+            .ok()
+            .and_then(|seconds| Utc.timestamp_opt(seconds, 0).single()),
+    }
+}
+
+pub fn check_liveness(uptime_seconds: i64) -> Liveness {
+    Liveness {
+        status: "alive",
+        uptime_seconds,
+        build: build_info(),
+        timestamp: Utc::now(),
+    }
+}
+
+/// Checks the database and background workers
+///
+/// Error messages are only included with `include_errors`, for admins.
+pub async fn check_readiness(
+    db: &Database,
+    workers: &Workers,
+    uptime_seconds: i64,
+    include_errors: bool,
+) -> Readiness {
+    let database = check_database(db, include_errors).await;
+
+    let (applied_version, pizza_count) = if database.is_connected {
+        let db = db.client();
+        let applied_version = health_repo::query_schema_version(&db)
+            .await
+            .inspect_err(|e| tracing::warn!("Failed to read the schema version: {}", e))
+            .ok()
+            .flatten();
+        let pizza_count = health_repo::count_pizzas(&db)
+            .await
+            .inspect_err(|e| tracing::warn!("Failed to count pizzas: {}", e))
+            .ok();
+        (applied_version, pizza_count)
+    } else {
+        (None, None)
+    };
+
+    let schema = SchemaCheck {
+        expected_version: SCHEMA_VERSION,
+        applied_version,
+        is_current: applied_version == Some(SCHEMA_VERSION),
+    };
+    let seed = SeedCheck {
+        pizza_count,
+        is_seeded: pizza_count.is_some_and(|count| count > 0),
+    };
+    let workers = workers.report(include_errors);
+
+    let status = if !database.is_connected || !seed.is_seeded {
+        ReadinessStatus::Unavailable
+    } else if !schema.is_current
+        || workers
+            .iter()
+            .any(|worker| worker.status != WorkerStatus::Running)
+    {
+        ReadinessStatus::Degraded
+    } else {
+        ReadinessStatus::Ready
+    };
+
+    Readiness {
+        status,
+        database,
+        schema,
+        seed,
+        workers,
+        uptime_seconds,
+        build: build_info(),
+        timestamp: Utc::now(),
+    }
+}
+
+/// Times a round trip to the database and updates `db_up`
+///
+/// While the supervisor is reconnecting the connection is reported down
+/// without a round trip, with the error that caused the reconnect.
+pub async fn check_database(db: &Database, include_errors: bool) -> DatabaseCheck {
+    let connection = db.status();
+    if connection.state == ConnectionState::Reconnecting {
+        record_db_up(false);
+        return DatabaseCheck {
+            is_connected: false,
+            latency_ms: 0.0,
+            error: connection.last_error.clone().filter(|_| include_errors),
+            connection,
+        };
+    }
+
+    let started = Instant::now();
+    let result = health_repo::ping(&db.client()).await;
+    let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+    record_db_up(result.is_ok());
+
+    if let Err(e) = &result {
+        tracing::warn!("Database health check failed: {}", e);
+    }
+
+    DatabaseCheck {
+        is_connected: result.is_ok(),
+        latency_ms,
+        connection,
+        error: result
+            .err()
+            .filter(|_| include_errors)
+            .map(|e| e.to_string()),
+    }
+}
@@ -0,0 +1,155 @@
+use crate::images::{self, variants, ImageStore};
+use crate::middleware::error::AppError;
+use crate::repository::pizza_repo;
+use crate::services::audit_service;
+use crate::services::pizza_service::{self, MenuCache};
+use shared::models::{AuditAction, ImageFormat, ImageVariant, Pizza, PizzaImage};
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+use uuid::Uuid;
+
+/// Replaces the photo of a menu pizza with an uploaded JPEG, PNG or WebP image
+///
+/// The upload is stored in every variant width under a new version, so a
+/// cached copy of the old photo is never served for the new one. The old
+/// variants are deleted once the pizza points at the new ones.
+pub async fn upload_pizza_image(
+    db: &Surreal<Any>,
+    images: &dyn ImageStore,
+    menu_cache: &MenuCache,
+    public_url: &str,
+    id: &str,
+    content_type: Option<&str>,
+    bytes: Vec<u8>,
+) -> Result<Pizza, AppError> {
+    let format = content_type
+        .map(|content_type| content_type.split(';').next().unwrap_or_default().trim())
+        .and_then(variants::upload_format)
+        .ok_or_else(|| {
+            AppError::ValidationError(vec![
+                "Images must be uploaded as image/jpeg, image/png or image/webp".to_string(),
+            ])
+        })?;
+    if bytes.is_empty() {
+        return Err(AppError::ValidationError(vec![
+            "The image is empty".to_string()
+        ]));
+    }
+
+    let pizza = pizza_service::get_pizza_by_id(db, id).await?;
+
+    let encoded = tokio::task::spawn_blocking(move || variants::render(&bytes, format))
+        .await
+        .map_err(|e| AppError::InternalError(format!("Image processing failed: {}", e)))??;
+
+    let version = Uuid::new_v4().simple().to_string();
+    let mut stored: Vec<ImageVariant> = Vec::new();
+    for variant in encoded {
+        let key = format!(
+            "pizzas/{}/{}/{}.{}",
+            pizza.id,
+            version,
+            variant.width,
+            variant.format.extension()
+        );
+        if let Err(e) = images.put(&key, variant.bytes).await {
+            delete_variants(images, &stored).await;
+            return Err(e.into());
+        }
+        stored.push(ImageVariant {
+            url: format!("{}/{}", public_url, key),
+            key,
+            format: variant.format,
+            width: variant.width,
+            height: variant.height,
+        });
+    }
+
+    let image = PizzaImage { variants: stored };
+    let image_url = image
+        .largest(ImageFormat::Jpeg)
+        .map(|variant| variant.url.clone());
+    let result =
+        pizza_repo::update_pizza_image(db, &pizza.id, Some(image.clone()), image_url).await;
+    let updated = match result {
+        Ok(Some(updated)) => updated,
+        result => {
+            // Nothing points at the new variants
+            delete_variants(images, &image.variants).await;
+            return Err(match result {
+                Err(e) => e.into(),
+                _ => AppError::NotFound(format!("Pizza with id {} not found", id)),
+            });
+        }
+    };
+
+    menu_cache.invalidate();
+    if let Some(old) = &pizza.image {
+        delete_variants(images, &old.variants).await;
+    }
+    audit_service::record(
+        db,
+        AuditAction::PizzaUpdated,
+        &pizza.id,
+        Some(&pizza),
+        Some(&updated),
+    )
+    .await;
+
+    Ok(updated)
+}
+
+/// Removes the photo of a menu pizza, leaving it with the placeholder
+pub async fn delete_pizza_image(
+    db: &Surreal<Any>,
+    images: &dyn ImageStore,
+    menu_cache: &MenuCache,
+    id: &str,
+) -> Result<Pizza, AppError> {
+    let pizza = pizza_service::get_pizza_by_id(db, id).await?;
+
+    let updated = pizza_repo::update_pizza_image(db, &pizza.id, None, None)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Pizza with id {} not found", id)))?;
+
+    menu_cache.invalidate();
+    if let Some(old) = &pizza.image {
+        delete_variants(images, &old.variants).await;
+    }
+    audit_service::record(
+        db,
+        AuditAction::PizzaUpdated,
+        &pizza.id,
+        Some(&pizza),
+        Some(&updated),
+    )
+    .await;
+
+    Ok(updated)
+}
+
+/// A stored image variant and its format, for serving
+pub async fn get_image(
+    images: &dyn ImageStore,
+    key: &str,
+) -> Result<(ImageFormat, Vec<u8>), AppError> {
+    let not_found = || AppError::NotFound(format!("Image {} not found", key));
+
+    let format = [ImageFormat::Webp, ImageFormat::Jpeg]
+        .into_iter()
+        .find(|format| key.ends_with(&format!(".{}", format.extension())))
+        .filter(|_| images::is_valid_key(key))
+        .ok_or_else(not_found)?;
+
+    let bytes = images.get(key).await?.ok_or_else(not_found)?;
+    Ok((format, bytes))
+}
+
+/// Deletes variants nothing points at anymore; failures only leave files behind
+async fn delete_variants(images: &dyn ImageStore, variants: &[ImageVariant]) {
+    for variant in variants {
+        if let Err(e) = images.delete(&variant.key).await {
+            tracing::warn!("Failed to delete image {}: {}", variant.key, e);
+        }
+    }
+}
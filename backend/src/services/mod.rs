@@ -1,2 +1,16 @@
+pub mod audit_service;
+pub mod backup_service;
+pub mod customer_service;
+pub mod health_service;
+pub mod image_service;
+pub mod notification_service;
 pub mod order_service;
+pub mod outbox;
+pub mod payment_service;
 pub mod pizza_service;
+pub mod pricing_service;
+pub mod receipt_service;
+pub mod refund_service;
+pub mod report_service;
+pub mod verification_service;
+pub mod webhook_service;
@@ -0,0 +1,143 @@
+use crate::notification::{templates, Message, Notifiers, NotifyError};
+use crate::repository::db::Database;
+use crate::repository::notification_repo;
+use crate::services::health_service::WorkerHeartbeat;
+use crate::services::outbox::{self, Outbox, OutboxFuture};
+use chrono::Utc;
+use shared::models::{Notification, NotificationChannel, NotificationStatus, Order};
+use std::time::Duration;
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+use uuid::Uuid;
+
+/// Delivery attempts before a notification is given up
+const MAX_ATTEMPTS: u32 = 6;
+/// Delay before the first retry, doubled on every further attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(3600);
+
+/// Queues the messages announcing an order's current status
+///
+/// One message is queued per channel the customer opted in to. A status
+/// change must not fail because of notifications, so errors are only logged.
+pub async fn notify_status_change(db: &Surreal<Any>, order: &Order) {
+    let mut recipients = Vec::new();
+    if let (true, Some(phone)) = (order.notifications.sms, &order.customer.normalized_phone) {
+        recipients.push((NotificationChannel::Sms, phone.clone()));
+    }
+    if let Some(email) = &order.notifications.email {
+        recipients.push((NotificationChannel::Email, email.clone()));
+    }
+
+    for (channel, recipient) in recipients {
+        let Some((subject, body)) = templates::render(order, channel) else {
+            continue;
+        };
+
+        let now = Utc::now();
+        let notification = Notification {
+            id: Uuid::new_v4().to_string(),
+            order_id: order.id.clone(),
+            channel,
+            recipient,
+            subject,
+            body,
+            status: NotificationStatus::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+            created_at: now,
+            sent_at: None,
+        };
+
+        if let Err(e) = notification_repo::create_notification_in_db(db, &notification).await {
+            tracing::error!(
+                "Failed to queue {} notification for order {}: {}",
+                channel.as_str(),
+                order.order_number,
+                e
+            );
+        }
+    }
+}
+
+/// Delivers queued notifications until the process stops
+pub async fn run_outbox(db: Database, notifiers: Notifiers, heartbeat: WorkerHeartbeat) {
+    outbox::run(db, NotificationOutbox { notifiers }, heartbeat).await;
+}
+
+/// The `notification` table, delivered with the configured transports
+struct NotificationOutbox {
+    notifiers: Notifiers,
+}
+
+impl Outbox for NotificationOutbox {
+    type Entry = Notification;
+
+    const NAME: &'static str = "notification outbox";
+
+    fn claim<'a>(
+        &'a self,
+        db: &'a Surreal<Any>,
+        limit: u32,
+        lease: Duration,
+    ) -> OutboxFuture<'a, Result<Vec<Notification>, surrealdb::Error>> {
+        Box::pin(notification_repo::claim_due_notifications(db, limit, lease))
+    }
+
+    fn deliver<'a>(
+        &'a self,
+        db: &'a Surreal<Any>,
+        notification: &'a Notification,
+    ) -> OutboxFuture<'a, ()> {
+        Box::pin(deliver(db, &self.notifiers, notification))
+    }
+}
+
+/// Makes one delivery attempt and records its outcome
+async fn deliver(db: &Surreal<Any>, notifiers: &Notifiers, notification: &Notification) {
+    let attempts = notification.attempts + 1;
+    let result = match notifiers.for_channel(notification.channel) {
+        Some(notifier) => notifier.send(&Message::from(notification)).await,
+        None => Err(NotifyError::Rejected(format!(
+            "{} notifications are disabled",
+            notification.channel.as_str()
+        ))),
+    };
+
+    let recorded = match result {
+        Ok(()) => notification_repo::mark_notification_sent(db, &notification.id, attempts).await,
+        Err(e) => {
+            let retry_at = match e {
+                NotifyError::Unavailable(_) if attempts < MAX_ATTEMPTS => Some(
+                    Utc::now() + outbox::retry_delay(attempts, RETRY_BASE_DELAY, RETRY_MAX_DELAY),
+                ),
+                _ => None,
+            };
+            tracing::warn!(
+                "{} notification {} for order {} failed (attempt {}): {}",
+                notification.channel.as_str(),
+                notification.id,
+                notification.order_id,
+                attempts,
+                e
+            );
+            notification_repo::record_failed_attempt(
+                db,
+                &notification.id,
+                attempts,
+                &e.to_string(),
+                retry_at,
+            )
+            .await
+        }
+    };
+
+    if let Err(e) = recorded {
+        tracing::error!(
+            "Failed to record delivery of notification {}: {}",
+            notification.id,
+            e
+        );
+    }
+}
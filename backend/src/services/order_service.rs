@@ -1,108 +1,294 @@
+use crate::config::{PhoneConfig, TaxConfig};
+use crate::metrics;
 use crate::middleware::error::AppError;
-use crate::repository::{order_repo, pizza_repo};
+use crate::payment::PaymentProvider;
+use crate::repository::order_repo::{self, OrderFilter, OrderPage};
+use crate::services::{
+    audit_service, customer_service, notification_service, payment_service, pricing_service,
+    refund_service, verification_service, webhook_service,
+};
 use crate::utils::order_number::generate_order_number;
-use chrono::Utc;
-use shared::dto::{CreateOrderRequest, CreateOrderResponse};
-use shared::models::{Order, OrderItem, OrderItemType, OrderStatus};
-use surrealdb::engine::remote::ws::Client;
+use chrono::{DateTime, Utc};
+use shared::dto::{
+    CreateOrderRequest, CreateOrderResponse, GetOrdersResponse, OrderSort, SortDirection,
+};
+use shared::models::{
+    AuditAction, CustomerInfo, NotificationConsent, Order, OrderItem, OrderStatus, PaymentMethod,
+    PaymentStatus, WebhookEvent,
+};
+use shared::validation::{normalize_phone_number, PhoneRegion};
+use surrealdb::engine::any::Any;
 use surrealdb::Surreal;
 use uuid::Uuid;
 
+/// Orders per page when the client does not ask for a size
+const DEFAULT_ORDER_PAGE_SIZE: usize = 50;
+/// Most orders returned in a single page
+const MAX_ORDER_PAGE_SIZE: usize = 200;
+
 pub async fn create_order(
-    db: &Surreal<Client>,
+    db: &Surreal<Any>,
+    tax_config: &TaxConfig,
+    phone_config: &PhoneConfig,
+    payments: &dyn PaymentProvider,
     request: CreateOrderRequest,
 ) -> Result<CreateOrderResponse, AppError> {
+    let phone_region = phone_config.region();
+
     // Validate the request
-    validate_order_request(&request)?;
-
-    // Calculate order items with pricing
-    let mut order_items = Vec::new();
-    let mut total_amount = 0.0;
-
-    for item_req in &request.items {
-        let (item_type, unit_price) = match &item_req.item_type {
-            OrderItemType::StandardPizza { pizza_id, size } => {
-                // Fetch pizza from database
-                let pizza = pizza_repo::query_pizza_by_id(db, pizza_id)
-                    .await?
-                    .ok_or_else(|| {
-                        AppError::ValidationError(vec![format!(
-                            "Pizza with id {} not found",
-                            pizza_id
-                        )])
-                    })?;
-
-                // Get price for the specified size
-                let price = size.get_price(&pizza.price);
-
-                (
-                    OrderItemType::StandardPizza {
-                        pizza_id: pizza_id.clone(),
-                        size: size.clone(),
-                    },
-                    price,
-                )
-            }
-            OrderItemType::CustomPizza { custom } => {
-                let price = custom.get_price();
-                (
-                    OrderItemType::CustomPizza {
-                        custom: custom.clone(),
-                    },
-                    price,
-                )
-            }
-        };
-
-        let subtotal = unit_price * item_req.quantity as f64;
-        total_amount += subtotal;
-
-        order_items.push(OrderItem {
-            id: Uuid::new_v4().to_string(),
-            item_type,
-            quantity: item_req.quantity,
-            unit_price,
-            subtotal,
-        });
+    validate_order_request(&request, phone_region)?;
+
+    // Keep the phone as typed, next to the form used to identify the customer
+    let customer = CustomerInfo {
+        normalized_phone: normalize_phone_number(&request.customer.phone, phone_region).ok(),
+        ..request.customer.clone()
+    };
+
+    // Only customers who proved they own the phone number may order, if configured
+    if phone_config.verification_required {
+        let phone = customer.normalized_phone.as_deref().unwrap_or_default();
+        verification_service::check_token(db, phone, request.verification_token.as_deref()).await?;
     }
 
+    let notifications = NotificationConsent {
+        sms: request.notifications.sms,
+        email: request
+            .notifications
+            .email
+            .as_deref()
+            .map(str::trim)
+            .filter(|email| !email.is_empty())
+            .map(str::to_string),
+    };
+
+    // Price the items through the shared pricing service
+    let quote = pricing_service::build_quote(db, tax_config, &request.items).await?;
+    let total_amount = quote.total;
+    let tax_breakdown = quote.tax_breakdown;
+
+    let order_items = quote
+        .lines
+        .into_iter()
+        .map(|line| OrderItem {
+            id: Uuid::new_v4().to_string(),
+            item_type: line.item_type,
+            product_name: Some(line.product_name),
+            size_label: Some(line.size_label),
+            quantity: line.quantity,
+            unit_price: line.unit_price,
+            subtotal: line.subtotal,
+            tax: Some(line.tax),
+        })
+        .collect();
+
     // Generate order number
     let order_number = generate_order_number(db).await.map_err(|e| {
         AppError::InternalError(format!("Failed to generate order number: {}", e))
     })?;
 
+    // Online orders are only confirmed once the payment succeeds
+    let payment_status = match request.payment_method {
+        PaymentMethod::PayAtPickup => PaymentStatus::Unpaid,
+        PaymentMethod::PayOnline => PaymentStatus::Pending,
+    };
+
     // Create order
     let order = Order {
         id: format!("order:{}", Uuid::new_v4()),
         order_number: order_number.clone(),
-        customer: request.customer.clone(),
+        customer,
         items: order_items,
         total_amount,
+        tax_breakdown: Some(tax_breakdown),
+        payment_method: request.payment_method,
+        payment_status,
+        payment_id: None,
+        refunded_amount: 0.0,
+        notifications,
         status: OrderStatus::Pending,
         pickup_time: request.pickup_time,
         created_at: Utc::now(),
     };
 
-    // Save to database
-    let created_order = order_repo::create_order_in_db(db, &order).await?;
+    // Save to database, linked to the customer's profile
+    let customer_id = order.customer.normalized_phone.clone();
+    let created_order = order_repo::create_order_in_db(db, &order, customer_id.as_deref()).await?;
+    metrics::record_order_created(&created_order);
+    audit_service::record(
+        db,
+        AuditAction::OrderCreated,
+        &created_order.id,
+        None,
+        Some(&created_order),
+    )
+    .await;
+
+    if let Some(customer_id) = &customer_id {
+        customer_service::record_order(db, customer_id, &created_order).await;
+    }
+
+    webhook_service::publish(db, WebhookEvent::OrderCreated, &created_order, None).await;
+
+    let payment_url = match request.payment_method {
+        PaymentMethod::PayAtPickup => None,
+        PaymentMethod::PayOnline => {
+            payment_service::start_payment(db, payments, &created_order).await?
+        }
+    };
 
     Ok(CreateOrderResponse {
         order_id: created_order.id,
         order_number,
         total_amount,
         pickup_time: request.pickup_time,
+        payment_status,
+        payment_url,
     })
 }
 
-pub async fn get_order_by_id(db: &Surreal<Client>, id: &str) -> Result<Order, AppError> {
+pub async fn get_order_by_id(db: &Surreal<Any>, id: &str) -> Result<Order, AppError> {
     let order = order_repo::query_order_by_id(db, id).await?;
 
     order.ok_or_else(|| AppError::NotFound(format!("Order with id {} not found", id)))
 }
 
-fn validate_order_request(request: &CreateOrderRequest) -> Result<(), AppError> {
+/// Lists one page of orders for the admin dashboard
+///
+/// The search matches order number, customer name and phone, ignoring case.
+/// The cursor is the `next_cursor` of the previous page and must be used with
+/// the same sort.
+pub async fn list_orders(
+    db: &Surreal<Any>,
+    mut filter: OrderFilter,
+    sort: OrderSort,
+    direction: SortDirection,
+    cursor: Option<&str>,
+    limit: Option<usize>,
+) -> Result<GetOrdersResponse, AppError> {
+    filter.search = filter
+        .search
+        .map(|search| search.trim().to_lowercase())
+        .filter(|search| !search.is_empty());
+
+    let limit = limit
+        .unwrap_or(DEFAULT_ORDER_PAGE_SIZE)
+        .clamp(1, MAX_ORDER_PAGE_SIZE);
+    let page = OrderPage {
+        sort,
+        direction,
+        after: cursor.map(decode_cursor).transpose()?,
+        // One extra order tells whether there is a next page
+        limit: limit + 1,
+    };
+
+    let mut orders = order_repo::query_orders(db, &filter, &page).await?;
+
+    let next_cursor = if orders.len() > limit {
+        orders.truncate(limit);
+        orders.last().map(|order| encode_cursor(order, sort))
+    } else {
+        None
+    };
+
+    Ok(GetOrdersResponse {
+        orders,
+        next_cursor,
+    })
+}
+
+/// Cursor pointing after an order: its sort value in nanoseconds and its id
+fn encode_cursor(order: &Order, sort: OrderSort) -> String {
+    let value = match sort {
+        OrderSort::CreatedAt => order.created_at,
+        OrderSort::PickupTime => order.pickup_time,
+    };
+    format!(
+        "{}_{}",
+        value.timestamp_nanos_opt().unwrap_or_default(),
+        order.id
+    )
+}
+
+fn decode_cursor(cursor: &str) -> Result<(DateTime<Utc>, String), AppError> {
+    cursor
+        .split_once('_')
+        .and_then(|(nanos, id)| {
+            let nanos = nanos.parse::<i64>().ok()?;
+            (!id.is_empty()).then(|| (DateTime::from_timestamp_nanos(nanos), id.to_string()))
+        })
+        .ok_or_else(|| AppError::ValidationError(vec!["Invalid cursor".to_string()]))
+}
+
+/// Moves an order to another status
+///
+/// Cancelling an order that was paid online refunds whatever has not been
+/// refunded yet before the status changes, so a failed refund leaves the
+/// order untouched.
+pub async fn update_order_status(
+    db: &Surreal<Any>,
+    payments: &dyn PaymentProvider,
+    id: &str,
+    status: OrderStatus,
+) -> Result<Order, AppError> {
+    let order = get_order_by_id(db, id).await?;
+
+    if order.status == status {
+        return Ok(order);
+    }
+    if matches!(order.status, OrderStatus::Cancelled | OrderStatus::PickedUp) {
+        return Err(AppError::ValidationError(vec![format!(
+            "Order {} is {} and can no longer change",
+            order.order_number,
+            order.status.as_str()
+        )]));
+    }
+
+    if status == OrderStatus::Cancelled && order.payment_status.is_refundable() {
+        refund_service::refund_order(db, payments, &order, None, "Order cancelled".to_string())
+            .await?;
+    }
+
+    let updated = order_repo::update_order_status(db, &order.id, status)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Order with id {} not found", id)))?;
+
+    // A refund on cancelling is recorded on its own, so only the status is compared
+    let before = Order {
+        status: order.status,
+        ..updated.clone()
+    };
+    audit_service::record(
+        db,
+        AuditAction::OrderStatusChanged,
+        &updated.id,
+        Some(&before),
+        Some(&updated),
+    )
+    .await;
+
+    announce_status_change(db, &updated, order.status).await;
+
+    Ok(updated)
+}
+
+/// Tells the customer and webhook subscribers that an order changed status
+pub async fn announce_status_change(db: &Surreal<Any>, order: &Order, previous: OrderStatus) {
+    notification_service::notify_status_change(db, order).await;
+
+    webhook_service::publish(db, WebhookEvent::OrderStatusChanged, order, Some(previous)).await;
+    if order.status == OrderStatus::Cancelled {
+        metrics::record_order_cancelled();
+        webhook_service::publish(db, WebhookEvent::OrderCancelled, order, Some(previous)).await;
+    }
+}
+
+fn validate_order_request(
+    request: &CreateOrderRequest,
+    phone_region: &PhoneRegion,
+) -> Result<(), AppError> {
     use shared::validation::{
-        validate_customer_name, validate_order_items, validate_phone_number, validate_pickup_time,
+        validate_customer_name, validate_email, validate_order_items, validate_phone_number,
+        validate_pickup_time,
     };
 
     let mut errors = Vec::new();
@@ -113,7 +299,18 @@ fn validate_order_request(request: &CreateOrderRequest) -> Result<(), AppError>
     }
 
     // Validate phone
-    if let Err(e) = validate_phone_number(&request.customer.phone) {
+    if let Err(e) = validate_phone_number(&request.customer.phone, phone_region) {
+        errors.push(e);
+    }
+
+    // Validate the notification email, if the customer wants one
+    if let Some(Err(e)) = request
+        .notifications
+        .email
+        .as_deref()
+        .filter(|email| !email.trim().is_empty())
+        .map(validate_email)
+    {
         errors.push(e);
     }
 
@@ -0,0 +1,101 @@
+//! Delivery loop shared by the background workers of queue tables
+//!
+//! Customer notifications and webhook deliveries are both written to a table
+//! and delivered later. A worker claims due entries for a lease, so another
+//! worker does not pick them up at the same time, makes one attempt per entry
+//! and records its outcome; failed attempts are retried with exponential
+//! backoff.
+
+use crate::repository::db::Database;
+use crate::services::health_service::WorkerHeartbeat;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+
+/// How often a queue is checked for due entries
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Entries delivered per poll
+const BATCH_SIZE: u32 = 20;
+/// How long a claimed entry is hidden from other workers
+const CLAIM_LEASE: Duration = Duration::from_secs(300);
+/// How long a run may take before the health check reports the worker stalled
+pub const STALE_AFTER: Duration = CLAIM_LEASE;
+
+/// Boxed future returned by queues, so the delivery loop can be generic over them
+pub type OutboxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Table of entries waiting to be delivered
+pub trait Outbox: Send + Sync {
+    type Entry: Send + Sync;
+
+    /// Name of the queue in log messages
+    const NAME: &'static str;
+
+    /// Claims up to `limit` due entries, hiding them from other workers for `lease`
+    fn claim<'a>(
+        &'a self,
+        db: &'a Surreal<Any>,
+        limit: u32,
+        lease: Duration,
+    ) -> OutboxFuture<'a, Result<Vec<Self::Entry>, surrealdb::Error>>;
+
+    /// Makes one delivery attempt and records its outcome
+    fn deliver<'a>(&'a self, db: &'a Surreal<Any>, entry: &'a Self::Entry) -> OutboxFuture<'a, ()>;
+}
+
+/// Delivers due entries of a queue until the process stops
+pub async fn run<O: Outbox>(db: Database, outbox: O, heartbeat: WorkerHeartbeat) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        // Taken per run to pick up a new connection after a reconnect
+        let db = db.client();
+        let due = match outbox.claim(&db, BATCH_SIZE, CLAIM_LEASE).await {
+            Ok(due) => due,
+            Err(e) => {
+                tracing::error!("Failed to read the {}: {}", O::NAME, e);
+                heartbeat.failed(e);
+                continue;
+            }
+        };
+
+        for entry in &due {
+            outbox.deliver(&db, entry).await;
+        }
+        heartbeat.ran();
+    }
+}
+
+/// Exponential backoff after the given number of failed attempts
+///
+/// The first retry waits `base_delay`, every further one twice as long, up to
+/// `max_delay`.
+pub fn retry_delay(attempts: u32, base_delay: Duration, max_delay: Duration) -> chrono::Duration {
+    let delay = base_delay
+        .saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+        .min(max_delay);
+    chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_the_maximum() {
+        let base = Duration::from_secs(30);
+        let max = Duration::from_secs(3600);
+
+        assert_eq!(retry_delay(1, base, max), chrono::Duration::seconds(30));
+        assert_eq!(retry_delay(2, base, max), chrono::Duration::seconds(60));
+        assert_eq!(retry_delay(4, base, max), chrono::Duration::seconds(240));
+        assert_eq!(retry_delay(8, base, max), chrono::Duration::seconds(3600));
+        assert_eq!(
+            retry_delay(u32::MAX, base, max),
+            chrono::Duration::seconds(3600)
+        );
+    }
+}
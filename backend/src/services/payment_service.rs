@@ -0,0 +1,282 @@
+use crate::middleware::actor::act_as;
+use crate::middleware::error::AppError;
+use crate::payment::{
+    PaymentEvent, PaymentEvents, PaymentOutcome, PaymentProvider, PaymentRequest,
+};
+use crate::repository::db::Database;
+use crate::repository::order_repo;
+use crate::services::health_service::WorkerHeartbeat;
+use crate::services::{audit_service, order_service, refund_service};
+use shared::models::{Actor, ActorKind, AuditAction, Order, OrderStatus, PaymentStatus};
+use std::sync::Arc;
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+
+/// Starts the online payment of a freshly created order
+///
+/// Returns the checkout URL, if the provider has one. When the provider
+/// cannot start the payment the order is cancelled, since it would never
+/// be confirmed.
+pub async fn start_payment(
+    db: &Surreal<Any>,
+    payments: &dyn PaymentProvider,
+    order: &Order,
+) -> Result<Option<String>, AppError> {
+    let request = PaymentRequest {
+        order_id: order.id.clone(),
+        order_number: order.order_number.clone(),
+        amount: order.total_amount,
+    };
+
+    match payments.create_payment(request).await {
+        Ok(session) => {
+            order_repo::update_order_payment_id(db, &order.id, &session.payment_id).await?;
+            let started = Order {
+                payment_id: Some(session.payment_id),
+                ..order.clone()
+            };
+            audit_service::record(
+                db,
+                AuditAction::OrderPaymentChanged,
+                &order.id,
+                Some(order),
+                Some(&started),
+            )
+            .await;
+            Ok(session.checkout_url)
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to start payment for order {}: {}",
+                order.order_number,
+                e
+            );
+            let cancelled = order_repo::update_order_payment(
+                db,
+                &order.id,
+                PaymentStatus::Failed,
+                None,
+                OrderStatus::Cancelled,
+            )
+            .await?;
+            if let Some(cancelled) = cancelled {
+                audit_service::record(
+                    db,
+                    AuditAction::OrderPaymentChanged,
+                    &order.id,
+                    Some(order),
+                    Some(&cancelled),
+                )
+                .await;
+                order_service::announce_status_change(db, &cancelled, order.status).await;
+            }
+            Err(e.into())
+        }
+    }
+}
+
+/// Records the outcome of an online payment on its order
+///
+/// A successful payment confirms a pending order, a failed one cancels it.
+/// A payment that succeeds after the order was cancelled is refunded right
+/// away. Events for orders that are already paid are ignored, so providers
+/// may deliver the same event more than once.
+pub async fn apply_payment_event(
+    db: &Surreal<Any>,
+    payments: &dyn PaymentProvider,
+    event: PaymentEvent,
+) -> Result<Order, AppError> {
+    let order = order_service::get_order_by_id(db, &event.order_id).await?;
+
+    let Some((payment_status, status)) =
+        payment_transition(event.outcome, order.payment_status, order.status)
+    else {
+        return Ok(order);
+    };
+
+    tracing::info!(
+        "Payment {} for order {}: {}",
+        event.payment_id,
+        order.order_number,
+        payment_status.as_str()
+    );
+
+    let updated = order_repo::update_order_payment(
+        db,
+        &order.id,
+        payment_status,
+        Some(event.payment_id),
+        status,
+    )
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Order with id {} not found", order.id)))?;
+
+    audit_service::record(
+        db,
+        AuditAction::OrderPaymentChanged,
+        &order.id,
+        Some(&order),
+        Some(&updated),
+    )
+    .await;
+
+    if updated.status != order.status {
+        order_service::announce_status_change(db, &updated, order.status).await;
+    }
+
+    // The money arrived for an order nobody will make; give it back the same
+    // way cancelling a paid order does
+    if updated.status == OrderStatus::Cancelled && updated.payment_status.is_refundable() {
+        tracing::warn!(
+            "Payment {} arrived after order {} was cancelled, refunding",
+            updated.payment_id.as_deref().unwrap_or_default(),
+            updated.order_number
+        );
+        refund_service::refund_order(
+            db,
+            payments,
+            &updated,
+            None,
+            "Order cancelled before payment".to_string(),
+        )
+        .await?;
+        return order_service::get_order_by_id(db, &updated.id).await;
+    }
+
+    Ok(updated)
+}
+
+/// Payment and order status after a payment outcome
+///
+/// Returns `None` for orders that are already paid, so a repeated event
+/// changes nothing.
+fn payment_transition(
+    outcome: PaymentOutcome,
+    payment_status: PaymentStatus,
+    status: OrderStatus,
+) -> Option<(PaymentStatus, OrderStatus)> {
+    if matches!(
+        payment_status,
+        PaymentStatus::Paid | PaymentStatus::PartiallyRefunded | PaymentStatus::Refunded
+    ) {
+        return None;
+    }
+
+    Some(match (outcome, status) {
+        (PaymentOutcome::Succeeded, OrderStatus::Pending) => {
+            (PaymentStatus::Paid, OrderStatus::Confirmed)
+        }
+        (PaymentOutcome::Succeeded, status) => (PaymentStatus::Paid, status),
+        (PaymentOutcome::Failed, OrderStatus::Pending) => {
+            (PaymentStatus::Failed, OrderStatus::Cancelled)
+        }
+        (PaymentOutcome::Failed, status) => (PaymentStatus::Failed, status),
+    })
+}
+
+/// Applies events the provider delivers in-process until the channel closes
+///
+/// Changes are recorded in the audit log as made by the payment provider.
+pub async fn process_payment_events(
+    db: Database,
+    payments: Arc<dyn PaymentProvider>,
+    mut events: PaymentEvents,
+    heartbeat: WorkerHeartbeat,
+) {
+    while let Some(event) = events.recv().await {
+        let payment_id = event.payment_id.clone();
+        let applied = act_as(
+            Actor::new(ActorKind::PaymentProvider),
+            apply_payment_event(&db.client(), payments.as_ref(), event),
+        )
+        .await;
+        match applied {
+            Ok(_) => heartbeat.ran(),
+            Err(e) => {
+                tracing::error!("Failed to apply payment event {}: {:?}", payment_id, e);
+                heartbeat.failed(format!("Payment event {}: {:?}", payment_id, e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_success_confirms_pending_order() {
+        assert_eq!(
+            payment_transition(
+                PaymentOutcome::Succeeded,
+                PaymentStatus::Pending,
+                OrderStatus::Pending
+            ),
+            Some((PaymentStatus::Paid, OrderStatus::Confirmed))
+        );
+    }
+
+    #[test]
+    fn test_failure_cancels_pending_order() {
+        assert_eq!(
+            payment_transition(
+                PaymentOutcome::Failed,
+                PaymentStatus::Pending,
+                OrderStatus::Pending
+            ),
+            Some((PaymentStatus::Failed, OrderStatus::Cancelled))
+        );
+    }
+
+    #[test]
+    fn test_failure_keeps_order_status_after_pending() {
+        assert_eq!(
+            payment_transition(
+                PaymentOutcome::Failed,
+                PaymentStatus::Pending,
+                OrderStatus::Cancelled
+            ),
+            Some((PaymentStatus::Failed, OrderStatus::Cancelled))
+        );
+    }
+
+    #[test]
+    fn test_success_for_cancelled_order_marks_it_paid_for_refund() {
+        assert_eq!(
+            payment_transition(
+                PaymentOutcome::Succeeded,
+                PaymentStatus::Pending,
+                OrderStatus::Cancelled
+            ),
+            Some((PaymentStatus::Paid, OrderStatus::Cancelled))
+        );
+    }
+
+    #[test]
+    fn test_success_after_failure_marks_order_paid() {
+        assert_eq!(
+            payment_transition(
+                PaymentOutcome::Succeeded,
+                PaymentStatus::Failed,
+                OrderStatus::Confirmed
+            ),
+            Some((PaymentStatus::Paid, OrderStatus::Confirmed))
+        );
+    }
+
+    #[test]
+    fn test_events_for_paid_orders_are_ignored() {
+        for payment_status in [
+            PaymentStatus::Paid,
+            PaymentStatus::PartiallyRefunded,
+            PaymentStatus::Refunded,
+        ] {
+            for outcome in [PaymentOutcome::Succeeded, PaymentOutcome::Failed] {
+                assert_eq!(
+                    payment_transition(outcome, payment_status, OrderStatus::Confirmed),
+                    None
+                );
+            }
+        }
+    }
+}
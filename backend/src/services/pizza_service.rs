@@ -1,19 +1,170 @@
 use crate::middleware::error::AppError;
 use crate::repository::pizza_repo;
-use shared::dto::GetPizzasResponse;
-use shared::models::Pizza;
-use surrealdb::engine::remote::ws::Client;
+use crate::services::audit_service;
+use crate::utils::http_cache::Tagged;
+use shared::dto::{GetPizzasResponse, GetPriceHistoryResponse, UpdatePizzaRequest};
+use shared::models::{AuditAction, CustomPizza, Pizza};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use surrealdb::engine::any::Any;
 use surrealdb::Surreal;
 
+/// How long the menu is served from memory before it is read again
+///
+/// Changes made through this backend clear it at once; the expiry picks up
+/// changes made around it, like a restore from the command line.
+const MENU_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// The menu of available pizzas, shared by all requests until it changes
+///
+/// Clones share the same cache.
+#[derive(Clone, Default)]
+pub struct MenuCache {
+    state: Arc<Mutex<CachedMenu>>,
+}
+
+#[derive(Default)]
+struct CachedMenu {
+    /// Bumped by every invalidation, so a read that raced with a change is not kept
+    generation: u64,
+    menu: Option<(Instant, Tagged<GetPizzasResponse>)>,
+}
+
+impl MenuCache {
+    /// Drops the cached menu; call after any change to the `pizza` table
+    pub fn invalidate(&self) {
+        let mut cache = self.lock();
+        cache.generation += 1;
+        cache.menu = None;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CachedMenu> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The menu customers order from, with its ETag, from memory while it is fresh
 pub async fn get_all_available_pizzas(
-    db: &Surreal<Client>,
-) -> Result<GetPizzasResponse, AppError> {
+    db: &Surreal<Any>,
+    menu_cache: &MenuCache,
+) -> Result<Tagged<GetPizzasResponse>, AppError> {
+    let generation = {
+        let cache = menu_cache.lock();
+        let fresh = cache
+            .menu
+            .as_ref()
+            .filter(|(loaded_at, _)| loaded_at.elapsed() < MENU_CACHE_TTL);
+        if let Some((_, menu)) = fresh {
+            return Ok(menu.clone());
+        }
+        cache.generation
+    };
+
     let pizzas = pizza_repo::query_all_available_pizzas(db).await?;
-    Ok(GetPizzasResponse { pizzas })
+    let menu = Tagged::new(GetPizzasResponse {
+        pizzas,
+        custom_pizza_price: CustomPizza::price_table(),
+    })?;
+
+    let mut cache = menu_cache.lock();
+    if cache.generation == generation {
+        cache.menu = Some((Instant::now(), menu.clone()));
+    }
+    Ok(menu)
+}
+
+/// Whole menu including unavailable pizzas
+pub async fn get_all_pizzas(db: &Surreal<Any>) -> Result<GetPizzasResponse, AppError> {
+    let pizzas = pizza_repo::query_all_pizzas(db).await?;
+    Ok(GetPizzasResponse {
+        pizzas,
+        custom_pizza_price: CustomPizza::price_table(),
+    })
 }
 
-pub async fn get_pizza_by_id(db: &Surreal<Client>, id: &str) -> Result<Pizza, AppError> {
+pub async fn get_pizza_by_id(db: &Surreal<Any>, id: &str) -> Result<Pizza, AppError> {
     let pizza = pizza_repo::query_pizza_by_id(db, id).await?;
 
     pizza.ok_or_else(|| AppError::NotFound(format!("Pizza with id {} not found", id)))
 }
+
+/// Edits a menu pizza, keeping the fields the request leaves out
+pub async fn update_pizza(
+    db: &Surreal<Any>,
+    menu_cache: &MenuCache,
+    id: &str,
+    request: UpdatePizzaRequest,
+) -> Result<Pizza, AppError> {
+    use shared::validation::{validate_pizza_name, validate_pizza_price};
+
+    let pizza = get_pizza_by_id(db, id).await?;
+
+    let name = request
+        .name
+        .map(|name| name.trim().to_string())
+        .unwrap_or_else(|| pizza.name.clone());
+    let description = request
+        .description
+        .unwrap_or_else(|| pizza.description.clone());
+    let price = request.price.unwrap_or_else(|| pizza.price.clone());
+    let is_available = request.is_available.unwrap_or(pizza.is_available);
+
+    let errors: Vec<String> = [validate_pizza_name(&name), validate_pizza_price(&price)]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect();
+    if !errors.is_empty() {
+        return Err(AppError::ValidationError(errors));
+    }
+
+    let updated = pizza_repo::update_pizza(db, &pizza.id, name, description, price, is_available)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Pizza with id {} not found", id)))?;
+    menu_cache.invalidate();
+
+    audit_service::record(
+        db,
+        AuditAction::PizzaUpdated,
+        &pizza.id,
+        Some(&pizza),
+        Some(&updated),
+    )
+    .await;
+
+    Ok(updated)
+}
+
+/// Name and price changes of a pizza, oldest first
+pub async fn get_price_history(
+    db: &Surreal<Any>,
+    id: &str,
+) -> Result<GetPriceHistoryResponse, AppError> {
+    let pizza = get_pizza_by_id(db, id).await?;
+    let entries = pizza_repo::query_price_history(db, &pizza.id).await?;
+    Ok(GetPriceHistoryResponse {
+        pizza_id: pizza.id,
+        entries,
+    })
+}
+
+/// Looks up display names of pizzas by id, including unavailable ones
+///
+/// Ids of pizzas that no longer exist are left out.
+pub async fn get_pizza_names(
+    db: &Surreal<Any>,
+    ids: &[&str],
+) -> Result<HashMap<String, String>, AppError> {
+    let mut names = HashMap::new();
+    for &id in ids {
+        if names.contains_key(id) {
+            continue;
+        }
+        if let Some(pizza) = pizza_repo::query_pizza_by_id(db, id).await? {
+            names.insert(id.to_string(), pizza.name);
+        }
+    }
+    Ok(names)
+}
@@ -0,0 +1,148 @@
+use crate::config::TaxConfig;
+use crate::middleware::error::AppError;
+use crate::repository::pizza_repo;
+use crate::utils::money::round_currency;
+use shared::dto::{OrderItemRequest, QuoteLine, QuoteResponse};
+use shared::models::{LineTax, OrderItemType, TaxBreakdown, TaxCategory, TaxRateTotal};
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+
+/// Prices a list of items against the current menu.
+///
+/// This is the single source of truth for pricing: both the quote endpoint
+/// and order creation go through it, so the amounts a customer sees in the
+/// cart are the amounts that get charged.
+pub async fn build_quote(
+    db: &Surreal<Any>,
+    tax_config: &TaxConfig,
+    items: &[OrderItemRequest],
+) -> Result<QuoteResponse, AppError> {
+    use shared::validation::validate_order_items;
+
+    validate_order_items(items).map_err(|e| AppError::ValidationError(vec![e]))?;
+
+    let mut lines = Vec::with_capacity(items.len());
+
+    for item_req in items {
+        let (product_name, size, unit_price, category) = match &item_req.item_type {
+            OrderItemType::StandardPizza { pizza_id, size } => {
+                // Fetch pizza from database
+                let pizza = pizza_repo::query_pizza_by_id(db, pizza_id)
+                    .await?
+                    .ok_or_else(|| {
+                        AppError::ValidationError(vec![format!(
+                            "Pizza with id {} not found",
+                            pizza_id
+                        )])
+                    })?;
+
+                if !pizza.is_available {
+                    return Err(AppError::ValidationError(vec![format!(
+                        "{} is currently unavailable",
+                        pizza.name
+                    )]));
+                }
+
+                (
+                    pizza.name,
+                    *size,
+                    size.get_price(&pizza.price),
+                    pizza.tax_category,
+                )
+            }
+            OrderItemType::CustomPizza { custom } => (
+                "Custom Pizza".to_string(),
+                custom.size,
+                custom.get_price(),
+                TaxCategory::TakeawayFood,
+            ),
+        };
+        let size_label = size.as_str().to_string();
+
+        let subtotal = round_currency(unit_price * item_req.quantity as f64);
+        // No promotions are configured yet
+        let discount_amount = 0.0;
+        let tax = calculate_line_tax(tax_config, category, subtotal - discount_amount);
+
+        lines.push(QuoteLine {
+            item_type: item_req.item_type.clone(),
+            name: format!("{} - {}", product_name, size_label),
+            product_name,
+            size_label,
+            quantity: item_req.quantity,
+            unit_price,
+            subtotal,
+            discount_amount,
+            total: tax.gross,
+            tax,
+        });
+    }
+
+    let tax_breakdown = summarize_taxes(tax_config, &lines);
+
+    Ok(QuoteResponse {
+        subtotal: round_currency(lines.iter().map(|line| line.subtotal).sum()),
+        discount_total: round_currency(lines.iter().map(|line| line.discount_amount).sum()),
+        tax_total: tax_breakdown.tax_total,
+        total: tax_breakdown.gross_total,
+        tax_breakdown,
+        lines,
+    })
+}
+
+/// Splits a line amount into net, tax and gross parts
+///
+/// Depending on configuration the amount is either the gross price (tax is
+/// extracted from it) or the net price (tax is added on top).
+fn calculate_line_tax(tax_config: &TaxConfig, category: TaxCategory, amount: f64) -> LineTax {
+    let rate = tax_config.rate_for(category);
+
+    let (net, tax) = if tax_config.prices_include_tax {
+        let net = round_currency(amount / (1.0 + rate));
+        (net, round_currency(amount - net))
+    } else {
+        (round_currency(amount), round_currency(amount * rate))
+    };
+
+    LineTax {
+        category,
+        rate,
+        net,
+        tax,
+        gross: round_currency(net + tax),
+    }
+}
+
+/// Aggregates line taxes per category and rate into an order breakdown
+fn summarize_taxes(tax_config: &TaxConfig, lines: &[QuoteLine]) -> TaxBreakdown {
+    let mut rates: Vec<TaxRateTotal> = Vec::new();
+
+    for line in lines {
+        let tax = &line.tax;
+        match rates
+            .iter_mut()
+            .find(|total| total.category == tax.category && total.rate == tax.rate)
+        {
+            Some(total) => {
+                total.net = round_currency(total.net + tax.net);
+                total.tax = round_currency(total.tax + tax.tax);
+                total.gross = round_currency(total.gross + tax.gross);
+            }
+            None => rates.push(TaxRateTotal {
+                category: tax.category,
+                rate: tax.rate,
+                net: tax.net,
+                tax: tax.tax,
+                gross: tax.gross,
+            }),
+        }
+    }
+
+    TaxBreakdown {
+        prices_include_tax: tax_config.prices_include_tax,
+        net_total: round_currency(rates.iter().map(|total| total.net).sum()),
+        tax_total: round_currency(rates.iter().map(|total| total.tax).sum()),
+        gross_total: round_currency(rates.iter().map(|total| total.gross).sum()),
+        rates,
+    }
+}
@@ -0,0 +1,40 @@
+use crate::middleware::error::AppError;
+use crate::receipt::{self, ReceiptCopy, ReceiptFormat};
+use crate::services::{order_service, pizza_service};
+use chrono_tz::Tz;
+use shared::models::OrderItemType;
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+
+/// Renders a printable copy of a stored order
+pub async fn render_receipt(
+    db: &Surreal<Any>,
+    order_id: &str,
+    copy: ReceiptCopy,
+    format: ReceiptFormat,
+    timezone: Tz,
+) -> Result<(String, Vec<u8>), AppError> {
+    let order = order_service::get_order_by_id(db, order_id).await?;
+
+    // Older orders only store pizza ids, look up the names to print
+    let pizza_ids: Vec<&str> = order
+        .items
+        .iter()
+        .filter(|item| item.product_name.is_none())
+        .filter_map(|item| match &item.item_type {
+            OrderItemType::StandardPizza { pizza_id, .. } => Some(pizza_id.as_str()),
+            OrderItemType::CustomPizza { .. } => None,
+        })
+        .collect();
+    let pizza_names = pizza_service::get_pizza_names(db, &pizza_ids).await?;
+
+    let body = receipt::render_order(&order, &pizza_names, copy, format, timezone);
+    let file_name = format!(
+        "{}-{}.{}",
+        order.order_number,
+        copy.as_str(),
+        format.extension()
+    );
+
+    Ok((file_name, body))
+}
@@ -0,0 +1,219 @@
+use crate::middleware::error::AppError;
+use crate::payment::{PaymentProvider, RefundRequest};
+use crate::repository::refund_repo;
+use crate::services::{audit_service, order_service};
+use crate::utils::money::round_currency;
+use chrono::Utc;
+use shared::dto::{GetRefundsResponse, RefundLineRequest};
+use shared::models::{AuditAction, Order, OrderItem, PaymentStatus, Refund, RefundLine};
+use std::collections::HashMap;
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+use uuid::Uuid;
+
+pub async fn get_refunds_for_order(
+    db: &Surreal<Any>,
+    order_id: &str,
+) -> Result<GetRefundsResponse, AppError> {
+    let order = order_service::get_order_by_id(db, order_id).await?;
+    let refunds = refund_repo::query_refunds_for_order(db, &order.id).await?;
+
+    Ok(GetRefundsResponse {
+        refunds,
+        refunded_amount: order.refunded_amount,
+    })
+}
+
+/// Refunds selected lines of a paid order, or everything not refunded yet
+///
+/// The refund and the order's `refunded_amount` are written together, and only
+/// while the stored amount is still the one of `order`; otherwise the refund is
+/// rejected as a conflict. A refund the payment provider declines is removed
+/// again, so refunds on record always match the provider.
+pub async fn refund_order(
+    db: &Surreal<Any>,
+    payments: &dyn PaymentProvider,
+    order: &Order,
+    lines: Option<&[RefundLineRequest]>,
+    reason: String,
+) -> Result<Refund, AppError> {
+    let payment_id = match (&order.payment_id, order.payment_status.is_refundable()) {
+        (Some(payment_id), true) => payment_id.clone(),
+        _ => {
+            return Err(AppError::ValidationError(vec![format!(
+                "Order {} has no online payment to refund",
+                order.order_number
+            )]))
+        }
+    };
+
+    let previous = refund_repo::query_refunds_for_order(db, &order.id).await?;
+    let mut refunded_quantities: HashMap<&str, u32> = HashMap::new();
+    for line in previous.iter().flat_map(|refund| &refund.lines) {
+        *refunded_quantities
+            .entry(line.order_item_id.as_str())
+            .or_default() += line.quantity;
+    }
+
+    let requested: Vec<(&OrderItem, u32)> = match lines {
+        Some(lines) => {
+            let mut requested = Vec::with_capacity(lines.len());
+            let mut errors = Vec::new();
+            for line in lines {
+                match order
+                    .items
+                    .iter()
+                    .find(|item| item.id == line.order_item_id)
+                {
+                    Some(item) => requested.push((item, line.quantity)),
+                    None => errors.push(format!("Order item {} not found", line.order_item_id)),
+                }
+            }
+            if !errors.is_empty() {
+                return Err(AppError::ValidationError(errors));
+            }
+            requested
+        }
+        None => order
+            .items
+            .iter()
+            .map(|item| {
+                let refunded = refunded_quantities
+                    .get(item.id.as_str())
+                    .copied()
+                    .unwrap_or(0);
+                (item, item.quantity.saturating_sub(refunded))
+            })
+            .filter(|(_, quantity)| *quantity > 0)
+            .collect(),
+    };
+
+    let mut refund_lines = Vec::with_capacity(requested.len());
+    let mut errors = Vec::new();
+    for (item, quantity) in requested {
+        let refunded = refunded_quantities.entry(item.id.as_str()).or_default();
+        let remaining = item.quantity - *refunded;
+        if quantity == 0 || quantity > remaining {
+            errors.push(format!(
+                "Cannot refund {} of item {}, {} remaining",
+                quantity, item.id, remaining
+            ));
+            continue;
+        }
+
+        refund_lines.push(RefundLine {
+            order_item_id: item.id.clone(),
+            quantity,
+            amount: line_refund_amount(item, *refunded, quantity),
+        });
+        *refunded += quantity;
+    }
+
+    if !errors.is_empty() {
+        return Err(AppError::ValidationError(errors));
+    }
+    if refund_lines.is_empty() {
+        return Err(AppError::ValidationError(vec![format!(
+            "Order {} is already fully refunded",
+            order.order_number
+        )]));
+    }
+
+    let amount = round_currency(refund_lines.iter().map(|line| line.amount).sum());
+    let fully_refunded = order.items.iter().all(|item| {
+        refunded_quantities
+            .get(item.id.as_str())
+            .copied()
+            .unwrap_or(0)
+            >= item.quantity
+    });
+    let payment_status = if fully_refunded {
+        PaymentStatus::Refunded
+    } else {
+        PaymentStatus::PartiallyRefunded
+    };
+
+    // Recorded before asking the provider, so a concurrent refund of the same
+    // lines sees it or fails the check on the stored amount
+    let pending = Refund {
+        id: Uuid::new_v4().to_string(),
+        order_id: order.id.clone(),
+        lines: refund_lines,
+        amount,
+        reason,
+        provider_refund_id: String::new(),
+        created_at: Utc::now(),
+    };
+    let refunded = refund_repo::create_refund_for_order(
+        db,
+        &pending,
+        order.refunded_amount,
+        round_currency(order.refunded_amount + amount),
+        payment_status,
+    )
+    .await?
+    .ok_or_else(|| {
+        AppError::Conflict(format!(
+            "Order {} was refunded in the meantime, please try again",
+            order.order_number
+        ))
+    })?;
+
+    let provider_refund_id = match payments
+        .refund(RefundRequest {
+            payment_id,
+            order_id: order.id.clone(),
+            amount,
+        })
+        .await
+    {
+        Ok(provider_refund_id) => provider_refund_id,
+        Err(e) => {
+            refund_repo::delete_refund_for_order(db, &pending).await?;
+            return Err(e.into());
+        }
+    };
+
+    let refund = refund_repo::update_refund_provider_id(db, &pending.id, &provider_refund_id)
+        .await?
+        .unwrap_or(Refund {
+            provider_refund_id,
+            ..pending
+        });
+    audit_service::record(
+        db,
+        AuditAction::RefundCreated,
+        &refund.id,
+        None,
+        Some(&refund),
+    )
+    .await;
+    audit_service::record(
+        db,
+        AuditAction::OrderRefunded,
+        &order.id,
+        Some(order),
+        Some(&refunded),
+    )
+    .await;
+
+    tracing::info!(
+        "Refunded {:.2} for order {} ({})",
+        amount,
+        order.order_number,
+        payment_status.as_str()
+    );
+
+    Ok(refund)
+}
+
+/// Share of a line's charged amount for `quantity` more units
+///
+/// Computed from cumulative quantities so that refunding every unit returns
+/// exactly what was charged for the line, without rounding drift.
+fn line_refund_amount(item: &OrderItem, already_refunded: u32, quantity: u32) -> f64 {
+    let charged = item.tax.as_ref().map_or(item.subtotal, |tax| tax.gross);
+    let share = |units: u32| round_currency(charged * units as f64 / item.quantity as f64);
+
+    round_currency(share(already_refunded + quantity) - share(already_refunded))
+}
@@ -0,0 +1,237 @@
+use crate::middleware::error::AppError;
+use crate::repository::report_repo::{self, ReportFilter};
+use crate::services::pizza_service;
+use crate::utils::money::round_currency;
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use shared::dto::{
+    ItemMix, ItemSales, ItemSalesReport, ReportGrouping, SalesPeriod, SalesReport, SalesSummary,
+    StatusCount,
+};
+use shared::models::OrderStatus;
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+
+/// Days covered when a report has no start date
+const DEFAULT_RANGE_DAYS: i64 = 30;
+
+/// Date range and statuses a report is restricted to
+///
+/// Dates are inclusive and interpreted in UTC. Without a status filter, or
+/// with an empty one, every status except `Cancelled` is included, so revenue
+/// figures only count orders that were actually sold.
+#[derive(Debug, Clone)]
+pub struct ReportRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub statuses: Vec<OrderStatus>,
+}
+
+impl ReportRange {
+    pub fn new(
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+        statuses: Option<Vec<OrderStatus>>,
+    ) -> Result<Self, AppError> {
+        let to = to.unwrap_or_else(|| Utc::now().date_naive());
+        let from = from.unwrap_or(to - Duration::days(DEFAULT_RANGE_DAYS - 1));
+
+        if from > to {
+            return Err(AppError::ValidationError(vec![
+                "Report start date must not be after the end date".to_string(),
+            ]));
+        }
+
+        let statuses = statuses
+            .filter(|statuses| !statuses.is_empty())
+            .unwrap_or_else(|| {
+                // Pending orders may still fail payment, so only confirmed ones count
+                vec![
+                    OrderStatus::Confirmed,
+                    OrderStatus::Preparing,
+                    OrderStatus::Ready,
+                    OrderStatus::PickedUp,
+                ]
+            });
+
+        Ok(Self { from, to, statuses })
+    }
+
+    fn filter(&self) -> ReportFilter {
+        ReportFilter {
+            from: self.from.and_time(NaiveTime::MIN).and_utc(),
+            to: (self.to + Duration::days(1)).and_time(NaiveTime::MIN).and_utc(),
+            statuses: self.statuses.clone(),
+        }
+    }
+}
+
+/// Revenue and order counts per day, ISO week or hour of the day
+///
+/// Refunds are attributed to the period the refunded order was placed in.
+pub async fn get_sales_report(
+    db: &Surreal<Any>,
+    range: &ReportRange,
+    group_by: ReportGrouping,
+) -> Result<SalesReport, AppError> {
+    let period_format = match group_by {
+        ReportGrouping::Day => "%Y-%m-%d",
+        ReportGrouping::Week => "%G-W%V",
+        ReportGrouping::Hour => "%H:00",
+    };
+
+    let periods = report_repo::query_sales_by_period(db, &range.filter(), period_format)
+        .await?
+        .into_iter()
+        .map(|totals| {
+            let net_revenue = round_currency(totals.gross_revenue - totals.refunded_amount);
+            SalesPeriod {
+                average_ticket: average(totals.gross_revenue, totals.order_count),
+                period: totals.period,
+                order_count: totals.order_count,
+                gross_revenue: round_currency(totals.gross_revenue),
+                refunded_amount: round_currency(totals.refunded_amount),
+                net_revenue,
+            }
+        })
+        .collect();
+
+    Ok(SalesReport {
+        from: range.from,
+        to: range.to,
+        group_by,
+        periods,
+    })
+}
+
+/// Revenue per pizza and size, plus the standard vs custom mix
+pub async fn get_item_sales_report(
+    db: &Surreal<Any>,
+    range: &ReportRange,
+) -> Result<ItemSalesReport, AppError> {
+    let (items, kinds) = report_repo::query_item_sales(db, &range.filter()).await?;
+
+    let unnamed: Vec<&str> = items
+        .iter()
+        .filter(|item| item.product_name.is_none())
+        .filter_map(|item| item.pizza_id.as_deref())
+        .collect();
+    let pizza_names = pizza_service::get_pizza_names(db, &unnamed).await?;
+
+    let items = items
+        .into_iter()
+        .map(|item| {
+            // Older orders carry no name, they fall back to the current menu
+            let name = match (item.product_name, &item.pizza_id) {
+                (Some(product_name), _) => product_name,
+                (None, Some(pizza_id)) => pizza_names.get(pizza_id).unwrap_or(pizza_id).clone(),
+                (None, None) => "Custom Pizza".to_string(),
+            };
+            ItemSales {
+                pizza_id: item.pizza_id,
+                name,
+                size: item.size,
+                quantity: item.quantity,
+                revenue: round_currency(item.revenue),
+            }
+        })
+        .collect();
+
+    let total_quantity: u64 = kinds.iter().map(|kind| kind.quantity).sum();
+    let mix = kinds
+        .into_iter()
+        .map(|kind| ItemMix {
+            quantity_share: ratio(kind.quantity, total_quantity),
+            kind: kind.kind,
+            quantity: kind.quantity,
+            revenue: round_currency(kind.revenue),
+        })
+        .collect();
+
+    Ok(ItemSalesReport {
+        from: range.from,
+        to: range.to,
+        items,
+        mix,
+    })
+}
+
+/// Headline figures: revenue, refunds, average ticket and cancellation rate
+pub async fn get_sales_summary(
+    db: &Surreal<Any>,
+    range: &ReportRange,
+) -> Result<SalesSummary, AppError> {
+    let filter = range.filter();
+    let totals = report_repo::query_range_totals(db, &filter).await?;
+    let status_counts: Vec<StatusCount> = report_repo::query_status_counts(db, &filter)
+        .await?
+        .into_iter()
+        .map(|totals| StatusCount {
+            status: totals.status,
+            count: totals.count,
+        })
+        .collect();
+
+    let all_orders: u64 = status_counts.iter().map(|count| count.count).sum();
+    let cancelled_orders = status_counts
+        .iter()
+        .filter(|count| count.status == OrderStatus::Cancelled)
+        .map(|count| count.count)
+        .sum();
+
+    Ok(SalesSummary {
+        from: range.from,
+        to: range.to,
+        order_count: totals.order_count,
+        gross_revenue: round_currency(totals.gross_revenue),
+        refunded_amount: round_currency(totals.refunded_amount),
+        net_revenue: round_currency(totals.gross_revenue - totals.refunded_amount),
+        average_ticket: average(totals.gross_revenue, totals.order_count),
+        cancellation_rate: ratio(cancelled_orders, all_orders),
+        status_counts,
+    })
+}
+
+fn average(amount: f64, count: u64) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        round_currency(amount / count as f64)
+    }
+}
+
+/// Fraction of a total, rounded to four decimals
+fn ratio(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (part as f64 / total as f64 * 10000.0).round() / 10000.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_statuses_exclude_unconfirmed_and_cancelled_orders() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 14);
+
+        for statuses in [None, Some(Vec::new())] {
+            let range = ReportRange::new(day, day, statuses).unwrap();
+
+            assert!(!range.statuses.contains(&OrderStatus::Pending));
+            assert!(!range.statuses.contains(&OrderStatus::Cancelled));
+            assert!(range.statuses.contains(&OrderStatus::Confirmed));
+            assert!(range.statuses.contains(&OrderStatus::PickedUp));
+        }
+    }
+
+    #[test]
+    fn test_explicit_statuses_are_kept() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 14);
+
+        let range = ReportRange::new(day, day, Some(vec![OrderStatus::Pending])).unwrap();
+
+        assert_eq!(range.statuses, vec![OrderStatus::Pending]);
+    }
+}
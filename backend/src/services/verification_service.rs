@@ -0,0 +1,272 @@
+use crate::middleware::error::AppError;
+use crate::notification::{Message, Notifier};
+use crate::repository::verification_repo;
+use chrono::{Duration, Utc};
+use sha2::{Digest, Sha256};
+use shared::dto::{
+    ConfirmVerificationCodeRequest, ConfirmVerificationCodeResponse, SendVerificationCodeRequest,
+    SendVerificationCodeResponse,
+};
+use shared::validation::{normalize_phone_number, PhoneRegion};
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+use uuid::Uuid;
+
+/// How long a code can be entered after it was sent
+const CODE_TTL_MINUTES: i64 = 10;
+/// Least time between two codes sent to the same number
+const RESEND_INTERVAL_SECONDS: i64 = 60;
+/// Codes that can be entered before a new code has to be requested
+const MAX_CODE_ATTEMPTS: u32 = 5;
+/// How long a verified number can be ordered with
+const TOKEN_TTL_MINUTES: i64 = 30;
+
+/// Texts a one-time code to a phone number
+///
+/// Codes are sent directly rather than through the notification outbox, since
+/// a code delivered after a retry delay is of no use.
+pub async fn send_code(
+    db: &Surreal<Any>,
+    sms: Option<&dyn Notifier>,
+    phone_region: &PhoneRegion,
+    request: &SendVerificationCodeRequest,
+) -> Result<SendVerificationCodeResponse, AppError> {
+    let phone = normalize_phone_number(&request.phone, phone_region)
+        .map_err(|e| AppError::ValidationError(vec![e]))?;
+    let sms = sms.ok_or_else(|| {
+        AppError::InternalError("Phone verification is not available".to_string())
+    })?;
+
+    let code = format!("{:06}", Uuid::new_v4().as_u128() % 1_000_000);
+    let expires_at = Utc::now() + Duration::minutes(CODE_TTL_MINUTES);
+    let saved = verification_repo::save_code(
+        db,
+        &phone,
+        &hash(&code),
+        expires_at,
+        RESEND_INTERVAL_SECONDS,
+    )
+    .await?;
+    if !saved {
+        return Err(AppError::TooManyRequests(
+            "A code was just sent. Wait a minute before requesting another one.".to_string(),
+        ));
+    }
+
+    let message = Message {
+        recipient: phone.clone(),
+        subject: "Your verification code".to_string(),
+        body: format!(
+            "Royal Pizza: your verification code is {}. It expires in {} minutes.",
+            code, CODE_TTL_MINUTES
+        ),
+    };
+    sms.send(&message).await.map_err(|e| {
+        tracing::error!("Failed to send verification code to {}: {}", phone, e);
+        AppError::InternalError("Failed to send the verification code".to_string())
+    })?;
+
+    Ok(SendVerificationCodeResponse { phone, expires_at })
+}
+
+/// Exchanges a correct code for a token proving the number was verified
+pub async fn confirm_code(
+    db: &Surreal<Any>,
+    phone_region: &PhoneRegion,
+    request: &ConfirmVerificationCodeRequest,
+) -> Result<ConfirmVerificationCodeResponse, AppError> {
+    let invalid = |message: &str| AppError::ValidationError(vec![message.to_string()]);
+
+    let phone = normalize_phone_number(&request.phone, phone_region)
+        .map_err(|e| AppError::ValidationError(vec![e]))?;
+    // The attempt is counted before the code is compared, so the limit holds
+    // for guesses made at the same time
+    let Some(verification) =
+        verification_repo::consume_attempt(db, &phone, MAX_CODE_ATTEMPTS).await?
+    else {
+        return match verification_repo::query_verification(db, &phone).await? {
+            Some(_) => Err(AppError::TooManyRequests(
+                "Too many wrong codes. Request a new one.".to_string(),
+            )),
+            None => Err(invalid("Request a verification code first.")),
+        };
+    };
+
+    let code_hash = match (&verification.code_hash, verification.code_expires_at) {
+        (Some(code_hash), Some(expires_at)) if Utc::now() < expires_at => code_hash,
+        _ => return Err(invalid("The code has expired. Request a new one.")),
+    };
+    if hash(request.code.trim()) != *code_hash {
+        let attempts_left = MAX_CODE_ATTEMPTS.saturating_sub(verification.attempts);
+        return Err(invalid(&format!(
+            "The code is not correct. Attempts left: {}.",
+            attempts_left
+        )));
+    }
+
+    let token = Uuid::new_v4().simple().to_string();
+    let expires_at = Utc::now() + Duration::minutes(TOKEN_TTL_MINUTES);
+    verification_repo::save_token(db, &phone, &hash(&token), expires_at).await?;
+
+    Ok(ConfirmVerificationCodeResponse { token, expires_at })
+}
+
+/// Checks that a token was issued for the phone number and is still valid
+pub async fn check_token(
+    db: &Surreal<Any>,
+    phone: &str,
+    token: Option<&str>,
+) -> Result<(), AppError> {
+    let unverified =
+        || AppError::ValidationError(vec!["Verify your phone number first.".to_string()]);
+
+    let token = token.ok_or_else(unverified)?;
+    let verification = verification_repo::query_verification(db, phone)
+        .await?
+        .ok_or_else(unverified)?;
+
+    match (&verification.token_hash, verification.token_expires_at) {
+        (Some(token_hash), Some(expires_at))
+            if Utc::now() < expires_at && *token_hash == hash(token) =>
+        {
+            Ok(())
+        }
+        _ => Err(unverified()),
+    }
+}
+
+fn hash(value: &str) -> String {
+    format!("{:x}", Sha256::digest(value.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::NotifyFuture;
+    use crate::repository::seed;
+    use std::sync::Mutex;
+    use surrealdb::engine::any::connect;
+
+    const PHONE: &str = "+31612345678";
+
+    /// Keeps the texts it is asked to send
+    #[derive(Default)]
+    struct Outbox(Mutex<Vec<Message>>);
+
+    impl Notifier for Outbox {
+        fn send<'a>(&'a self, message: &'a Message) -> NotifyFuture<'a> {
+            self.0.lock().unwrap().push(message.clone());
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    impl Outbox {
+        /// The code in the last text sent
+        fn last_code(&self) -> String {
+            let messages = self.0.lock().unwrap();
+            let body = &messages.last().unwrap().body;
+            body.chars().filter(char::is_ascii_digit).take(6).collect()
+        }
+    }
+
+    async fn database() -> Surreal<Any> {
+        let db = connect("mem://").await.unwrap();
+        db.use_ns("test").use_db("test").await.unwrap();
+        seed::init_schema_only(&db).await.unwrap();
+        db
+    }
+
+    fn region() -> &'static PhoneRegion {
+        PhoneRegion::find("NL").unwrap()
+    }
+
+    async fn confirm(
+        db: &Surreal<Any>,
+        code: &str,
+    ) -> Result<ConfirmVerificationCodeResponse, AppError> {
+        let request = ConfirmVerificationCodeRequest {
+            phone: PHONE.to_string(),
+            code: code.to_string(),
+        };
+        confirm_code(db, region(), &request).await
+    }
+
+    #[tokio::test]
+    async fn test_send_code_throttles_resends() {
+        let db = database().await;
+        let outbox = Outbox::default();
+        let request = SendVerificationCodeRequest {
+            phone: PHONE.to_string(),
+        };
+
+        assert!(send_code(&db, Some(&outbox), region(), &request)
+            .await
+            .is_ok());
+        assert!(matches!(
+            send_code(&db, Some(&outbox), region(), &request).await,
+            Err(AppError::TooManyRequests(_))
+        ));
+        assert_eq!(outbox.0.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_code_issues_token() {
+        let db = database().await;
+        let outbox = Outbox::default();
+        let request = SendVerificationCodeRequest {
+            phone: PHONE.to_string(),
+        };
+        send_code(&db, Some(&outbox), region(), &request)
+            .await
+            .unwrap();
+
+        let confirmed = confirm(&db, &outbox.last_code()).await.unwrap();
+
+        assert!(check_token(&db, PHONE, Some(&confirmed.token))
+            .await
+            .is_ok());
+        assert!(check_token(&db, PHONE, Some("guess")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_code_stops_after_max_attempts() {
+        let db = database().await;
+        let outbox = Outbox::default();
+        let request = SendVerificationCodeRequest {
+            phone: PHONE.to_string(),
+        };
+        send_code(&db, Some(&outbox), region(), &request)
+            .await
+            .unwrap();
+        let code = outbox.last_code();
+        let wrong = if code == "000000" { "111111" } else { "000000" };
+
+        for _ in 0..MAX_CODE_ATTEMPTS {
+            assert!(matches!(
+                confirm(&db, wrong).await,
+                Err(AppError::ValidationError(_))
+            ));
+        }
+
+        // Even the right code is refused once the attempts are used up
+        assert!(matches!(
+            confirm(&db, &code).await,
+            Err(AppError::TooManyRequests(_))
+        ));
+        let verification = verification_repo::query_verification(&db, PHONE)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(verification.attempts, MAX_CODE_ATTEMPTS);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_code_without_code_sent() {
+        let db = database().await;
+
+        assert!(matches!(
+            confirm(&db, "123456").await,
+            Err(AppError::ValidationError(_))
+        ));
+    }
+}
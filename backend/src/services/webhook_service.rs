@@ -0,0 +1,439 @@
+use crate::middleware::error::AppError;
+use crate::repository::db::Database;
+use crate::repository::webhook_repo;
+use crate::services::audit_service;
+use crate::services::health_service::WorkerHeartbeat;
+use crate::services::outbox::{self, Outbox, OutboxFuture};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde_json::json;
+use sha2::Sha256;
+use shared::dto::{
+    CreateWebhookRequest, GetWebhookDeliveriesResponse, GetWebhooksResponse, UpdateWebhookRequest,
+};
+use shared::models::{
+    AuditAction, Order, OrderStatus, WebhookDelivery, WebhookDeliveryStatus, WebhookEndpoint,
+    WebhookEvent,
+};
+use shared::validation::{validate_webhook_events, validate_webhook_url};
+use std::time::Duration;
+use surrealdb::engine::any::Any;
+use surrealdb::Surreal;
+use uuid::Uuid;
+
+/// Header with the event type of a delivery
+pub const EVENT_HEADER: &str = "X-RoyalPizza-Event";
+/// Header with the delivery id, stable across retries
+pub const DELIVERY_HEADER: &str = "X-RoyalPizza-Delivery";
+/// Header with the payload signature, `t=<unix time>,v1=<hex HMAC-SHA256>`
+pub const SIGNATURE_HEADER: &str = "X-RoyalPizza-Signature";
+
+/// How long an endpoint may take to answer
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Attempts before a delivery moves to the dead letters
+const MAX_ATTEMPTS: u32 = 10;
+/// Delay before the first retry, doubled on every further attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(6 * 3600);
+/// Deliveries listed when the client does not ask for a number
+const DEFAULT_DELIVERY_LOG_SIZE: u32 = 50;
+const MAX_DELIVERY_LOG_SIZE: u32 = 200;
+/// Characters of an endpoint's error response kept in the delivery log
+const MAX_ERROR_LENGTH: usize = 500;
+
+pub async fn list_webhooks(db: &Surreal<Any>) -> Result<GetWebhooksResponse, AppError> {
+    let webhooks = webhook_repo::query_webhooks(db).await?;
+    Ok(GetWebhooksResponse { webhooks })
+}
+
+/// Registers an endpoint with a newly generated signing secret
+pub async fn create_webhook(
+    db: &Surreal<Any>,
+    request: CreateWebhookRequest,
+) -> Result<WebhookEndpoint, AppError> {
+    let url = request.url.trim().to_string();
+    validate_webhook(&url, &request.events)?;
+
+    let webhook = WebhookEndpoint {
+        id: Uuid::new_v4().to_string(),
+        url,
+        events: dedup_events(request.events),
+        secret: format!("whsec_{}", Uuid::new_v4().simple()),
+        description: clean_description(request.description),
+        is_active: true,
+        created_at: Utc::now(),
+    };
+
+    let created = webhook_repo::create_webhook_in_db(db, &webhook).await?;
+    audit_service::record(
+        db,
+        AuditAction::WebhookCreated,
+        &created.id,
+        None,
+        Some(&created),
+    )
+    .await;
+
+    Ok(created)
+}
+
+pub async fn update_webhook(
+    db: &Surreal<Any>,
+    id: &str,
+    request: UpdateWebhookRequest,
+) -> Result<WebhookEndpoint, AppError> {
+    let webhook = get_webhook(db, id).await?;
+
+    let url = request
+        .url
+        .map(|url| url.trim().to_string())
+        .unwrap_or_else(|| webhook.url.clone());
+    let events = request
+        .events
+        .map(dedup_events)
+        .unwrap_or_else(|| webhook.events.clone());
+    let description = match request.description {
+        Some(description) => clean_description(Some(description)),
+        None => webhook.description.clone(),
+    };
+    let is_active = request.is_active.unwrap_or(webhook.is_active);
+
+    validate_webhook(&url, &events)?;
+
+    let updated =
+        webhook_repo::update_webhook(db, &webhook.id, url, events, description, is_active)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Webhook with id {} not found", id)))?;
+
+    audit_service::record(
+        db,
+        AuditAction::WebhookUpdated,
+        &webhook.id,
+        Some(&webhook),
+        Some(&updated),
+    )
+    .await;
+
+    Ok(updated)
+}
+
+pub async fn delete_webhook(db: &Surreal<Any>, id: &str) -> Result<(), AppError> {
+    let webhook = get_webhook(db, id).await?;
+    webhook_repo::delete_webhook(db, &webhook.id).await?;
+    audit_service::record(
+        db,
+        AuditAction::WebhookDeleted,
+        &webhook.id,
+        Some(&webhook),
+        None,
+    )
+    .await;
+    Ok(())
+}
+
+/// Delivery log of an endpoint, most recent first
+pub async fn get_deliveries(
+    db: &Surreal<Any>,
+    id: &str,
+    status: Option<WebhookDeliveryStatus>,
+    limit: Option<u32>,
+) -> Result<GetWebhookDeliveriesResponse, AppError> {
+    let webhook = get_webhook(db, id).await?;
+    let limit = limit
+        .unwrap_or(DEFAULT_DELIVERY_LOG_SIZE)
+        .clamp(1, MAX_DELIVERY_LOG_SIZE);
+
+    let deliveries = webhook_repo::query_deliveries(db, &webhook.id, status, limit).await?;
+    Ok(GetWebhookDeliveriesResponse { deliveries })
+}
+
+/// Queues a dead letter for another round of attempts
+pub async fn retry_delivery(db: &Surreal<Any>, id: &str) -> Result<WebhookDelivery, AppError> {
+    let delivery = webhook_repo::requeue_delivery(db, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("No dead letter delivery with id {}", id)))?;
+
+    // Only dead letters are requeued, which also resets their attempts
+    let before = WebhookDelivery {
+        status: WebhookDeliveryStatus::DeadLetter,
+        ..delivery.clone()
+    };
+    audit_service::record(
+        db,
+        AuditAction::WebhookDeliveryRetried,
+        &delivery.id,
+        Some(&before),
+        Some(&delivery),
+    )
+    .await;
+
+    Ok(delivery)
+}
+
+/// Queues an event about an order for every endpoint subscribed to it
+///
+/// The order itself is already saved, so failures are only logged.
+pub async fn publish(
+    db: &Surreal<Any>,
+    event: WebhookEvent,
+    order: &Order,
+    previous_status: Option<OrderStatus>,
+) {
+    let endpoints = match webhook_repo::query_webhooks(db).await {
+        Ok(endpoints) => endpoints,
+        Err(e) => {
+            tracing::error!(
+                "Failed to load webhooks for {} of order {}: {}",
+                event.as_str(),
+                order.order_number,
+                e
+            );
+            return;
+        }
+    };
+
+    let subscribed: Vec<&WebhookEndpoint> = endpoints
+        .iter()
+        .filter(|endpoint| endpoint.is_subscribed(event))
+        .collect();
+    if subscribed.is_empty() {
+        return;
+    }
+
+    let now = Utc::now();
+    let payload = json!({
+        "id": Uuid::new_v4().to_string(),
+        "type": event.as_str(),
+        "created_at": now,
+        "data": {
+            "order": order,
+            "previous_status": previous_status,
+        },
+    })
+    .to_string();
+
+    for endpoint in subscribed {
+        let delivery = WebhookDelivery {
+            id: Uuid::new_v4().to_string(),
+            endpoint_id: endpoint.id.clone(),
+            event,
+            order_id: order.id.clone(),
+            payload: payload.clone(),
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            last_status_code: None,
+            last_error: None,
+            created_at: now,
+            delivered_at: None,
+        };
+
+        if let Err(e) = webhook_repo::create_delivery_in_db(db, &delivery).await {
+            tracing::error!(
+                "Failed to queue {} of order {} for webhook {}: {}",
+                event.as_str(),
+                order.order_number,
+                endpoint.id,
+                e
+            );
+        }
+    }
+}
+
+/// Delivers queued events until the process stops
+pub async fn run_deliveries(db: Database, heartbeat: WorkerHeartbeat) {
+    let client = match reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent("RoyalPizza-Webhooks/1.0")
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::error!(
+                "Failed to create webhook client, webhooks are not delivered: {}",
+                e
+            );
+            heartbeat.failed(e);
+            return;
+        }
+    };
+
+    outbox::run(db, WebhookQueue { client }, heartbeat).await;
+}
+
+/// The `webhook_delivery` table, posted to the subscribed endpoints
+struct WebhookQueue {
+    client: reqwest::Client,
+}
+
+impl Outbox for WebhookQueue {
+    type Entry = WebhookDelivery;
+
+    const NAME: &'static str = "webhook queue";
+
+    fn claim<'a>(
+        &'a self,
+        db: &'a Surreal<Any>,
+        limit: u32,
+        lease: Duration,
+    ) -> OutboxFuture<'a, Result<Vec<WebhookDelivery>, surrealdb::Error>> {
+        Box::pin(webhook_repo::claim_due_deliveries(db, limit, lease))
+    }
+
+    fn deliver<'a>(
+        &'a self,
+        db: &'a Surreal<Any>,
+        delivery: &'a WebhookDelivery,
+    ) -> OutboxFuture<'a, ()> {
+        Box::pin(deliver(db, &self.client, delivery))
+    }
+}
+
+/// Makes one delivery attempt and records its outcome
+async fn deliver(db: &Surreal<Any>, client: &reqwest::Client, delivery: &WebhookDelivery) {
+    let attempts = delivery.attempts + 1;
+
+    let outcome = match webhook_repo::query_webhook_by_id(db, &delivery.endpoint_id).await {
+        Ok(Some(endpoint)) if endpoint.is_active => post(client, &endpoint, delivery).await,
+        Ok(Some(_)) => Err(DeliveryFailure::permanent("Webhook endpoint is disabled")),
+        Ok(None) => Err(DeliveryFailure::permanent("Webhook endpoint was deleted")),
+        Err(e) => Err(DeliveryFailure::retryable(
+            None,
+            format!("Failed to load webhook endpoint: {}", e),
+        )),
+    };
+
+    let recorded = match outcome {
+        Ok(status_code) => {
+            webhook_repo::mark_delivery_delivered(db, &delivery.id, attempts, status_code).await
+        }
+        Err(failure) => {
+            let retry_at = (failure.retryable && attempts < MAX_ATTEMPTS).then(|| {
+                Utc::now() + outbox::retry_delay(attempts, RETRY_BASE_DELAY, RETRY_MAX_DELAY)
+            });
+            tracing::warn!(
+                "Webhook delivery {} of {} failed (attempt {}): {}",
+                delivery.id,
+                delivery.event.as_str(),
+                attempts,
+                failure.error
+            );
+            webhook_repo::record_failed_attempt(
+                db,
+                &delivery.id,
+                attempts,
+                failure.status_code,
+                &failure.error,
+                retry_at,
+            )
+            .await
+        }
+    };
+
+    if let Err(e) = recorded {
+        tracing::error!("Failed to record webhook delivery {}: {}", delivery.id, e);
+    }
+}
+
+/// Why a delivery attempt failed
+struct DeliveryFailure {
+    /// HTTP status of the answer, if the endpoint answered
+    status_code: Option<u16>,
+    error: String,
+    /// Whether a later attempt may succeed
+    retryable: bool,
+}
+
+impl DeliveryFailure {
+    fn retryable(status_code: Option<u16>, error: String) -> Self {
+        Self {
+            status_code,
+            error,
+            retryable: true,
+        }
+    }
+
+    fn permanent(error: &str) -> Self {
+        Self {
+            status_code: None,
+            error: error.to_string(),
+            retryable: false,
+        }
+    }
+}
+
+/// Posts a delivery to its endpoint, returning the status code of a 2xx answer
+async fn post(
+    client: &reqwest::Client,
+    endpoint: &WebhookEndpoint,
+    delivery: &WebhookDelivery,
+) -> Result<u16, DeliveryFailure> {
+    let signature = sign(&endpoint.secret, Utc::now().timestamp(), &delivery.payload);
+
+    let response = client
+        .post(&endpoint.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, delivery.event.as_str())
+        .header(DELIVERY_HEADER, &delivery.id)
+        .header(SIGNATURE_HEADER, signature)
+        .body(delivery.payload.clone())
+        .send()
+        .await
+        .map_err(|e| DeliveryFailure::retryable(None, e.to_string()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(status.as_u16());
+    }
+
+    let mut body = response.text().await.unwrap_or_default();
+    if let Some((index, _)) = body.char_indices().nth(MAX_ERROR_LENGTH) {
+        body.truncate(index);
+    }
+    Err(DeliveryFailure::retryable(
+        Some(status.as_u16()),
+        format!("Endpoint responded {}: {}", status, body),
+    ))
+}
+
+/// Signature header value for a payload sent at `timestamp`
+///
+/// The HMAC-SHA256 covers `<timestamp>.<payload>`, so receivers can reject
+/// replayed requests by checking the timestamp.
+fn sign(secret: &str, timestamp: i64, payload: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.", timestamp).as_bytes());
+    mac.update(payload.as_bytes());
+    format!("t={},v1={:x}", timestamp, mac.finalize().into_bytes())
+}
+
+async fn get_webhook(db: &Surreal<Any>, id: &str) -> Result<WebhookEndpoint, AppError> {
+    webhook_repo::query_webhook_by_id(db, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Webhook with id {} not found", id)))
+}
+
+fn validate_webhook(url: &str, events: &[WebhookEvent]) -> Result<(), AppError> {
+    let errors: Vec<String> = [validate_webhook_url(url), validate_webhook_events(events)]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect();
+    if !errors.is_empty() {
+        return Err(AppError::ValidationError(errors));
+    }
+    Ok(())
+}
+
+fn dedup_events(events: Vec<WebhookEvent>) -> Vec<WebhookEvent> {
+    WebhookEvent::ALL
+        .into_iter()
+        .filter(|event| events.contains(event))
+        .collect()
+}
+
+fn clean_description(description: Option<String>) -> Option<String> {
+    description
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty())
+}
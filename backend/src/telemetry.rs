@@ -0,0 +1,84 @@
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{Protocol, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{trace::SdkTracerProvider, Resource};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+const SERVICE_NAME: &str = "royal-pizza-backend";
+
+/// Tracing set up for the process, flushed with `shutdown`
+pub struct Telemetry {
+    provider: Option<SdkTracerProvider>,
+}
+
+/// Installs the log output and, when `OTEL_EXPORTER_OTLP_ENDPOINT` or
+/// `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT` is set, exports spans over OTLP/HTTP
+///
+/// `OTEL_EXPORTER_OTLP_PROTOCOL` selects `http/protobuf` (default) or `http/json`.
+pub fn init() -> Result<Telemetry, String> {
+    let provider = if otlp_configured() {
+        Some(create_provider()?)
+    } else {
+        None
+    };
+    let otel_layer = provider
+        .as_ref()
+        .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer(SERVICE_NAME)));
+
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "backend=debug,tower_http=debug".into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .with(otel_layer)
+        .init();
+
+    Ok(Telemetry { provider })
+}
+
+impl Telemetry {
+    pub fn is_exporting(&self) -> bool {
+        self.provider.is_some()
+    }
+
+    /// Exports the spans still buffered
+    pub fn shutdown(self) {
+        if let Some(Err(e)) = self.provider.map(|provider| provider.shutdown()) {
+            tracing::warn!("Failed to flush trace spans: {}", e);
+        }
+    }
+}
+
+fn otlp_configured() -> bool {
+    [
+        "OTEL_EXPORTER_OTLP_ENDPOINT",
+        "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+    ]
+    .iter()
+    .any(|name| std::env::var(name).is_ok_and(|value| !value.trim().is_empty()))
+}
+
+fn create_provider() -> Result<SdkTracerProvider, String> {
+    let protocol = match std::env::var("OTEL_EXPORTER_OTLP_PROTOCOL").as_deref() {
+        Err(_) | Ok("http/protobuf") => Protocol::HttpBinary,
+        Ok("http/json") => Protocol::HttpJson,
+        Ok(other) => {
+            return Err(format!(
+                "Unsupported OTEL_EXPORTER_OTLP_PROTOCOL '{}', use http/protobuf or http/json",
+                other
+            ))
+        }
+    };
+
+    // The endpoint is read from the standard OTEL_EXPORTER_OTLP_* variables
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_protocol(protocol)
+        .build()
+        .map_err(|e| format!("Failed to create OTLP exporter: {}", e))?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(SERVICE_NAME).build())
+        .build())
+}
@@ -0,0 +1,25 @@
+/// Writes a header row and data rows as RFC 4180 CSV
+pub fn write_csv(headers: &[&str], rows: impl IntoIterator<Item = Vec<String>>) -> String {
+    let mut out = String::new();
+    write_row(&mut out, headers.iter().copied());
+    for row in rows {
+        write_row(&mut out, row.iter().map(String::as_str));
+    }
+    out
+}
+
+fn write_row<'a>(out: &mut String, fields: impl Iterator<Item = &'a str>) {
+    for (index, field) in fields.enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(field);
+        }
+    }
+    out.push_str("\r\n");
+}
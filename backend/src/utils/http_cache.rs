@@ -0,0 +1,63 @@
+use crate::middleware::error::AppError;
+use axum::{
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// A response body with the strong ETag of its JSON encoding
+#[derive(Debug, Clone)]
+pub struct Tagged<T> {
+    pub value: T,
+    pub etag: String,
+}
+
+impl<T: Serialize> Tagged<T> {
+    pub fn new(value: T) -> Result<Self, AppError> {
+        let body = serde_json::to_vec(&value)
+            .map_err(|e| AppError::InternalError(format!("Failed to encode response: {}", e)))?;
+        // Half a SHA-256 is plenty to tell versions of a body apart
+        let digest = Sha256::digest(&body);
+        let etag = format!(
+            "\"{}\"",
+            digest[..16]
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<String>()
+        );
+        Ok(Self { value, etag })
+    }
+}
+
+/// Whether the client already has the body, going by its `If-None-Match` header
+///
+/// `If-None-Match` compares weakly, so `W/"..."` matches the strong tag too.
+pub fn is_not_modified(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/// JSON response for a cacheable GET, `304 Not Modified` when the client has the same version
+pub fn cached_json<T: Serialize>(
+    headers: &HeaderMap,
+    tagged: Tagged<T>,
+    cache_control: &'static str,
+) -> Response {
+    let caching = [
+        (header::ETAG, tagged.etag.clone()),
+        (header::CACHE_CONTROL, cache_control.to_string()),
+    ];
+
+    if is_not_modified(headers, &tagged.etag) {
+        (StatusCode::NOT_MODIFIED, caching).into_response()
+    } else {
+        (caching, Json(tagged.value)).into_response()
+    }
+}
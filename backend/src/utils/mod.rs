@@ -1 +1,5 @@
+pub mod csv;
+pub mod http_cache;
+pub mod money;
 pub mod order_number;
+pub mod query;
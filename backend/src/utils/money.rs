@@ -0,0 +1,14 @@
+/// Round an amount to whole cents
+pub fn round_currency(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// Format currency amount as dollar string
+pub fn format_currency(amount: f64) -> String {
+    format!("${:.2}", amount)
+}
+
+/// Format a rate fraction as a percentage string
+pub fn format_percent(rate: f64) -> String {
+    format!("{}%", (rate * 10000.0).round() / 100.0)
+}
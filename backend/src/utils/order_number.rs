@@ -1,11 +1,11 @@
 use crate::repository::order_repo;
 use chrono::Utc;
-use surrealdb::engine::remote::ws::Client;
+use surrealdb::engine::any::Any;
 use surrealdb::Surreal;
 
 /// Generate order number in format: RP-YYYYMMDD-NNN
 /// Example: RP-20260211-001
-pub async fn generate_order_number(db: &Surreal<Client>) -> Result<String, surrealdb::Error> {
+pub async fn generate_order_number(db: &Surreal<Any>) -> Result<String, surrealdb::Error> {
     let now = Utc::now();
     let date_prefix = now.format("%Y%m%d").to_string();
 
@@ -0,0 +1,51 @@
+use crate::middleware::error::AppError;
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use serde::{de::IntoDeserializer, Deserialize};
+use shared::models::OrderStatus;
+
+/// Bound of a time range given in a query string, as a date or a date-time
+///
+/// Dates are interpreted in UTC and cover the whole day, so `?from=2026-03-01&to=2026-03-01`
+/// selects that single day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum TimeBound {
+    Date(NaiveDate),
+    DateTime(DateTime<Utc>),
+}
+
+impl TimeBound {
+    /// Inclusive start of a range beginning at this bound
+    pub fn start(self) -> DateTime<Utc> {
+        match self {
+            TimeBound::Date(date) => date.and_time(NaiveTime::MIN).and_utc(),
+            TimeBound::DateTime(time) => time,
+        }
+    }
+
+    /// Exclusive end of a range ending at this bound
+    pub fn end(self) -> DateTime<Utc> {
+        match self {
+            TimeBound::Date(date) => (date + Duration::days(1))
+                .and_time(NaiveTime::MIN)
+                .and_utc(),
+            TimeBound::DateTime(time) => time,
+        }
+    }
+}
+
+/// Parses a comma separated list of order statuses, e.g. `Confirmed,Preparing`
+pub fn parse_order_statuses(value: &str) -> Result<Vec<OrderStatus>, AppError> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|status| !status.is_empty())
+        .map(|status| {
+            OrderStatus::deserialize(status.into_deserializer()).map_err(
+                |_: serde::de::value::Error| {
+                    AppError::ValidationError(vec![format!("Unknown order status {}", status)])
+                },
+            )
+        })
+        .collect()
+}
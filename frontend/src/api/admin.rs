@@ -0,0 +1,231 @@
+use gloo_net::http::{Request, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use shared::dto::{
+    CreateWebhookRequest, CustomerHistoryResponse, GetCustomersResponse, GetOrdersResponse,
+    GetPizzasResponse, GetWebhookDeliveriesResponse, GetWebhooksResponse, ItemSalesReport,
+    SalesSummary, UpdateOrderStatusRequest, UpdatePizzaRequest, UpdateWebhookRequest,
+};
+use shared::models::{Order, OrderStatus, Pizza, WebhookDelivery, WebhookEndpoint};
+
+use super::client::API_BASE_URL;
+use super::error::ApiError;
+
+/// Filters of the admin order list; empty fields do not restrict the list
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrderListFilter {
+    /// Inclusive start of the order date, `YYYY-MM-DD`
+    pub from: String,
+    /// Inclusive end of the order date, `YYYY-MM-DD`
+    pub to: String,
+    pub status: Option<OrderStatus>,
+    pub search: String,
+}
+
+/// Fetch a page of orders matching the filter, newest first
+///
+/// Pass the `next_cursor` of the previous page to continue the list.
+pub async fn fetch_orders(
+    token: &str,
+    filter: &OrderListFilter,
+    cursor: Option<&str>,
+) -> Result<GetOrdersResponse, ApiError> {
+    let mut params = Vec::new();
+    if !filter.from.is_empty() {
+        params.push(("created_from", filter.from.clone()));
+    }
+    if !filter.to.is_empty() {
+        params.push(("created_to", filter.to.clone()));
+    }
+    if let Some(status) = filter.status {
+        params.push(("status", format!("{:?}", status)));
+    }
+    if !filter.search.trim().is_empty() {
+        params.push(("search", filter.search.trim().to_string()));
+    }
+    if let Some(cursor) = cursor {
+        params.push(("cursor", cursor.to_string()));
+    }
+
+    let request = Request::get(&format!("{}/admin/orders", API_BASE_URL)).query(params);
+    send(authorized(request, token)).await
+}
+
+/// Move an order to another status
+pub async fn update_order_status(
+    token: &str,
+    order_id: &str,
+    status: OrderStatus,
+) -> Result<Order, ApiError> {
+    let url = format!("{}/admin/orders/{}/status", API_BASE_URL, order_id);
+    send_json(
+        authorized(Request::put(&url), token),
+        &UpdateOrderStatusRequest { status },
+    )
+    .await
+}
+
+/// Find customers by name or phone number, most recent first
+pub async fn fetch_customers(token: &str, search: &str) -> Result<GetCustomersResponse, ApiError> {
+    let request = Request::get(&format!("{}/admin/customers", API_BASE_URL))
+        .query([("search", search.trim())]);
+    send(authorized(request, token)).await
+}
+
+/// Fetch a customer with their most recent orders
+pub async fn fetch_customer(
+    token: &str,
+    customer_id: &str,
+) -> Result<CustomerHistoryResponse, ApiError> {
+    let url = format!("{}/admin/customers/{}", API_BASE_URL, customer_id);
+    send(authorized(Request::get(&url), token)).await
+}
+
+/// Fetch the whole menu including unavailable pizzas
+pub async fn fetch_menu(token: &str) -> Result<GetPizzasResponse, ApiError> {
+    let url = format!("{}/admin/pizzas", API_BASE_URL);
+    send(authorized(Request::get(&url), token)).await
+}
+
+/// Edit a menu pizza
+pub async fn update_pizza(
+    token: &str,
+    pizza_id: &str,
+    request: &UpdatePizzaRequest,
+) -> Result<Pizza, ApiError> {
+    let url = format!("{}/admin/pizzas/{}", API_BASE_URL, pizza_id);
+    send_json(authorized(Request::put(&url), token), request).await
+}
+
+/// Fetch the sales summary of an inclusive date range (`YYYY-MM-DD`)
+pub async fn fetch_sales_summary(
+    token: &str,
+    from: &str,
+    to: &str,
+) -> Result<SalesSummary, ApiError> {
+    let request = Request::get(&format!("{}/admin/reports/summary", API_BASE_URL))
+        .query([("from", from), ("to", to)]);
+    send(authorized(request, token)).await
+}
+
+/// Fetch item sales of an inclusive date range (`YYYY-MM-DD`)
+pub async fn fetch_item_sales(
+    token: &str,
+    from: &str,
+    to: &str,
+) -> Result<ItemSalesReport, ApiError> {
+    let request = Request::get(&format!("{}/admin/reports/items", API_BASE_URL))
+        .query([("from", from), ("to", to)]);
+    send(authorized(request, token)).await
+}
+
+/// Fetch all webhook endpoints
+pub async fn fetch_webhooks(token: &str) -> Result<GetWebhooksResponse, ApiError> {
+    let url = format!("{}/admin/webhooks", API_BASE_URL);
+    send(authorized(Request::get(&url), token)).await
+}
+
+/// Register a webhook endpoint
+pub async fn create_webhook(
+    token: &str,
+    request: &CreateWebhookRequest,
+) -> Result<WebhookEndpoint, ApiError> {
+    let url = format!("{}/admin/webhooks", API_BASE_URL);
+    send_json(authorized(Request::post(&url), token), request).await
+}
+
+/// Change a webhook endpoint
+pub async fn update_webhook(
+    token: &str,
+    webhook_id: &str,
+    request: &UpdateWebhookRequest,
+) -> Result<WebhookEndpoint, ApiError> {
+    let url = format!("{}/admin/webhooks/{}", API_BASE_URL, webhook_id);
+    send_json(authorized(Request::put(&url), token), request).await
+}
+
+/// Remove a webhook endpoint
+pub async fn delete_webhook(token: &str, webhook_id: &str) -> Result<(), ApiError> {
+    let url = format!("{}/admin/webhooks/{}", API_BASE_URL, webhook_id);
+    let response = authorized(Request::delete(&url), token)
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if !response.ok() {
+        let status = response.status();
+        let message = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(ApiError::Http { status, message });
+    }
+
+    Ok(())
+}
+
+/// Fetch the most recent deliveries to a webhook endpoint
+pub async fn fetch_webhook_deliveries(
+    token: &str,
+    webhook_id: &str,
+) -> Result<GetWebhookDeliveriesResponse, ApiError> {
+    let url = format!("{}/admin/webhooks/{}/deliveries", API_BASE_URL, webhook_id);
+    send(authorized(Request::get(&url), token)).await
+}
+
+/// Queue a dead letter delivery again
+pub async fn retry_webhook_delivery(
+    token: &str,
+    delivery_id: &str,
+) -> Result<WebhookDelivery, ApiError> {
+    let url = format!(
+        "{}/admin/webhooks/deliveries/{}/retry",
+        API_BASE_URL, delivery_id
+    );
+    send(authorized(Request::post(&url), token)).await
+}
+
+fn authorized(request: RequestBuilder, token: &str) -> RequestBuilder {
+    request.header("Authorization", &format!("Bearer {}", token))
+}
+
+async fn send<T: DeserializeOwned>(request: RequestBuilder) -> Result<T, ApiError> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    parse(response).await
+}
+
+async fn send_json<B: serde::Serialize, T: DeserializeOwned>(
+    request: RequestBuilder,
+    body: &B,
+) -> Result<T, ApiError> {
+    let body = serde_json::to_string(body).map_err(|e| ApiError::Serialize(e.to_string()))?;
+
+    let response = request
+        .header("Content-Type", "application/json")
+        .body(body)
+        .map_err(|e| ApiError::Serialize(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    parse(response).await
+}
+
+async fn parse<T: DeserializeOwned>(response: Response) -> Result<T, ApiError> {
+    if !response.ok() {
+        let status = response.status();
+        let message = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(ApiError::Http { status, message });
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ApiError::Parse(e.to_string()))
+}
@@ -1,22 +1,48 @@
 use gloo_net::http::Request;
-use shared::dto::{CreateOrderRequest, CreateOrderResponse, GetPizzasResponse};
-use shared::models::{Order, Pizza};
+use shared::dto::{
+    ConfirmVerificationCodeRequest, ConfirmVerificationCodeResponse, CreateOrderRequest,
+    CreateOrderResponse, CustomerHistoryResponse, CustomerLookupRequest, GetPizzasResponse,
+    QuoteRequest, QuoteResponse, SendVerificationCodeRequest, SendVerificationCodeResponse,
+};
+use shared::models::Order;
+use std::cell::RefCell;
 
 use super::error::ApiError;
 
-const API_BASE_URL: &str = match option_env!("TRUNK_API_BASE_URL") {
+pub(crate) const API_BASE_URL: &str = match option_env!("TRUNK_API_BASE_URL") {
     Some(url) => url,
     None => "http://localhost:8080/api", // Development fallback
 };
 
-/// Fetch all pizzas from the API
-pub async fn fetch_pizzas() -> Result<Vec<Pizza>, ApiError> {
+thread_local! {
+    /// Last menu fetched and its ETag, revalidated with `If-None-Match`
+    static CACHED_MENU: RefCell<Option<(String, GetPizzasResponse)>> = const { RefCell::new(None) };
+}
+
+/// Fetch the menu (all pizzas and custom pizza pricing) from the API
+///
+/// A menu fetched before is only downloaded again when it has changed.
+pub async fn fetch_pizzas() -> Result<GetPizzasResponse, ApiError> {
     let url = format!("{}/pizzas", API_BASE_URL);
 
-    let response = Request::get(&url)
+    let cached_etag = CACHED_MENU.with_borrow(|menu| menu.as_ref().map(|(etag, _)| etag.clone()));
+    let mut request = Request::get(&url);
+    if let Some(etag) = &cached_etag {
+        request = request.header("If-None-Match", etag);
+    }
+
+    let response = request
         .send()
         .await
-        .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if response.status() == 304 {
+        // The menu we sent the ETag of is still current
+        let cached = CACHED_MENU.with_borrow(|menu| menu.as_ref().map(|(_, menu)| menu.clone()));
+        if let Some(menu) = cached {
+            return Ok(menu);
+        }
+    }
 
     if !response.ok() {
         let status = response.status();
@@ -24,25 +50,32 @@ pub async fn fetch_pizzas() -> Result<Vec<Pizza>, ApiError> {
             .text()
             .await
             .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(ApiError::HttpError { status, message });
+        return Err(ApiError::Http { status, message });
     }
 
+    let etag = response.headers().get("etag");
     let data: GetPizzasResponse = response
         .json()
         .await
-        .map_err(|e| ApiError::ParseError(e.to_string()))?;
+        .map_err(|e| ApiError::Parse(e.to_string()))?;
 
-    Ok(data.pizzas)
+    CACHED_MENU.set(etag.map(|etag| (etag, data.clone())));
+    Ok(data)
 }
 
-/// Fetch a single pizza by ID
-pub async fn fetch_pizza_by_id(id: &str) -> Result<Pizza, ApiError> {
-    let url = format!("{}/pizzas/{}", API_BASE_URL, id);
+/// Fetch the authoritative pricing for a set of cart items
+pub async fn fetch_quote(request: QuoteRequest) -> Result<QuoteResponse, ApiError> {
+    let url = format!("{}/quote", API_BASE_URL);
 
-    let response = Request::get(&url)
+    let body = serde_json::to_string(&request).map_err(|e| ApiError::Serialize(e.to_string()))?;
+
+    let response = Request::post(&url)
+        .header("Content-Type", "application/json")
+        .body(body)
+        .map_err(|e| ApiError::Serialize(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if !response.ok() {
         let status = response.status();
@@ -50,15 +83,15 @@ pub async fn fetch_pizza_by_id(id: &str) -> Result<Pizza, ApiError> {
             .text()
             .await
             .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(ApiError::HttpError { status, message });
+        return Err(ApiError::Http { status, message });
     }
 
-    let pizza: Pizza = response
+    let quote: QuoteResponse = response
         .json()
         .await
-        .map_err(|e| ApiError::ParseError(e.to_string()))?;
+        .map_err(|e| ApiError::Parse(e.to_string()))?;
 
-    Ok(pizza)
+    Ok(quote)
 }
 
 /// Create a new order
@@ -67,16 +100,15 @@ pub async fn create_order(
 ) -> Result<CreateOrderResponse, ApiError> {
     let url = format!("{}/orders", API_BASE_URL);
 
-    let body = serde_json::to_string(&request)
-        .map_err(|e| ApiError::SerializeError(e.to_string()))?;
+    let body = serde_json::to_string(&request).map_err(|e| ApiError::Serialize(e.to_string()))?;
 
     let response = Request::post(&url)
         .header("Content-Type", "application/json")
         .body(body)
-        .map_err(|e| ApiError::SerializeError(e.to_string()))?
+        .map_err(|e| ApiError::Serialize(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if !response.ok() {
         let status = response.status();
@@ -84,13 +116,13 @@ pub async fn create_order(
             .text()
             .await
             .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(ApiError::HttpError { status, message });
+        return Err(ApiError::Http { status, message });
     }
 
     let order_response: CreateOrderResponse = response
         .json()
         .await
-        .map_err(|e| ApiError::ParseError(e.to_string()))?;
+        .map_err(|e| ApiError::Parse(e.to_string()))?;
 
     Ok(order_response)
 }
@@ -102,7 +134,7 @@ pub async fn fetch_order_by_id(id: &str) -> Result<Order, ApiError> {
     let response = Request::get(&url)
         .send()
         .await
-        .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+        .map_err(|e| ApiError::Network(e.to_string()))?;
 
     if !response.ok() {
         let status = response.status();
@@ -110,13 +142,112 @@ pub async fn fetch_order_by_id(id: &str) -> Result<Order, ApiError> {
             .text()
             .await
             .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(ApiError::HttpError { status, message });
+        return Err(ApiError::Http { status, message });
     }
 
     let order: Order = response
         .json()
         .await
-        .map_err(|e| ApiError::ParseError(e.to_string()))?;
+        .map_err(|e| ApiError::Parse(e.to_string()))?;
 
     Ok(order)
 }
+
+/// Fetch the previous orders of a returning customer
+pub async fn lookup_customer(
+    request: CustomerLookupRequest,
+) -> Result<CustomerHistoryResponse, ApiError> {
+    let url = format!("{}/customers/lookup", API_BASE_URL);
+
+    let body = serde_json::to_string(&request).map_err(|e| ApiError::Serialize(e.to_string()))?;
+
+    let response = Request::post(&url)
+        .header("Content-Type", "application/json")
+        .body(body)
+        .map_err(|e| ApiError::Serialize(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if !response.ok() {
+        let status = response.status();
+        let message = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(ApiError::Http { status, message });
+    }
+
+    let history: CustomerHistoryResponse = response
+        .json()
+        .await
+        .map_err(|e| ApiError::Parse(e.to_string()))?;
+
+    Ok(history)
+}
+
+/// Text a one-time code to a phone number
+pub async fn send_verification_code(
+    request: SendVerificationCodeRequest,
+) -> Result<SendVerificationCodeResponse, ApiError> {
+    let url = format!("{}/verify/phone", API_BASE_URL);
+
+    let body = serde_json::to_string(&request).map_err(|e| ApiError::Serialize(e.to_string()))?;
+
+    let response = Request::post(&url)
+        .header("Content-Type", "application/json")
+        .body(body)
+        .map_err(|e| ApiError::Serialize(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if !response.ok() {
+        let status = response.status();
+        let message = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(ApiError::Http { status, message });
+    }
+
+    let sent: SendVerificationCodeResponse = response
+        .json()
+        .await
+        .map_err(|e| ApiError::Parse(e.to_string()))?;
+
+    Ok(sent)
+}
+
+/// Exchange the code texted to a phone number for a verification token
+pub async fn confirm_verification_code(
+    request: ConfirmVerificationCodeRequest,
+) -> Result<ConfirmVerificationCodeResponse, ApiError> {
+    let url = format!("{}/verify/confirm", API_BASE_URL);
+
+    let body = serde_json::to_string(&request).map_err(|e| ApiError::Serialize(e.to_string()))?;
+
+    let response = Request::post(&url)
+        .header("Content-Type", "application/json")
+        .body(body)
+        .map_err(|e| ApiError::Serialize(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+
+    if !response.ok() {
+        let status = response.status();
+        let message = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(ApiError::Http { status, message });
+    }
+
+    let confirmed: ConfirmVerificationCodeResponse = response
+        .json()
+        .await
+        .map_err(|e| ApiError::Parse(e.to_string()))?;
+
+    Ok(confirmed)
+}
@@ -1,33 +1,69 @@
+use shared::dto::ErrorResponse;
 use std::fmt;
 
 /// API error types
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum ApiError {
-    NetworkError(String),
-    HttpError { status: u16, message: String },
-    ParseError(String),
-    SerializeError(String),
+    Network(String),
+    Http { status: u16, message: String },
+    Parse(String),
+    Serialize(String),
 }
 
 impl ApiError {
+    /// Check if the server rejected the credentials of the request
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, ApiError::Http { status: 401, .. })
+    }
+
+    /// Check if the requested resource does not exist
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, ApiError::Http { status: 404, .. })
+    }
+
+    /// Id the server gave the failed request, for support to look it up
+    pub fn request_id(&self) -> Option<String> {
+        match self {
+            ApiError::Http { message, .. } => serde_json::from_str::<ErrorResponse>(message)
+                .ok()
+                .and_then(|response| response.request_id),
+            _ => None,
+        }
+    }
+
     /// Convert error to user-friendly message
     pub fn user_message(&self) -> String {
         match self {
-            ApiError::NetworkError(_) => {
+            ApiError::Network(_) => {
                 "Unable to connect to the server. Please check your internet connection and try again.".to_string()
             }
-            ApiError::HttpError { status, message } => {
+            ApiError::Http { status, message } => {
                 match *status {
                     400 => format!("Invalid request: {}", message),
+                    401 => "You are not authorized. Please log in again.".to_string(),
                     404 => "The requested resource was not found.".to_string(),
-                    500..=599 => "Server error. Please try again later.".to_string(),
+                    422 => serde_json::from_str::<ErrorResponse>(message)
+                        .ok()
+                        .and_then(|response| response.details)
+                        .map(|details| details.join(" "))
+                        .unwrap_or_else(|| format!("Invalid request: {}", message)),
+                    429 => serde_json::from_str::<ErrorResponse>(message)
+                        .map(|response| response.error)
+                        .unwrap_or_else(|_| "Too many requests. Please wait a moment.".to_string()),
+                    500..=599 => match self.request_id() {
+                        Some(request_id) => format!(
+                            "Server error. Please try again later. If it keeps happening, contact us and mention reference {}.",
+                            request_id
+                        ),
+                        None => "Server error. Please try again later.".to_string(),
+                    },
                     _ => format!("Request failed: {}", message),
                 }
             }
-            ApiError::ParseError(_) => {
+            ApiError::Parse(_) => {
                 "Failed to process server response. Please try again.".to_string()
             }
-            ApiError::SerializeError(_) => {
+            ApiError::Serialize(_) => {
                 "Failed to prepare request. Please check your input and try again.".to_string()
             }
         }
@@ -37,12 +73,12 @@ impl ApiError {
 impl fmt::Display for ApiError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            ApiError::NetworkError(msg) => write!(f, "Network error: {}", msg),
-            ApiError::HttpError { status, message } => {
+            ApiError::Network(msg) => write!(f, "Network error: {}", msg),
+            ApiError::Http { status, message } => {
                 write!(f, "HTTP error {}: {}", status, message)
             }
-            ApiError::ParseError(msg) => write!(f, "Parse error: {}", msg),
-            ApiError::SerializeError(msg) => write!(f, "Serialize error: {}", msg),
+            ApiError::Parse(msg) => write!(f, "Parse error: {}", msg),
+            ApiError::Serialize(msg) => write!(f, "Serialize error: {}", msg),
         }
     }
 }
@@ -1,13 +1,22 @@
 use leptos::prelude::*;
 use leptos_router::{components::*, path};
 
+use crate::pages::admin::{
+    customers::AdminCustomersPage, login::AdminLoginPage, menu::AdminMenuPage,
+    order_detail::AdminOrderDetailPage, orders::AdminOrdersPage, sales::AdminSalesPage,
+    webhooks::AdminWebhooksPage,
+};
 use crate::pages::{confirmation::ConfirmationPage, menu::MenuPage, order::OrderPage};
+use crate::state::admin::provide_admin_session;
 use crate::state::cart::provide_cart_state;
+use crate::state::quote::provide_quote_state;
 
 #[component]
 pub fn App() -> impl IntoView {
-    // Provide cart state to entire app
-    provide_cart_state();
+    // Provide cart state and its server-side quote to entire app
+    let cart = provide_cart_state();
+    provide_quote_state(cart);
+    provide_admin_session();
 
     view! {
         <Router>
@@ -16,6 +25,13 @@ pub fn App() -> impl IntoView {
                     <Route path=path!("") view=MenuPage />
                     <Route path=path!("order") view=OrderPage />
                     <Route path=path!("confirmation/:id") view=ConfirmationPage />
+                    <Route path=path!("admin") view=AdminOrdersPage />
+                    <Route path=path!("admin/login") view=AdminLoginPage />
+                    <Route path=path!("admin/orders/:id") view=AdminOrderDetailPage />
+                    <Route path=path!("admin/customers") view=AdminCustomersPage />
+                    <Route path=path!("admin/webhooks") view=AdminWebhooksPage />
+                    <Route path=path!("admin/menu") view=AdminMenuPage />
+                    <Route path=path!("admin/sales") view=AdminSalesPage />
                 </Routes>
             </main>
         </Router>
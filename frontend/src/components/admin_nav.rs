@@ -0,0 +1,33 @@
+use leptos::prelude::*;
+use leptos_router::{components::A, hooks::use_navigate};
+
+use crate::state::admin::use_admin_session;
+
+/// Navigation of the admin section
+///
+/// Also guards the admin pages: without a token the visitor is sent to the
+/// login page.
+#[component]
+pub fn AdminNav() -> impl IntoView {
+    let session = use_admin_session();
+    let navigate = use_navigate();
+
+    Effect::new(move |_| {
+        if !session.is_logged_in() {
+            navigate("/admin/login", Default::default());
+        }
+    });
+
+    view! {
+        <nav class="admin-nav">
+            <A href="/admin">"Orders"</A>
+            <A href="/admin/customers">"Customers"</A>
+            <A href="/admin/menu">"Menu"</A>
+            <A href="/admin/sales">"Sales"</A>
+            <A href="/admin/webhooks">"Webhooks"</A>
+            <button class="admin-logout-button" on:click=move |_| session.logout()>
+                "Log out"
+            </button>
+        </nav>
+    }
+}
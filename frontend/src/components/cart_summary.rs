@@ -2,15 +2,17 @@ use leptos::prelude::*;
 use leptos_router::hooks::*;
 
 use crate::state::cart::use_cart;
+use crate::state::quote::use_quote;
 use crate::utils::format::format_currency;
 
 #[component]
 pub fn CartSummary() -> impl IntoView {
     let cart = use_cart();
+    let quote = use_quote();
     let navigate = use_navigate();
 
     let item_count = Memo::new(move |_| cart.item_count());
-    let total = Memo::new(move |_| cart.total());
+    let total = Memo::new(move |_| quote.total().unwrap_or_else(|| cart.total()));
     let is_empty = Memo::new(move |_| cart.is_empty());
 
     let proceed_to_order = move |_| {
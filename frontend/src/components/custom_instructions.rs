@@ -0,0 +1,62 @@
+use leptos::prelude::*;
+use shared::validation::constants::MAX_INSTRUCTIONS_LENGTH;
+
+use crate::state::cart::use_cart;
+
+/// Instructions of a custom pizza in the cart, with inline editing
+#[component]
+pub fn CustomInstructions(item_id: String, instructions: String) -> impl IntoView {
+    let cart = use_cart();
+
+    let (is_editing, set_is_editing) = signal(false);
+    let (draft, set_draft) = signal(instructions.clone());
+    let (error, set_error) = signal(None::<String>);
+
+    let start_editing = {
+        let instructions = instructions.clone();
+        move |_| {
+            set_draft.set(instructions.clone());
+            set_error.set(None);
+            set_is_editing.set(true);
+        }
+    };
+
+    let save = move |_| match cart.update_custom_instructions(&item_id, draft.get()) {
+        // The line is re-rendered under its new ID, so no need to leave edit mode
+        Ok(_) => set_is_editing.set(false),
+        Err(e) => set_error.set(Some(e)),
+    };
+
+    view! {
+        <div class="custom-instructions">
+            <Show
+                when=move || is_editing.get()
+                fallback=move || {
+                    view! {
+                        <p class="custom-instructions-text">{instructions.clone()}</p>
+                        <button class="edit-instructions-button" on:click=start_editing.clone()>
+                            "Edit instructions"
+                        </button>
+                    }
+                }
+            >
+                <textarea
+                    class="instructions-input"
+                    rows="3"
+                    maxlength=MAX_INSTRUCTIONS_LENGTH
+                    prop:value=move || draft.get()
+                    on:input=move |ev| set_draft.set(event_target_value(&ev))
+                ></textarea>
+                {move || error.get().map(|e| view! { <div class="validation-error">{e}</div> })}
+                <div class="custom-instructions-actions">
+                    <button class="save-instructions-button" on:click=save.clone()>
+                        "Save"
+                    </button>
+                    <button class="cancel-instructions-button" on:click=move |_| set_is_editing.set(false)>
+                        "Cancel"
+                    </button>
+                </div>
+            </Show>
+        </div>
+    }
+}
@@ -1,13 +1,16 @@
 use leptos::prelude::*;
-use shared::models::{CustomPizza, PizzaSize};
+use shared::models::{CustomPizza, PizzaPrice, PizzaSize};
 
-use crate::utils::format::format_currency;
+use shared::validation::{
+    constants::{MAX_INSTRUCTIONS_LENGTH, MIN_INSTRUCTIONS_LENGTH},
+    validate_custom_instructions,
+};
 
-const MAX_INSTRUCTIONS_LENGTH: usize = 500;
-const MIN_INSTRUCTIONS_LENGTH: usize = 10;
+use crate::utils::format::format_currency;
 
 #[component]
 pub fn CustomPizzaCard(
+    price: PizzaPrice,
     #[prop(into)] on_add_to_cart: Callback<(CustomPizza, u32), ()>,
 ) -> impl IntoView {
     let (instructions, set_instructions) = signal(String::new());
@@ -19,41 +22,20 @@ pub fn CustomPizzaCard(
     let char_count = Memo::new(move |_| instructions.get().len());
     let remaining_chars = Memo::new(move |_| MAX_INSTRUCTIONS_LENGTH - char_count.get());
 
-    let current_price = Memo::new(move |_| {
-        let custom = CustomPizza {
-            instructions: String::new(),
-            size: selected_size.get(),
-        };
-        custom.get_price()
-    });
+    let size_prices = price.clone();
+    let current_price = Memo::new(move |_| selected_size.get().get_price(&size_prices));
 
     let is_valid = Memo::new(move |_| {
         let len = char_count.get();
-        len >= MIN_INSTRUCTIONS_LENGTH && len <= MAX_INSTRUCTIONS_LENGTH
+        (MIN_INSTRUCTIONS_LENGTH..=MAX_INSTRUCTIONS_LENGTH).contains(&len)
     });
 
     let add_to_cart = move |_| {
         let inst = instructions.get();
 
         // Validate
-        if inst.trim().is_empty() {
-            set_validation_error.set(Some("Please provide instructions for your custom pizza.".to_string()));
-            return;
-        }
-
-        if inst.len() < MIN_INSTRUCTIONS_LENGTH {
-            set_validation_error.set(Some(format!(
-                "Instructions must be at least {} characters.",
-                MIN_INSTRUCTIONS_LENGTH
-            )));
-            return;
-        }
-
-        if inst.len() > MAX_INSTRUCTIONS_LENGTH {
-            set_validation_error.set(Some(format!(
-                "Instructions cannot exceed {} characters.",
-                MAX_INSTRUCTIONS_LENGTH
-            )));
+        if let Err(e) = validate_custom_instructions(&inst) {
+            set_validation_error.set(Some(e));
             return;
         }
 
@@ -130,7 +112,7 @@ pub fn CustomPizzaCard(
                             on:click=move |_| set_selected_size.set(PizzaSize::Small)
                         >
                             <span class="size-name">"Small"</span>
-                            <span class="size-price">"$10.99"</span>
+                            <span class="size-price">{format_currency(price.small)}</span>
                         </button>
                         <button
                             class="size-button"
@@ -138,7 +120,7 @@ pub fn CustomPizzaCard(
                             on:click=move |_| set_selected_size.set(PizzaSize::Medium)
                         >
                             <span class="size-name">"Medium"</span>
-                            <span class="size-price">"$14.99"</span>
+                            <span class="size-price">{format_currency(price.medium)}</span>
                         </button>
                         <button
                             class="size-button"
@@ -146,7 +128,7 @@ pub fn CustomPizzaCard(
                             on:click=move |_| set_selected_size.set(PizzaSize::Large)
                         >
                             <span class="size-name">"Large"</span>
-                            <span class="size-price">"$17.99"</span>
+                            <span class="size-price">{format_currency(price.large)}</span>
                         </button>
                     </div>
                 </div>
@@ -161,7 +143,7 @@ pub fn CustomPizzaCard(
                             prop:value=move || quantity.get()
                             on:input=move |ev| {
                                 if let Ok(val) = event_target_value(&ev).parse::<u32>() {
-                                    set_quantity.set(val.max(1).min(10));
+                                    set_quantity.set(val.clamp(1, 10));
                                 }
                             }
                         />
@@ -1,8 +1,11 @@
+pub mod admin_nav;
 pub mod cart_summary;
+pub mod custom_instructions;
 pub mod custom_pizza_card;
 pub mod error_display;
 pub mod layout;
 pub mod loading;
+pub mod phone_verification;
 pub mod pizza_card;
-pub mod skeleton;
+pub mod returning_customer;
 pub mod toast;
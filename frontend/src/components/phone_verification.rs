@@ -0,0 +1,130 @@
+use leptos::{prelude::*, task::spawn_local};
+use shared::dto::{ConfirmVerificationCodeRequest, SendVerificationCodeRequest};
+
+use crate::api::client::{confirm_verification_code, send_verification_code};
+
+/// Verifies a phone number with a one-time code texted to it
+///
+/// `phone` is the number in E.164 format, or `None` while it is invalid.
+/// `token` receives the verification token, and is cleared again when the
+/// customer changes the number after verifying it.
+#[component]
+pub fn PhoneVerification(
+    phone: Signal<Option<String>>,
+    token: RwSignal<Option<String>>,
+) -> impl IntoView {
+    let (code, set_code) = signal(String::new());
+    let (code_sent_to, set_code_sent_to) = signal(None::<String>);
+    let (verified_phone, set_verified_phone) = signal(None::<String>);
+    let (is_busy, set_is_busy) = signal(false);
+    let (error, set_error) = signal(None::<String>);
+
+    // A token only proves the number it was issued for
+    Effect::new(move |_| {
+        let phone = phone.get();
+        let verified = verified_phone.get_untracked();
+        if verified.is_some() && verified != phone {
+            set_verified_phone.set(None);
+            token.set(None);
+        }
+    });
+
+    let send_code = move |_| {
+        let Some(phone) = phone.get() else {
+            return;
+        };
+        set_error.set(None);
+        set_is_busy.set(true);
+        spawn_local(async move {
+            match send_verification_code(SendVerificationCodeRequest { phone }).await {
+                Ok(response) => {
+                    set_code.set(String::new());
+                    set_code_sent_to.set(Some(response.phone));
+                }
+                Err(err) => set_error.set(Some(err.user_message())),
+            }
+            set_is_busy.set(false);
+        });
+    };
+
+    let confirm_code = move |_| {
+        let Some(phone) = code_sent_to.get() else {
+            return;
+        };
+        set_error.set(None);
+        set_is_busy.set(true);
+        spawn_local(async move {
+            let request = ConfirmVerificationCodeRequest {
+                phone: phone.clone(),
+                code: code.get_untracked(),
+            };
+            match confirm_verification_code(request).await {
+                Ok(response) => {
+                    set_verified_phone.set(Some(phone));
+                    set_code_sent_to.set(None);
+                    token.set(Some(response.token));
+                }
+                Err(err) => set_error.set(Some(err.user_message())),
+            }
+            set_is_busy.set(false);
+        });
+    };
+
+    let is_verified = move || verified_phone.get().is_some() && verified_phone.get() == phone.get();
+    let is_code_sent = move || code_sent_to.get().is_some() && code_sent_to.get() == phone.get();
+
+    view! {
+        <div class="phone-verification">
+            {move || {
+                if is_verified() {
+                    view! { <p class="phone-verified">"✓ Phone number verified"</p> }.into_any()
+                } else if is_code_sent() {
+                    view! {
+                        <div class="phone-verification-row">
+                            <input
+                                type="text"
+                                inputmode="numeric"
+                                autocomplete="one-time-code"
+                                maxlength="6"
+                                placeholder="6-digit code"
+                                aria-label="Verification code"
+                                prop:value=move || code.get()
+                                on:input=move |ev| set_code.set(event_target_value(&ev))
+                            />
+                            <button
+                                type="button"
+                                class="phone-verification-button"
+                                disabled=move || is_busy.get() || code.get().trim().len() != 6
+                                on:click=confirm_code
+                            >
+                                "Confirm"
+                            </button>
+                            <button
+                                type="button"
+                                class="admin-link-button"
+                                disabled=move || is_busy.get()
+                                on:click=send_code
+                            >
+                                "Send a new code"
+                            </button>
+                        </div>
+                    }
+                        .into_any()
+                } else {
+                    view! {
+                        <button
+                            type="button"
+                            class="phone-verification-button"
+                            disabled=move || is_busy.get() || phone.get().is_none()
+                            on:click=send_code
+                        >
+                            "Text me a verification code"
+                        </button>
+                    }
+                        .into_any()
+                }
+            }}
+            {move || error.get().map(|error| view! { <p class="field-error">{error}</p> })}
+        </div>
+    }
+}
@@ -1,8 +1,11 @@
 use leptos::prelude::*;
-use shared::models::{Pizza, PizzaSize};
+use shared::models::{ImageFormat, Pizza, PizzaSize};
 
 use crate::utils::format::format_currency;
 
+/// Width of a card's image at each `.pizza-grid` breakpoint
+const IMAGE_SIZES: &str = "(min-width: 1024px) 33vw, (min-width: 768px) 50vw, 100vw";
+
 #[component]
 pub fn PizzaCard(
     pizza: Pizza,
@@ -35,9 +38,22 @@ pub fn PizzaCard(
     view! {
         <div class="pizza-card">
             <div class="pizza-image">
-                {match pizza.image_url.as_ref() {
-                    Some(url) => view! { <img src={url.clone()} alt={pizza.name.clone()} /> }.into_any(),
-                    None => view! { <div class="placeholder-image">"🍕"</div> }.into_any()
+                {match (pizza.image.as_ref(), pizza.image_url.as_ref()) {
+                    // Uploaded photos come in several widths, the browser picks one for the grid column
+                    (Some(image), _) => view! {
+                        <picture>
+                            <source type="image/webp" srcset={image.srcset(ImageFormat::Webp)} sizes=IMAGE_SIZES />
+                            <img
+                                src={image.largest(ImageFormat::Jpeg).map(|variant| variant.url.clone())}
+                                srcset={image.srcset(ImageFormat::Jpeg)}
+                                sizes=IMAGE_SIZES
+                                alt={pizza.name.clone()}
+                                loading="lazy"
+                            />
+                        </picture>
+                    }.into_any(),
+                    (None, Some(url)) => view! { <img src={url.clone()} alt={pizza.name.clone()} loading="lazy" /> }.into_any(),
+                    (None, None) => view! { <div class="placeholder-image">"🍕"</div> }.into_any()
                 }}
             </div>
 
@@ -89,7 +105,7 @@ pub fn PizzaCard(
                             prop:value=move || quantity.get()
                             on:input=move |ev| {
                                 if let Ok(val) = event_target_value(&ev).parse::<u32>() {
-                                    set_quantity.set(val.max(1).min(10));
+                                    set_quantity.set(val.clamp(1, 10));
                                 }
                             }
                         />
@@ -0,0 +1,232 @@
+use leptos::{ev, prelude::*, task::spawn_local};
+use shared::dto::{CustomerHistoryResponse, CustomerLookupRequest, GetPizzasResponse};
+use shared::models::{Order, OrderItemType};
+use shared::validation::normalize_phone_number;
+
+use crate::api::client::lookup_customer;
+use crate::components::phone_verification::PhoneVerification;
+use crate::components::toast::{Toast, ToastContainer, ToastType};
+use crate::state::cart::use_cart;
+use crate::utils::format::{format_currency, format_date};
+use crate::utils::phone::phone_region;
+
+/// Previous orders of a returning customer, with one-click reorder
+///
+/// The customer proves the phone number is theirs with a texted code, and
+/// names any earlier order, as printed on the confirmation page and receipt.
+#[component]
+pub fn ReturningCustomer(menu: GetPizzasResponse) -> impl IntoView {
+    let cart = use_cart();
+
+    let (is_open, set_is_open) = signal(false);
+    let (phone, set_phone) = signal(String::new());
+    let (order_number, set_order_number) = signal(String::new());
+    let verification_token = RwSignal::new(None::<String>);
+    let (is_loading, set_is_loading) = signal(false);
+    let (lookup_error, set_lookup_error) = signal(None::<String>);
+    let history = RwSignal::new(None::<CustomerHistoryResponse>);
+    let toast = RwSignal::new(None::<(String, ToastType)>);
+
+    let menu = StoredValue::new(menu);
+
+    let find_orders = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        if phone.get().trim().is_empty() || order_number.get().trim().is_empty() {
+            set_lookup_error.set(Some(
+                "Enter your phone number and the number of a previous order.".to_string(),
+            ));
+            return;
+        }
+
+        if let Err(error) = normalize_phone_number(&phone.get(), phone_region()) {
+            set_lookup_error.set(Some(error));
+            return;
+        }
+        if verification_token.get().is_none() {
+            set_lookup_error.set(Some(
+                "Verify your phone number with the code we text you.".to_string(),
+            ));
+            return;
+        }
+
+        let request = CustomerLookupRequest {
+            phone: phone.get(),
+            order_number: order_number.get(),
+            verification_token: verification_token.get(),
+        };
+        set_lookup_error.set(None);
+        set_is_loading.set(true);
+        spawn_local(async move {
+            match lookup_customer(request).await {
+                Ok(response) => history.set(Some(response)),
+                Err(err) if err.is_not_found() => set_lookup_error.set(Some(
+                    "We couldn't find orders for this phone and order number.".to_string(),
+                )),
+                Err(err) => set_lookup_error.set(Some(err.user_message())),
+            }
+            set_is_loading.set(false);
+        });
+    };
+
+    let order_again = move |order: &Order| {
+        let unavailable = menu.with_value(|menu| cart.add_order_items(&order.items, menu));
+        let message = if unavailable.is_empty() {
+            (
+                format!("Order {} added to your cart.", order.order_number),
+                ToastType::Success,
+            )
+        } else {
+            (
+                format!(
+                    "Added to your cart, except what is no longer available: {}.",
+                    unavailable.join(", ")
+                ),
+                ToastType::Warning,
+            )
+        };
+        toast.set(Some(message));
+    };
+
+    view! {
+        <section class="returning-customer">
+            {move || {
+                toast
+                    .get()
+                    .map(|(message, toast_type)| {
+                        view! {
+                            <ToastContainer>
+                                <Toast message toast_type />
+                            </ToastContainer>
+                        }
+                    })
+            }}
+
+            <Show
+                when=move || is_open.get()
+                fallback=move || {
+                    view! {
+                        <button
+                            class="returning-customer-toggle"
+                            on:click=move |_| set_is_open.set(true)
+                        >
+                            "Ordered before? Find your previous orders"
+                        </button>
+                    }
+                }
+            >
+                {move || match history.get() {
+                    Some(response) => {
+                        view! {
+                            <div class="returning-customer-history">
+                                <h3>{format!("Welcome back, {}!", response.customer.name)}</h3>
+                                <ul class="previous-orders">
+                                    {response
+                                        .orders
+                                        .into_iter()
+                                        .map(|order| {
+                                            let summary = menu
+                                                .with_value(|menu| order_summary(&order, menu));
+                                            view! {
+                                                <li class="previous-order">
+                                                    <div class="previous-order-info">
+                                                        <strong>{order.order_number.clone()}</strong>
+                                                        <span class="previous-order-date">
+                                                            {format_date(&order.created_at)}
+                                                        </span>
+                                                        <span class="previous-order-items">{summary}</span>
+                                                    </div>
+                                                    <span class="previous-order-total">
+                                                        {format_currency(order.total_amount)}
+                                                    </span>
+                                                    <button
+                                                        class="previous-order-button"
+                                                        on:click=move |_| order_again(&order)
+                                                    >
+                                                        "Order again"
+                                                    </button>
+                                                </li>
+                                            }
+                                        })
+                                        .collect::<Vec<_>>()}
+                                </ul>
+                            </div>
+                        }
+                            .into_any()
+                    }
+                    None => {
+                        view! {
+                            <form class="returning-customer-form" on:submit=find_orders>
+                                <div class="form-group">
+                                    <label for="returning-phone">"Phone number"</label>
+                                    <input
+                                        type="tel"
+                                        id="returning-phone"
+                                        autocomplete="tel"
+                                        prop:value=move || phone.get()
+                                        on:input=move |ev| set_phone.set(event_target_value(&ev))
+                                    />
+                                    <PhoneVerification
+                                        phone=Signal::derive(move || {
+                                            normalize_phone_number(&phone.get(), phone_region()).ok()
+                                        })
+                                        token=verification_token
+                                    />
+                                </div>
+                                <div class="form-group">
+                                    <label for="returning-order-number">"Previous order number"</label>
+                                    <input
+                                        type="text"
+                                        id="returning-order-number"
+                                        placeholder="RP-20260208-001"
+                                        prop:value=move || order_number.get()
+                                        on:input=move |ev| set_order_number.set(event_target_value(&ev))
+                                    />
+                                </div>
+                                {move || {
+                                    lookup_error
+                                        .get()
+                                        .map(|error| view! { <p class="error-message">{error}</p> })
+                                }}
+                                <button
+                                    type="submit"
+                                    class="returning-customer-submit"
+                                    disabled=move || is_loading.get()
+                                >
+                                    {move || if is_loading.get() { "Searching..." } else { "Find my orders" }}
+                                </button>
+                            </form>
+                        }
+                            .into_any()
+                    }
+                }}
+            </Show>
+        </section>
+    }
+}
+
+/// Short description of the items of an order, e.g. `2× Margherita - Medium`
+fn order_summary(order: &Order, menu: &GetPizzasResponse) -> String {
+    order
+        .items
+        .iter()
+        .map(|item| {
+            let name = item
+                .ordered_name()
+                .unwrap_or_else(|| match &item.item_type {
+                    OrderItemType::StandardPizza { pizza_id, size } => {
+                        let name = menu
+                            .pizzas
+                            .iter()
+                            .find(|pizza| pizza.id == *pizza_id)
+                            .map_or(pizza_id.as_str(), |pizza| pizza.name.as_str());
+                        format!("{} - {}", name, size.as_str())
+                    }
+                    OrderItemType::CustomPizza { custom } => {
+                        format!("Custom Pizza - {}", custom.size.as_str())
+                    }
+                });
+            format!("{}× {}", item.quantity, name)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
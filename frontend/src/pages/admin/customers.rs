@@ -0,0 +1,218 @@
+use leptos::prelude::*;
+use leptos_router::components::A;
+use shared::models::Customer;
+
+use super::status_class;
+use crate::api::admin::{fetch_customer, fetch_customers};
+use crate::components::{
+    admin_nav::AdminNav, error_display::ErrorDisplay, layout::PageLayout, loading::LoadingSpinner,
+};
+use crate::state::admin::use_admin_session;
+use crate::utils::format::{format_currency, format_datetime};
+
+#[component]
+pub fn AdminCustomersPage() -> impl IntoView {
+    let session = use_admin_session();
+
+    let search = RwSignal::new(String::new());
+    let selected = RwSignal::new(None::<String>);
+
+    let customers = LocalResource::new(move || {
+        let search = search.get();
+        let token = session.token();
+        async move {
+            let token = token?;
+            let result = fetch_customers(&token, &search).await;
+            if let Err(err) = &result {
+                session.handle_error(err);
+            }
+            Some(result)
+        }
+    });
+
+    let history = LocalResource::new(move || {
+        let selected = selected.get();
+        let token = session.token();
+        async move {
+            let (token, customer_id) = (token?, selected?);
+            let result = fetch_customer(&token, &customer_id).await;
+            if let Err(err) = &result {
+                session.handle_error(err);
+            }
+            Some(result)
+        }
+    });
+
+    let error_message = RwSignal::new(None::<String>);
+    let history_error = RwSignal::new(None::<String>);
+
+    view! {
+        <PageLayout>
+            <div class="admin-page">
+                <AdminNav />
+                <h2>"Customers"</h2>
+
+                <div class="admin-filters">
+                    <div class="form-group admin-search">
+                        <label for="customers-search">"Search"</label>
+                        <input
+                            type="search"
+                            id="customers-search"
+                            placeholder="Name or phone"
+                            prop:value=move || search.get()
+                            on:change=move |ev| search.set(event_target_value(&ev))
+                        />
+                    </div>
+                </div>
+
+                <Suspense fallback=move || view! { <LoadingSpinner /> }>
+                    {move || {
+                        customers
+                            .get()
+                            .flatten()
+                            .map(|result| match result {
+                                Ok(response) if response.customers.is_empty() => {
+                                    view! {
+                                        <div class="empty-state">
+                                            <h3>"No customers found"</h3>
+                                            <p>"Customers appear here after their first order."</p>
+                                        </div>
+                                    }
+                                        .into_any()
+                                }
+                                Ok(response) => {
+                                    view! {
+                                        <table class="admin-table">
+                                            <thead>
+                                                <tr>
+                                                    <th>"Name"</th>
+                                                    <th>"Phone"</th>
+                                                    <th class="numeric">"Orders"</th>
+                                                    <th>"Last order"</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {response
+                                                    .customers
+                                                    .into_iter()
+                                                    .map(|customer| customer_row(customer, selected))
+                                                    .collect::<Vec<_>>()}
+                                            </tbody>
+                                        </table>
+                                    }
+                                        .into_any()
+                                }
+                                Err(err) => {
+                                    error_message.set(Some(err.user_message()));
+                                    view! {
+                                        <ErrorDisplay error=Signal::derive(move || error_message.get()) />
+                                    }
+                                        .into_any()
+                                }
+                            })
+                    }}
+                </Suspense>
+
+                <Suspense fallback=move || view! { <LoadingSpinner /> }>
+                    {move || {
+                        history
+                            .get()
+                            .flatten()
+                            .map(|result| match result {
+                                Ok(history) => {
+                                    view! {
+                                        <section class="admin-customer-history">
+                                            <h3>
+                                                {history.customer.name}
+                                                <span class="admin-muted">{history.customer.phone}</span>
+                                            </h3>
+                                            {if history.orders.is_empty() {
+                                                view! { <p>"No linked orders yet."</p> }.into_any()
+                                            } else {
+                                                view! {
+                                                    <table class="admin-table">
+                                                        <thead>
+                                                            <tr>
+                                                                <th>"Order"</th>
+                                                                <th>"Placed"</th>
+                                                                <th>"Status"</th>
+                                                                <th class="numeric">"Total"</th>
+                                                            </tr>
+                                                        </thead>
+                                                        <tbody>
+                                                            {history
+                                                                .orders
+                                                                .into_iter()
+                                                                .map(|order| {
+                                                                    view! {
+                                                                        <tr>
+                                                                            <td>
+                                                                                <A href=format!("/admin/orders/{}", order.id)>
+                                                                                    {order.order_number}
+                                                                                </A>
+                                                                            </td>
+                                                                            <td>{format_datetime(&order.created_at)}</td>
+                                                                            <td>
+                                                                                <span class=status_class(order.status)>
+                                                                                    {order.status.as_str()}
+                                                                                </span>
+                                                                            </td>
+                                                                            <td class="numeric">
+                                                                                {format_currency(order.total_amount)}
+                                                                            </td>
+                                                                        </tr>
+                                                                    }
+                                                                })
+                                                                .collect::<Vec<_>>()}
+                                                        </tbody>
+                                                    </table>
+                                                }
+                                                    .into_any()
+                                            }}
+                                        </section>
+                                    }
+                                        .into_any()
+                                }
+                                Err(err) => {
+                                    history_error.set(Some(err.user_message()));
+                                    view! {
+                                        <ErrorDisplay error=Signal::derive(move || history_error.get()) />
+                                    }
+                                        .into_any()
+                                }
+                            })
+                    }}
+                </Suspense>
+            </div>
+        </PageLayout>
+    }
+}
+
+fn customer_row(customer: Customer, selected: RwSignal<Option<String>>) -> impl IntoView {
+    let id = customer.id.clone();
+    let is_selected = {
+        let id = id.clone();
+        move || selected.with(|selected| selected.as_deref() == Some(id.as_str()))
+    };
+
+    view! {
+        <tr class=("admin-row-selected", is_selected)>
+            <td>
+                <button
+                    class="admin-link-button"
+                    on:click=move |_| selected.set(Some(id.clone()))
+                >
+                    {customer.name}
+                </button>
+            </td>
+            <td>{customer.phone}</td>
+            <td class="numeric">{customer.order_count}</td>
+            <td>
+                {customer
+                    .last_order_at
+                    .map(|at| format_datetime(&at))
+                    .unwrap_or_default()}
+            </td>
+        </tr>
+    }
+}
@@ -0,0 +1,85 @@
+use chrono::Utc;
+use leptos::{ev, prelude::*, task::spawn_local};
+use leptos_router::hooks::use_navigate;
+
+use crate::api::admin::fetch_sales_summary;
+use crate::components::{error_display::ErrorDisplay, layout::PageLayout};
+use crate::state::admin::use_admin_session;
+
+#[component]
+pub fn AdminLoginPage() -> impl IntoView {
+    let session = use_admin_session();
+    let navigate = use_navigate();
+
+    let (token, set_token) = signal(String::new());
+    let (is_submitting, set_is_submitting) = signal(false);
+    let (error, set_error) = signal(None::<String>);
+
+    // Already logged in, go straight to the dashboard
+    Effect::new({
+        let navigate = navigate.clone();
+        move |_| {
+            if session.is_logged_in() {
+                navigate("/admin", Default::default());
+            }
+        }
+    });
+
+    let submit = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+
+        let token = token.get().trim().to_string();
+        if token.is_empty() {
+            set_error.set(Some("Please enter the admin token.".to_string()));
+            return;
+        }
+
+        set_is_submitting.set(true);
+        set_error.set(None);
+        let navigate = navigate.clone();
+
+        spawn_local(async move {
+            // Any admin endpoint tells whether the token is accepted
+            let today = Utc::now().format("%Y-%m-%d").to_string();
+            match fetch_sales_summary(&token, &today, &today).await {
+                Ok(_) => {
+                    session.login(token);
+                    navigate("/admin", Default::default());
+                }
+                Err(err) if err.is_unauthorized() => {
+                    set_error.set(Some("Invalid admin token.".to_string()));
+                }
+                Err(err) => set_error.set(Some(err.user_message())),
+            }
+            set_is_submitting.set(false);
+        });
+    };
+
+    view! {
+        <PageLayout>
+            <div class="admin-page admin-login-page">
+                <h2>"Admin Login"</h2>
+                <ErrorDisplay error=Signal::derive(move || error.get()) />
+                <form class="admin-login-form" on:submit=submit>
+                    <div class="form-group">
+                        <label for="admin-token">"Admin Token"</label>
+                        <input
+                            type="password"
+                            id="admin-token"
+                            autocomplete="current-password"
+                            prop:value=move || token.get()
+                            on:input=move |ev| set_token.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <button
+                        type="submit"
+                        class="admin-button primary"
+                        disabled=move || is_submitting.get()
+                    >
+                        {move || if is_submitting.get() { "Checking..." } else { "Log in" }}
+                    </button>
+                </form>
+            </div>
+        </PageLayout>
+    }
+}
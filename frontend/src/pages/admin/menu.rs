@@ -0,0 +1,238 @@
+use leptos::{prelude::*, task::spawn_local};
+use shared::dto::UpdatePizzaRequest;
+use shared::models::{Pizza, PizzaPrice};
+
+use crate::api::admin::{fetch_menu, update_pizza};
+use crate::components::{
+    admin_nav::AdminNav,
+    error_display::ErrorDisplay,
+    layout::PageLayout,
+    loading::LoadingSpinner,
+    toast::{Toast, ToastContainer, ToastType},
+};
+use crate::state::admin::use_admin_session;
+
+#[component]
+pub fn AdminMenuPage() -> impl IntoView {
+    let session = use_admin_session();
+
+    let menu = LocalResource::new(move || {
+        let token = session.token();
+        async move {
+            let token = token?;
+            let result = fetch_menu(&token).await;
+            if let Err(err) = &result {
+                session.handle_error(err);
+            }
+            Some(result)
+        }
+    });
+
+    let toast = RwSignal::new(None::<(String, ToastType)>);
+    let error_message = RwSignal::new(None::<String>);
+
+    view! {
+        <PageLayout>
+            <div class="admin-page">
+                <AdminNav />
+                <h2>"Menu"</h2>
+                <p class="admin-muted">
+                    "Unavailable pizzas are hidden from the menu but kept on past orders."
+                </p>
+
+                {move || {
+                    toast
+                        .get()
+                        .map(|(message, toast_type)| {
+                            view! {
+                                <ToastContainer>
+                                    <Toast message=message toast_type=toast_type />
+                                </ToastContainer>
+                            }
+                        })
+                }}
+
+                <Suspense fallback=move || view! { <LoadingSpinner /> }>
+                    {move || {
+                        menu
+                            .get()
+                            .flatten()
+                            .map(|result| match result {
+                                Ok(response) => {
+                                    view! {
+                                        <div class="admin-menu-list">
+                                            {response
+                                                .pizzas
+                                                .into_iter()
+                                                .map(|pizza| view! { <PizzaEditor pizza=pizza toast=toast /> })
+                                                .collect::<Vec<_>>()}
+                                        </div>
+                                    }
+                                        .into_any()
+                                }
+                                Err(err) => {
+                                    error_message.set(Some(err.user_message()));
+                                    view! {
+                                        <ErrorDisplay error=Signal::derive(move || error_message.get()) />
+                                    }
+                                        .into_any()
+                                }
+                            })
+                    }}
+                </Suspense>
+            </div>
+        </PageLayout>
+    }
+}
+
+/// Edit form of a single menu pizza
+#[component]
+fn PizzaEditor(pizza: Pizza, toast: RwSignal<Option<(String, ToastType)>>) -> impl IntoView {
+    use shared::validation::{validate_pizza_name, validate_pizza_price};
+
+    let session = use_admin_session();
+
+    let pizza_id = pizza.id.clone();
+    let (name, set_name) = signal(pizza.name);
+    let (description, set_description) = signal(pizza.description);
+    let (small, set_small) = signal(format!("{:.2}", pizza.price.small));
+    let (medium, set_medium) = signal(format!("{:.2}", pizza.price.medium));
+    let (large, set_large) = signal(format!("{:.2}", pizza.price.large));
+    let (is_available, set_is_available) = signal(pizza.is_available);
+    let (is_saving, set_is_saving) = signal(false);
+    let (errors, set_errors) = signal(Vec::<String>::new());
+
+    let save = move |_| {
+        let parse = |value: String, size: &str| {
+            value
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| format!("{} price must be a number.", size))
+        };
+
+        let mut validation_errors = Vec::new();
+        let price = match (
+            parse(small.get(), "Small"),
+            parse(medium.get(), "Medium"),
+            parse(large.get(), "Large"),
+        ) {
+            (Ok(small), Ok(medium), Ok(large)) => Some(PizzaPrice {
+                small,
+                medium,
+                large,
+            }),
+            (small, medium, large) => {
+                validation_errors.extend(
+                    [small.err(), medium.err(), large.err()]
+                        .into_iter()
+                        .flatten(),
+                );
+                None
+            }
+        };
+        if let Err(e) = validate_pizza_name(&name.get()) {
+            validation_errors.push(e);
+        }
+        if let Some(Err(e)) = price.as_ref().map(validate_pizza_price) {
+            validation_errors.push(e);
+        }
+
+        set_errors.set(validation_errors.clone());
+        if !validation_errors.is_empty() {
+            return;
+        }
+        let Some(token) = session.token() else {
+            return;
+        };
+
+        let request = UpdatePizzaRequest {
+            name: Some(name.get()),
+            description: Some(description.get()),
+            price,
+            is_available: Some(is_available.get()),
+        };
+        let pizza_id = pizza_id.clone();
+
+        set_is_saving.set(true);
+        spawn_local(async move {
+            match update_pizza(&token, &pizza_id, &request).await {
+                Ok(saved) => {
+                    toast.set(Some((format!("{} saved", saved.name), ToastType::Success)));
+                }
+                Err(err) => {
+                    session.handle_error(&err);
+                    toast.set(Some((err.user_message(), ToastType::Error)));
+                }
+            }
+            set_is_saving.set(false);
+        });
+    };
+
+    let price_input =
+        move |label: &'static str, value: ReadSignal<String>, set_value: WriteSignal<String>| {
+            view! {
+                <label class="admin-price-input">
+                    {label}
+                    <input
+                        type="number"
+                        min="0"
+                        step="0.01"
+                        prop:value=move || value.get()
+                        on:input=move |ev| set_value.set(event_target_value(&ev))
+                    />
+                </label>
+            }
+        };
+
+    view! {
+        <div class="admin-pizza-editor" class:unavailable=move || !is_available.get()>
+            <div class="form-group">
+                <label>"Name"</label>
+                <input
+                    type="text"
+                    prop:value=move || name.get()
+                    on:input=move |ev| set_name.set(event_target_value(&ev))
+                />
+            </div>
+            <div class="form-group">
+                <label>"Description"</label>
+                <textarea
+                    rows="2"
+                    prop:value=move || description.get()
+                    on:input=move |ev| set_description.set(event_target_value(&ev))
+                ></textarea>
+            </div>
+            <div class="admin-prices">
+                {price_input("Small", small, set_small)}
+                {price_input("Medium", medium, set_medium)}
+                {price_input("Large", large, set_large)}
+            </div>
+            <label class="admin-checkbox">
+                <input
+                    type="checkbox"
+                    prop:checked=move || is_available.get()
+                    on:change=move |ev| set_is_available.set(event_target_checked(&ev))
+                />
+                "Available"
+            </label>
+            <Show when=move || !errors.get().is_empty()>
+                <div class="validation-errors">
+                    {move || {
+                        errors
+                            .get()
+                            .into_iter()
+                            .map(|err| view! { <div class="error-item">{err}</div> })
+                            .collect::<Vec<_>>()
+                    }}
+                </div>
+            </Show>
+            <button
+                class="admin-button primary"
+                disabled=move || is_saving.get()
+                on:click=save
+            >
+                {move || if is_saving.get() { "Saving..." } else { "Save" }}
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,33 @@
+pub mod customers;
+pub mod login;
+pub mod menu;
+pub mod order_detail;
+pub mod orders;
+pub mod sales;
+pub mod webhooks;
+
+use shared::models::{OrderItem, OrderItemType, OrderStatus};
+use std::collections::HashMap;
+
+/// Display name of an order item as ordered, or with current menu names for older orders
+fn item_name(item: &OrderItem, pizza_names: &HashMap<String, String>) -> String {
+    if let Some(name) = item.ordered_name() {
+        return name;
+    }
+
+    match &item.item_type {
+        OrderItemType::StandardPizza { pizza_id, size } => format!(
+            "{} - {}",
+            pizza_names.get(pizza_id).unwrap_or(pizza_id),
+            size.as_str()
+        ),
+        OrderItemType::CustomPizza { custom } => {
+            format!("Custom Pizza - {}", custom.size.as_str())
+        }
+    }
+}
+
+/// CSS class of a status badge
+fn status_class(status: OrderStatus) -> String {
+    format!("status-badge status-{:?}", status).to_lowercase()
+}
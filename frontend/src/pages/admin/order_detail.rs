@@ -0,0 +1,257 @@
+use leptos::{prelude::*, task::spawn_local};
+use leptos_router::{components::A, hooks::use_params, params::Params};
+use shared::models::{Order, OrderStatus};
+use std::collections::HashMap;
+
+use super::{item_name, status_class};
+use crate::api::admin::{fetch_menu, update_order_status};
+use crate::api::client::{fetch_order_by_id, API_BASE_URL};
+use crate::components::{
+    admin_nav::AdminNav,
+    error_display::ErrorDisplay,
+    layout::PageLayout,
+    loading::LoadingSpinner,
+    toast::{Toast, ToastContainer, ToastType},
+};
+use crate::state::admin::use_admin_session;
+use crate::utils::format::{format_currency, format_datetime};
+
+#[derive(Params, PartialEq, Clone)]
+struct AdminOrderParams {
+    id: Option<String>,
+}
+
+/// Statuses an order can be moved to from its current one
+///
+/// Orders only move forward; cancelling is possible until pickup.
+fn next_statuses(current: OrderStatus) -> Vec<OrderStatus> {
+    if matches!(current, OrderStatus::PickedUp | OrderStatus::Cancelled) {
+        return Vec::new();
+    }
+    OrderStatus::ALL
+        .into_iter()
+        .skip_while(|status| *status != current)
+        .skip(1)
+        .collect()
+}
+
+#[component]
+pub fn AdminOrderDetailPage() -> impl IntoView {
+    let session = use_admin_session();
+    let params = use_params::<AdminOrderParams>();
+
+    let order_id = move || params.get().ok().and_then(|p| p.id).unwrap_or_default();
+
+    let order = LocalResource::new(move || {
+        let id = order_id();
+        async move { fetch_order_by_id(&id).await }
+    });
+
+    // Menu names for the order items
+    let pizza_names = LocalResource::new(move || {
+        let token = session.token();
+        async move {
+            let menu = fetch_menu(&token?).await.ok()?;
+            Some(
+                menu.pizzas
+                    .into_iter()
+                    .map(|pizza| (pizza.id, pizza.name))
+                    .collect::<HashMap<_, _>>(),
+            )
+        }
+    });
+
+    let toast = RwSignal::new(None::<(String, ToastType)>);
+    let (is_updating, set_is_updating) = signal(false);
+    let error_message = RwSignal::new(None::<String>);
+
+    let change_status = move |target: Order, status: OrderStatus| {
+        if status == OrderStatus::Cancelled {
+            let question = format!(
+                "Cancel order {}? Online payments are refunded automatically.",
+                target.order_number
+            );
+            if !window().confirm_with_message(&question).unwrap_or(false) {
+                return;
+            }
+        }
+        let Some(token) = session.token() else {
+            return;
+        };
+
+        set_is_updating.set(true);
+        spawn_local(async move {
+            match update_order_status(&token, &target.id, status).await {
+                Ok(updated) => {
+                    toast.set(Some((
+                        format!("Order {} is now {}", updated.order_number, status.as_str()),
+                        ToastType::Success,
+                    )));
+                    order.refetch();
+                }
+                Err(err) => {
+                    session.handle_error(&err);
+                    toast.set(Some((err.user_message(), ToastType::Error)));
+                }
+            }
+            set_is_updating.set(false);
+        });
+    };
+
+    view! {
+        <PageLayout>
+            <div class="admin-page">
+                <AdminNav />
+                <A href="/admin" attr:class="admin-back-link">"← Back to orders"</A>
+
+                {move || {
+                    toast
+                        .get()
+                        .map(|(message, toast_type)| {
+                            view! {
+                                <ToastContainer>
+                                    <Toast message=message toast_type=toast_type />
+                                </ToastContainer>
+                            }
+                        })
+                }}
+
+                <Suspense fallback=move || view! { <LoadingSpinner /> }>
+                    {move || {
+                        order
+                            .get()
+                            .map(|result| match result {
+                                Ok(order_data) => {
+                                    let names = pizza_names.get().flatten().unwrap_or_default();
+                                    let receipt_url = |copy: &str| {
+                                        format!(
+                                            "{}/orders/{}/receipt?format=pdf&copy={}",
+                                            API_BASE_URL,
+                                            order_data.id,
+                                            copy,
+                                        )
+                                    };
+                                    view! {
+                                        <div class="admin-order-detail">
+                                            <div class="admin-order-header">
+                                                <h2>"Order " {order_data.order_number.clone()}</h2>
+                                                <span class=status_class(order_data.status)>
+                                                    {order_data.status.as_str()}
+                                                </span>
+                                            </div>
+
+                                            <div class="detail-section">
+                                                <h3>"Customer"</h3>
+                                                <p>{order_data.customer.name.clone()}</p>
+                                                <p>{order_data.customer.phone.clone()}</p>
+                                            </div>
+
+                                            <div class="detail-section">
+                                                <h3>"Times"</h3>
+                                                <p>"Placed: " {format_datetime(&order_data.created_at)}</p>
+                                                <p>"Pickup: " {format_datetime(&order_data.pickup_time)}</p>
+                                            </div>
+
+                                            <div class="detail-section">
+                                                <h3>"Items"</h3>
+                                                <div class="order-items-list">
+                                                    {order_data
+                                                        .items
+                                                        .iter()
+                                                        .map(|item| {
+                                                            view! {
+                                                                <div class="order-item-row">
+                                                                    <span class="item-name">
+                                                                        {item_name(item, &names)}
+                                                                    </span>
+                                                                    <span class="item-quantity">"x " {item.quantity}</span>
+                                                                    <span class="item-price">
+                                                                        {format_currency(item.subtotal)}
+                                                                    </span>
+                                                                </div>
+                                                            }
+                                                        })
+                                                        .collect::<Vec<_>>()}
+                                                </div>
+                                            </div>
+
+                                            <div class="detail-section">
+                                                <h3>"Payment"</h3>
+                                                <p>
+                                                    {order_data.payment_method.as_str()} ": "
+                                                    {order_data.payment_status.as_str()}
+                                                </p>
+                                                <p>"Total: " {format_currency(order_data.total_amount)}</p>
+                                                {(order_data.refunded_amount > 0.0).then(|| {
+                                                    view! {
+                                                        <p>"Refunded: " {format_currency(order_data.refunded_amount)}</p>
+                                                    }
+                                                })}
+                                            </div>
+
+                                            <div class="detail-section">
+                                                <h3>"Print"</h3>
+                                                <div class="admin-actions">
+                                                    <a class="admin-button" href=receipt_url("customer") target="_blank">
+                                                        "Receipt"
+                                                    </a>
+                                                    <a class="admin-button" href=receipt_url("kitchen") target="_blank">
+                                                        "Kitchen ticket"
+                                                    </a>
+                                                </div>
+                                            </div>
+
+                                            <div class="detail-section">
+                                                <h3>"Change Status"</h3>
+                                                <div class="admin-actions">
+                                                    {
+                                                        let statuses = next_statuses(order_data.status);
+                                                        if statuses.is_empty() {
+                                                            view! {
+                                                                <p class="admin-muted">"This order can no longer change."</p>
+                                                            }
+                                                                .into_any()
+                                                        } else {
+                                                            statuses
+                                                                .into_iter()
+                                                                .map(|status| {
+                                                                    let order_data = order_data.clone();
+                                                                    let class = if status == OrderStatus::Cancelled {
+                                                                        "admin-button danger"
+                                                                    } else {
+                                                                        "admin-button primary"
+                                                                    };
+                                                                    view! {
+                                                                        <button
+                                                                            class=class
+                                                                            disabled=move || is_updating.get()
+                                                                            on:click=move |_| change_status(order_data.clone(), status)
+                                                                        >
+                                                                            {status.as_str()}
+                                                                        </button>
+                                                                    }
+                                                                })
+                                                                .collect::<Vec<_>>()
+                                                                .into_any()
+                                                        }
+                                                    }
+                                                </div>
+                                            </div>
+                                        </div>
+                                    }
+                                        .into_any()
+                                }
+                                Err(err) => {
+                                    error_message.set(Some(err.user_message()));
+                                    view! {
+                                        <ErrorDisplay error=Signal::derive(move || error_message.get()) />
+                                    }
+                                        .into_any()
+                                }
+                            })
+                    }}
+                </Suspense>
+            </div>
+        </PageLayout>
+    }
+}
@@ -0,0 +1,219 @@
+use chrono::Utc;
+use leptos::{prelude::*, task::spawn_local};
+use leptos_router::components::A;
+use shared::models::{Order, OrderStatus};
+
+use super::status_class;
+use crate::api::admin::{fetch_orders, OrderListFilter};
+use crate::components::{
+    admin_nav::AdminNav, error_display::ErrorDisplay, layout::PageLayout, loading::LoadingSpinner,
+};
+use crate::state::admin::use_admin_session;
+use crate::utils::format::{format_currency, format_datetime};
+
+#[component]
+pub fn AdminOrdersPage() -> impl IntoView {
+    let session = use_admin_session();
+
+    // Today's orders by default
+    let today = Utc::now().format("%Y-%m-%d").to_string();
+    let filter = RwSignal::new(OrderListFilter {
+        from: today.clone(),
+        to: today,
+        ..Default::default()
+    });
+
+    // Pages loaded with "Load more" after the first one
+    let more_orders = RwSignal::new(Vec::<Order>::new());
+    let next_cursor = RwSignal::new(None::<String>);
+    let (is_loading_more, set_is_loading_more) = signal(false);
+
+    let orders = LocalResource::new(move || {
+        let filter = filter.get();
+        let token = session.token();
+        async move {
+            let token = token?;
+            let result = fetch_orders(&token, &filter, None).await;
+            match &result {
+                Ok(response) => {
+                    more_orders.set(Vec::new());
+                    next_cursor.set(response.next_cursor.clone());
+                }
+                Err(err) => session.handle_error(err),
+            }
+            Some(result)
+        }
+    });
+
+    let load_more = move |_| {
+        let (Some(token), Some(cursor)) = (session.token(), next_cursor.get()) else {
+            return;
+        };
+        let filter = filter.get_untracked();
+
+        set_is_loading_more.set(true);
+        spawn_local(async move {
+            match fetch_orders(&token, &filter, Some(&cursor)).await {
+                Ok(response) => {
+                    more_orders.update(|orders| orders.extend(response.orders));
+                    next_cursor.set(response.next_cursor);
+                }
+                Err(err) => session.handle_error(&err),
+            }
+            set_is_loading_more.set(false);
+        });
+    };
+
+    let error_message = RwSignal::new(None::<String>);
+
+    view! {
+        <PageLayout>
+            <div class="admin-page">
+                <AdminNav />
+                <h2>"Orders"</h2>
+
+                <div class="admin-filters">
+                    <div class="form-group">
+                        <label for="orders-from">"From"</label>
+                        <input
+                            type="date"
+                            id="orders-from"
+                            prop:value=move || filter.with(|f| f.from.clone())
+                            on:change=move |ev| filter.update(|f| f.from = event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="form-group">
+                        <label for="orders-to">"To"</label>
+                        <input
+                            type="date"
+                            id="orders-to"
+                            prop:value=move || filter.with(|f| f.to.clone())
+                            on:change=move |ev| filter.update(|f| f.to = event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="form-group">
+                        <label for="orders-status">"Status"</label>
+                        <select
+                            id="orders-status"
+                            on:change=move |ev| {
+                                let value = event_target_value(&ev);
+                                filter.update(|f| {
+                                    f.status = OrderStatus::ALL
+                                        .into_iter()
+                                        .find(|status| format!("{:?}", status) == value);
+                                });
+                            }
+                        >
+                            <option value="">"All statuses"</option>
+                            {OrderStatus::ALL
+                                .into_iter()
+                                .map(|status| {
+                                    view! {
+                                        <option value=format!("{:?}", status)>{status.as_str()}</option>
+                                    }
+                                })
+                                .collect::<Vec<_>>()}
+                        </select>
+                    </div>
+                    <div class="form-group admin-search">
+                        <label for="orders-search">"Search"</label>
+                        <input
+                            type="search"
+                            id="orders-search"
+                            placeholder="Order number, name or phone"
+                            prop:value=move || filter.with(|f| f.search.clone())
+                            on:change=move |ev| filter.update(|f| f.search = event_target_value(&ev))
+                        />
+                    </div>
+                </div>
+
+                <Suspense fallback=move || view! { <LoadingSpinner /> }>
+                    {move || {
+                        orders
+                            .get()
+                            .flatten()
+                            .map(|result| match result {
+                                Ok(response) if response.orders.is_empty() => {
+                                    view! {
+                                        <div class="empty-state">
+                                            <h3>"No orders found"</h3>
+                                            <p>"Try another date range or clear the filters."</p>
+                                        </div>
+                                    }
+                                        .into_any()
+                                }
+                                Ok(response) => {
+                                    view! {
+                                        <table class="admin-table">
+                                            <thead>
+                                                <tr>
+                                                    <th>"Order"</th>
+                                                    <th>"Placed"</th>
+                                                    <th>"Customer"</th>
+                                                    <th>"Pickup"</th>
+                                                    <th>"Status"</th>
+                                                    <th>"Payment"</th>
+                                                    <th class="numeric">"Total"</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {move || {
+                                                    response
+                                                        .orders
+                                                        .iter()
+                                                        .cloned()
+                                                        .chain(more_orders.get())
+                                                        .map(order_row)
+                                                        .collect::<Vec<_>>()
+                                                }}
+                                            </tbody>
+                                        </table>
+                                        <Show when=move || next_cursor.get().is_some()>
+                                            <button
+                                                class="admin-button admin-load-more"
+                                                disabled=move || is_loading_more.get()
+                                                on:click=load_more
+                                            >
+                                                {move || {
+                                                    if is_loading_more.get() { "Loading..." } else { "Load more" }
+                                                }}
+                                            </button>
+                                        </Show>
+                                    }
+                                        .into_any()
+                                }
+                                Err(err) => {
+                                    error_message.set(Some(err.user_message()));
+                                    view! {
+                                        <ErrorDisplay error=Signal::derive(move || error_message.get()) />
+                                    }
+                                        .into_any()
+                                }
+                            })
+                    }}
+                </Suspense>
+            </div>
+        </PageLayout>
+    }
+}
+
+fn order_row(order: Order) -> impl IntoView {
+    view! {
+        <tr>
+            <td>
+                <A href=format!("/admin/orders/{}", order.id)>{order.order_number}</A>
+            </td>
+            <td>{format_datetime(&order.created_at)}</td>
+            <td>
+                {order.customer.name}
+                <span class="admin-muted">{order.customer.phone}</span>
+            </td>
+            <td>{format_datetime(&order.pickup_time)}</td>
+            <td>
+                <span class=status_class(order.status)>{order.status.as_str()}</span>
+            </td>
+            <td>{order.payment_status.as_str()}</td>
+            <td class="numeric">{format_currency(order.total_amount)}</td>
+        </tr>
+    }
+}
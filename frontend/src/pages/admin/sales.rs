@@ -0,0 +1,155 @@
+use chrono::Utc;
+use leptos::prelude::*;
+
+use super::status_class;
+use crate::api::admin::{fetch_item_sales, fetch_sales_summary};
+use crate::components::{
+    admin_nav::AdminNav, error_display::ErrorDisplay, layout::PageLayout, loading::LoadingSpinner,
+};
+use crate::state::admin::use_admin_session;
+use crate::utils::format::{format_currency, format_percent};
+
+/// Sales summary of a single day
+#[component]
+pub fn AdminSalesPage() -> impl IntoView {
+    let session = use_admin_session();
+
+    let (date, set_date) = signal(Utc::now().format("%Y-%m-%d").to_string());
+
+    let summary = LocalResource::new(move || {
+        let date = date.get();
+        let token = session.token();
+        async move {
+            let token = token?;
+            let result = fetch_sales_summary(&token, &date, &date).await;
+            if let Err(err) = &result {
+                session.handle_error(err);
+            }
+            Some(result)
+        }
+    });
+
+    let items = LocalResource::new(move || {
+        let date = date.get();
+        let token = session.token();
+        async move { fetch_item_sales(&token?, &date, &date).await.ok() }
+    });
+
+    let error_message = RwSignal::new(None::<String>);
+
+    view! {
+        <PageLayout>
+            <div class="admin-page">
+                <AdminNav />
+                <h2>"Daily Sales"</h2>
+
+                <div class="admin-filters">
+                    <div class="form-group">
+                        <label for="sales-date">"Date"</label>
+                        <input
+                            type="date"
+                            id="sales-date"
+                            prop:value=move || date.get()
+                            on:change=move |ev| set_date.set(event_target_value(&ev))
+                        />
+                    </div>
+                </div>
+
+                <Suspense fallback=move || view! { <LoadingSpinner /> }>
+                    {move || {
+                        summary
+                            .get()
+                            .flatten()
+                            .map(|result| match result {
+                                Ok(summary) => {
+                                    let figures = [
+                                        ("Orders", summary.order_count.to_string()),
+                                        ("Gross revenue", format_currency(summary.gross_revenue)),
+                                        ("Refunds", format_currency(summary.refunded_amount)),
+                                        ("Net revenue", format_currency(summary.net_revenue)),
+                                        ("Average ticket", format_currency(summary.average_ticket)),
+                                        ("Cancellation rate", format_percent(summary.cancellation_rate)),
+                                    ];
+                                    view! {
+                                        <div class="admin-figures">
+                                            {figures
+                                                .into_iter()
+                                                .map(|(label, value)| {
+                                                    view! {
+                                                        <div class="admin-figure">
+                                                            <span class="admin-figure-label">{label}</span>
+                                                            <span class="admin-figure-value">{value}</span>
+                                                        </div>
+                                                    }
+                                                })
+                                                .collect::<Vec<_>>()}
+                                        </div>
+
+                                        <div class="admin-status-counts">
+                                            {summary
+                                                .status_counts
+                                                .into_iter()
+                                                .map(|count| {
+                                                    view! {
+                                                        <span class=status_class(count.status)>
+                                                            {count.status.as_str()} ": " {count.count}
+                                                        </span>
+                                                    }
+                                                })
+                                                .collect::<Vec<_>>()}
+                                        </div>
+                                    }
+                                        .into_any()
+                                }
+                                Err(err) => {
+                                    error_message.set(Some(err.user_message()));
+                                    view! {
+                                        <ErrorDisplay error=Signal::derive(move || error_message.get()) />
+                                    }
+                                        .into_any()
+                                }
+                            })
+                    }}
+
+                    {move || {
+                        items
+                            .get()
+                            .flatten()
+                            .filter(|report| !report.items.is_empty())
+                            .map(|report| {
+                                view! {
+                                    <h3>"Items Sold"</h3>
+                                    <table class="admin-table">
+                                        <thead>
+                                            <tr>
+                                                <th>"Pizza"</th>
+                                                <th>"Size"</th>
+                                                <th class="numeric">"Quantity"</th>
+                                                <th class="numeric">"Revenue"</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {report
+                                                .items
+                                                .into_iter()
+                                                .map(|item| {
+                                                    view! {
+                                                        <tr>
+                                                            <td>{item.name}</td>
+                                                            <td>{item.size.as_str()}</td>
+                                                            <td class="numeric">{item.quantity}</td>
+                                                            <td class="numeric">{format_currency(item.revenue)}</td>
+                                                        </tr>
+                                                    }
+                                                })
+                                                .collect::<Vec<_>>()}
+                                        </tbody>
+                                    </table>
+                                }
+                            })
+                    }}
+                </Suspense>
+            </div>
+        </PageLayout>
+    }
+}
@@ -0,0 +1,441 @@
+use leptos::{prelude::*, task::spawn_local};
+use shared::dto::{CreateWebhookRequest, UpdateWebhookRequest};
+use shared::models::{WebhookDelivery, WebhookDeliveryStatus, WebhookEndpoint, WebhookEvent};
+use shared::validation::{validate_webhook_events, validate_webhook_url};
+
+use crate::api::admin::{
+    create_webhook, delete_webhook, fetch_webhook_deliveries, fetch_webhooks,
+    retry_webhook_delivery, update_webhook,
+};
+use crate::components::{
+    admin_nav::AdminNav,
+    error_display::ErrorDisplay,
+    layout::PageLayout,
+    loading::LoadingSpinner,
+    toast::{Toast, ToastContainer, ToastType},
+};
+use crate::state::admin::use_admin_session;
+use crate::utils::format::format_datetime;
+
+#[component]
+pub fn AdminWebhooksPage() -> impl IntoView {
+    let session = use_admin_session();
+
+    let selected = RwSignal::new(None::<String>);
+    let toast = RwSignal::new(None::<(String, ToastType)>);
+
+    let webhooks = LocalResource::new(move || {
+        let token = session.token();
+        async move {
+            let token = token?;
+            let result = fetch_webhooks(&token).await;
+            if let Err(err) = &result {
+                session.handle_error(err);
+            }
+            Some(result)
+        }
+    });
+
+    let deliveries = LocalResource::new(move || {
+        let selected = selected.get();
+        let token = session.token();
+        async move {
+            let (token, webhook_id) = (token?, selected?);
+            let result = fetch_webhook_deliveries(&token, &webhook_id).await;
+            if let Err(err) = &result {
+                session.handle_error(err);
+            }
+            Some(result)
+        }
+    });
+
+    let error_message = RwSignal::new(None::<String>);
+    let deliveries_error = RwSignal::new(None::<String>);
+
+    let run = move |action: Action| {
+        let Some(token) = session.token() else {
+            return;
+        };
+        spawn_local(async move {
+            let result = match &action {
+                Action::Toggle(webhook) => update_webhook(
+                    &token,
+                    &webhook.id,
+                    &UpdateWebhookRequest {
+                        is_active: Some(!webhook.is_active),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .map(|_| ()),
+                Action::Delete(webhook) => delete_webhook(&token, &webhook.id).await,
+                Action::Retry(delivery) => retry_webhook_delivery(&token, &delivery.id)
+                    .await
+                    .map(|_| ()),
+            };
+            match result {
+                Ok(()) => {
+                    toast.set(Some((action.done_message(), ToastType::Success)));
+                    if let Action::Delete(webhook) = &action
+                        && selected.get_untracked().as_deref() == Some(webhook.id.as_str())
+                    {
+                        selected.set(None);
+                    }
+                    webhooks.refetch();
+                    deliveries.refetch();
+                }
+                Err(err) => {
+                    session.handle_error(&err);
+                    toast.set(Some((err.user_message(), ToastType::Error)));
+                }
+            }
+        });
+    };
+
+    view! {
+        <PageLayout>
+            <div class="admin-page">
+                <AdminNav />
+                <h2>"Webhooks"</h2>
+                <p class="admin-muted">
+                    "Order events are posted as signed JSON to these endpoints. Failed deliveries are retried with increasing delays and end up as dead letters."
+                </p>
+
+                {move || {
+                    toast
+                        .get()
+                        .map(|(message, toast_type)| {
+                            view! {
+                                <ToastContainer>
+                                    <Toast message=message toast_type=toast_type />
+                                </ToastContainer>
+                            }
+                        })
+                }}
+
+                <WebhookForm toast=toast on_created=move || webhooks.refetch() />
+
+                <Suspense fallback=move || view! { <LoadingSpinner /> }>
+                    {move || {
+                        webhooks
+                            .get()
+                            .flatten()
+                            .map(|result| match result {
+                                Ok(response) if response.webhooks.is_empty() => {
+                                    view! {
+                                        <div class="empty-state">
+                                            <h3>"No webhooks yet"</h3>
+                                            <p>"Add the URL of a partner system to send it order events."</p>
+                                        </div>
+                                    }
+                                        .into_any()
+                                }
+                                Ok(response) => {
+                                    view! {
+                                        <table class="admin-table">
+                                            <thead>
+                                                <tr>
+                                                    <th>"Endpoint"</th>
+                                                    <th>"Events"</th>
+                                                    <th>"Signing secret"</th>
+                                                    <th></th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {response
+                                                    .webhooks
+                                                    .into_iter()
+                                                    .map(|webhook| webhook_row(webhook, selected, run))
+                                                    .collect::<Vec<_>>()}
+                                            </tbody>
+                                        </table>
+                                    }
+                                        .into_any()
+                                }
+                                Err(err) => {
+                                    error_message.set(Some(err.user_message()));
+                                    view! {
+                                        <ErrorDisplay error=Signal::derive(move || error_message.get()) />
+                                    }
+                                        .into_any()
+                                }
+                            })
+                    }}
+                </Suspense>
+
+                <Suspense fallback=move || view! { <LoadingSpinner /> }>
+                    {move || {
+                        deliveries
+                            .get()
+                            .flatten()
+                            .map(|result| match result {
+                                Ok(response) => {
+                                    view! {
+                                        <section class="admin-webhook-deliveries">
+                                            <h3>"Recent deliveries"</h3>
+                                            {if response.deliveries.is_empty() {
+                                                view! { <p>"Nothing delivered yet."</p> }.into_any()
+                                            } else {
+                                                view! {
+                                                    <table class="admin-table">
+                                                        <thead>
+                                                            <tr>
+                                                                <th>"Queued"</th>
+                                                                <th>"Event"</th>
+                                                                <th>"Status"</th>
+                                                                <th class="numeric">"Attempts"</th>
+                                                                <th>"Last response"</th>
+                                                                <th></th>
+                                                            </tr>
+                                                        </thead>
+                                                        <tbody>
+                                                            {response
+                                                                .deliveries
+                                                                .into_iter()
+                                                                .map(|delivery| delivery_row(delivery, run))
+                                                                .collect::<Vec<_>>()}
+                                                        </tbody>
+                                                    </table>
+                                                }
+                                                    .into_any()
+                                            }}
+                                        </section>
+                                    }
+                                        .into_any()
+                                }
+                                Err(err) => {
+                                    deliveries_error.set(Some(err.user_message()));
+                                    view! {
+                                        <ErrorDisplay error=Signal::derive(move || deliveries_error.get()) />
+                                    }
+                                        .into_any()
+                                }
+                            })
+                    }}
+                </Suspense>
+            </div>
+        </PageLayout>
+    }
+}
+
+/// Change made from the webhook and delivery tables
+enum Action {
+    Toggle(WebhookEndpoint),
+    Delete(WebhookEndpoint),
+    Retry(WebhookDelivery),
+}
+
+impl Action {
+    fn done_message(&self) -> String {
+        match self {
+            Action::Toggle(webhook) if webhook.is_active => format!("{} disabled", webhook.url),
+            Action::Toggle(webhook) => format!("{} enabled", webhook.url),
+            Action::Delete(webhook) => format!("{} removed", webhook.url),
+            Action::Retry(_) => "Delivery queued again".to_string(),
+        }
+    }
+}
+
+/// Form registering a new endpoint
+#[component]
+fn WebhookForm(
+    toast: RwSignal<Option<(String, ToastType)>>,
+    on_created: impl Fn() + Copy + 'static,
+) -> impl IntoView {
+    let session = use_admin_session();
+
+    let (url, set_url) = signal(String::new());
+    let (description, set_description) = signal(String::new());
+    let events = RwSignal::new(WebhookEvent::ALL.to_vec());
+    let (is_saving, set_is_saving) = signal(false);
+    let (errors, set_errors) = signal(Vec::<String>::new());
+
+    let add = move |_| {
+        let validation_errors: Vec<String> = [
+            validate_webhook_url(&url.get()),
+            validate_webhook_events(&events.get()),
+        ]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect();
+        set_errors.set(validation_errors.clone());
+        if !validation_errors.is_empty() {
+            return;
+        }
+        let Some(token) = session.token() else {
+            return;
+        };
+
+        let request = CreateWebhookRequest {
+            url: url.get().trim().to_string(),
+            events: events.get(),
+            description: Some(description.get()),
+        };
+        set_is_saving.set(true);
+        spawn_local(async move {
+            match create_webhook(&token, &request).await {
+                Ok(webhook) => {
+                    toast.set(Some((format!("{} added", webhook.url), ToastType::Success)));
+                    set_url.set(String::new());
+                    set_description.set(String::new());
+                    on_created();
+                }
+                Err(err) => {
+                    session.handle_error(&err);
+                    toast.set(Some((err.user_message(), ToastType::Error)));
+                }
+            }
+            set_is_saving.set(false);
+        });
+    };
+
+    view! {
+        <div class="admin-webhook-form">
+            <div class="form-group">
+                <label for="webhook-url">"Endpoint URL"</label>
+                <input
+                    type="url"
+                    id="webhook-url"
+                    placeholder="https://pos.example.com/royal-pizza"
+                    prop:value=move || url.get()
+                    on:input=move |ev| set_url.set(event_target_value(&ev))
+                />
+            </div>
+            <div class="form-group">
+                <label for="webhook-description">"Description"</label>
+                <input
+                    type="text"
+                    id="webhook-description"
+                    placeholder="Kitchen POS"
+                    prop:value=move || description.get()
+                    on:input=move |ev| set_description.set(event_target_value(&ev))
+                />
+            </div>
+            <div class="admin-webhook-events">
+                {WebhookEvent::ALL
+                    .into_iter()
+                    .map(|event| {
+                        view! {
+                            <label class="admin-checkbox">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=move || events.with(|events| events.contains(&event))
+                                    on:change=move |ev| {
+                                        let checked = event_target_checked(&ev);
+                                        events
+                                            .update(|events| {
+                                                events.retain(|e| *e != event);
+                                                if checked {
+                                                    events.push(event);
+                                                }
+                                            });
+                                    }
+                                />
+                                <code>{event.as_str()}</code>
+                            </label>
+                        }
+                    })
+                    .collect::<Vec<_>>()}
+            </div>
+            <Show when=move || !errors.get().is_empty()>
+                <div class="validation-errors">
+                    {move || {
+                        errors
+                            .get()
+                            .into_iter()
+                            .map(|err| view! { <div class="error-item">{err}</div> })
+                            .collect::<Vec<_>>()
+                    }}
+                </div>
+            </Show>
+            <button class="admin-button primary" disabled=move || is_saving.get() on:click=add>
+                {move || if is_saving.get() { "Adding..." } else { "Add webhook" }}
+            </button>
+        </div>
+    }
+}
+
+fn webhook_row(
+    webhook: WebhookEndpoint,
+    selected: RwSignal<Option<String>>,
+    run: impl Fn(Action) + Copy + 'static,
+) -> impl IntoView {
+    let id = webhook.id.clone();
+    let is_selected = {
+        let id = id.clone();
+        move || selected.with(|selected| selected.as_deref() == Some(id.as_str()))
+    };
+    let events = webhook
+        .events
+        .iter()
+        .map(WebhookEvent::as_str)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let toggle_label = if webhook.is_active {
+        "Disable"
+    } else {
+        "Enable"
+    };
+    let toggled = webhook.clone();
+    let deleted = webhook.clone();
+
+    view! {
+        <tr class=("admin-row-selected", is_selected) class:admin-row-inactive=!webhook.is_active>
+            <td>
+                <button class="admin-link-button" on:click=move |_| selected.set(Some(id.clone()))>
+                    {webhook.url}
+                </button>
+                {webhook.description.map(|description| view! { <div class="admin-muted">{description}</div> })}
+            </td>
+            <td>{events}</td>
+            <td>
+                <code>{webhook.secret}</code>
+            </td>
+            <td class="admin-actions">
+                <button class="admin-button" on:click=move |_| run(Action::Toggle(toggled.clone()))>
+                    {toggle_label}
+                </button>
+                <button
+                    class="admin-button danger"
+                    on:click=move |_| run(Action::Delete(deleted.clone()))
+                >
+                    "Delete"
+                </button>
+            </td>
+        </tr>
+    }
+}
+
+fn delivery_row(delivery: WebhookDelivery, run: impl Fn(Action) + Copy + 'static) -> impl IntoView {
+    let last_response = match (delivery.last_status_code, &delivery.last_error) {
+        (_, Some(error)) => error.clone(),
+        (Some(status_code), None) => status_code.to_string(),
+        (None, None) => String::new(),
+    };
+    let is_dead_letter = delivery.status == WebhookDeliveryStatus::DeadLetter;
+
+    view! {
+        <tr>
+            <td>{format_datetime(&delivery.created_at)}</td>
+            <td>
+                <code>{delivery.event.as_str()}</code>
+            </td>
+            <td>{delivery.status.as_str()}</td>
+            <td class="numeric">{delivery.attempts}</td>
+            <td class="admin-webhook-response">{last_response}</td>
+            <td>
+                {is_dead_letter
+                    .then(|| {
+                        view! {
+                            <button
+                                class="admin-button"
+                                on:click=move |_| run(Action::Retry(delivery.clone()))
+                            >
+                                "Retry"
+                            </button>
+                        }
+                    })}
+            </td>
+        </tr>
+    }
+}
@@ -1,4 +1,4 @@
-use leptos::prelude::*;
+use leptos::{prelude::*, task::spawn_local};
 use leptos_router::{
     hooks::{use_navigate, use_params},
     params::Params,
@@ -7,7 +7,8 @@ use leptos_router::{
 use crate::api::client::fetch_order_by_id;
 use crate::components::{error_display::ErrorDisplay, layout::PageLayout, loading::LoadingSpinner};
 use crate::state::cart::use_cart;
-use crate::utils::format::{format_currency, format_datetime};
+use crate::utils::format::{format_currency, format_datetime, format_percent};
+use shared::models::{PaymentMethod, PaymentStatus};
 
 #[derive(Params, PartialEq, Clone)]
 struct ConfirmationParams {
@@ -30,6 +31,19 @@ pub fn ConfirmationPage() -> impl IntoView {
         fetch_order_by_id(&id).await
     });
 
+    // Poll while an online payment is still being processed
+    Effect::new(move |_| {
+        let pending = order.get().is_some_and(|result| {
+            result.is_ok_and(|order| order.payment_status == PaymentStatus::Pending)
+        });
+        if pending {
+            spawn_local(async move {
+                gloo_timers::future::TimeoutFuture::new(2000).await;
+                order.refetch();
+            });
+        }
+    });
+
     let error_message = RwSignal::new(None::<String>);
 
     view! {
@@ -37,10 +51,8 @@ pub fn ConfirmationPage() -> impl IntoView {
             <div class="confirmation-page">
                 <Suspense fallback=move || view! { <LoadingSpinner /> }>
                     {
-                        let cart = cart.clone();
                         let navigate = navigate.clone();
                         move || {
-                        let cart = cart.clone();
                         let navigate = navigate.clone();
                         let order_another = move |_| {
                             cart.clear();
@@ -86,17 +98,18 @@ pub fn ConfirmationPage() -> impl IntoView {
                                                             .items
                                                             .iter()
                                                             .map(|item| {
-                                                                let item_name = match &item.item_type {
-                                                                    shared::models::OrderItemType::StandardPizza {
-                                                                        pizza_id,
-                                                                        size,
-                                                                    } => {
-                                                                        format!("Pizza {} - {:?}", pizza_id, size)
-                                                                    }
-                                                                    shared::models::OrderItemType::CustomPizza { custom } => {
-                                                                        format!("Custom Pizza - {:?}", custom.size)
-                                                                    }
-                                                                };
+                                                                // Orders from before names were kept only have the pizza id
+                                                                let item_name = item
+                                                                    .ordered_name()
+                                                                    .unwrap_or_else(|| match &item.item_type {
+                                                                        shared::models::OrderItemType::StandardPizza {
+                                                                            pizza_id,
+                                                                            size,
+                                                                        } => format!("Pizza {} - {}", pizza_id, size.as_str()),
+                                                                        shared::models::OrderItemType::CustomPizza {
+                                                                            custom,
+                                                                        } => format!("Custom Pizza - {}", custom.size.as_str()),
+                                                                    });
                                                                 view! {
                                                                     <div class="order-item-row">
                                                                         <span class="item-name">{item_name}</span>
@@ -120,12 +133,83 @@ pub fn ConfirmationPage() -> impl IntoView {
                                                     </p>
                                                 </div>
 
+                                                {order_data.tax_breakdown.clone().map(|breakdown| {
+                                                    view! {
+                                                        <div class="detail-section tax-section">
+                                                            <h3>"Tax Summary"</h3>
+                                                            <div class="tax-rows">
+                                                                {breakdown
+                                                                    .rates
+                                                                    .iter()
+                                                                    .map(|rate| {
+                                                                        view! {
+                                                                            <div class="tax-row">
+                                                                                <span class="tax-label">
+                                                                                    {format!(
+                                                                                        "{} ({})",
+                                                                                        rate.category.as_str(),
+                                                                                        format_percent(rate.rate),
+                                                                                    )}
+                                                                                </span>
+                                                                                <span>"Net " {format_currency(rate.net)}</span>
+                                                                                <span>"Tax " {format_currency(rate.tax)}</span>
+                                                                                <span>"Gross " {format_currency(rate.gross)}</span>
+                                                                            </div>
+                                                                        }
+                                                                    })
+                                                                    .collect::<Vec<_>>()}
+                                                                <div class="tax-row tax-total-row">
+                                                                    <span class="tax-label">"Total"</span>
+                                                                    <span>"Net " {format_currency(breakdown.net_total)}</span>
+                                                                    <span>"Tax " {format_currency(breakdown.tax_total)}</span>
+                                                                    <span>"Gross " {format_currency(breakdown.gross_total)}</span>
+                                                                </div>
+                                                            </div>
+                                                            <p class="tax-note">
+                                                                {if breakdown.prices_include_tax {
+                                                                    "Prices include tax."
+                                                                } else {
+                                                                    "Tax is added to menu prices."
+                                                                }}
+                                                            </p>
+                                                        </div>
+                                                    }
+                                                })}
+
                                                 <div class="detail-section total-section">
                                                     <h3>"Total Amount"</h3>
                                                     <p class="total-amount">
                                                         {format_currency(order_data.total_amount)}
                                                     </p>
                                                 </div>
+
+                                                <div class="detail-section payment-section">
+                                                    <h3>"Payment"</h3>
+                                                    <p>
+                                                        <strong>{order_data.payment_method.as_str()} ": "</strong>
+                                                        <span class=format!(
+                                                            "payment-status payment-{}",
+                                                            order_data.payment_status.as_str().to_lowercase().replace(' ', "-"),
+                                                        )>
+                                                            {order_data.payment_status.as_str()}
+                                                        </span>
+                                                    </p>
+                                                    <p class="payment-note">
+                                                        {match (order_data.payment_method, order_data.payment_status) {
+                                                            (PaymentMethod::PayAtPickup, _) => {
+                                                                "Please pay when you pick up your order."
+                                                            }
+                                                            (_, PaymentStatus::Pending) => {
+                                                                "Waiting for your payment to be confirmed..."
+                                                            }
+                                                            (_, PaymentStatus::Paid) => "Thank you, your payment was received.",
+                                                            (_, PaymentStatus::PartiallyRefunded | PaymentStatus::Refunded) => {
+                                                                "Your payment has been refunded to your original payment method."
+                                                            }
+                                                            _ => "Your payment did not go through and the order was cancelled.",
+                                                        }}
+                                                    </p>
+                                                </div>
                                             </div>
 
                                             <div class="confirmation-actions">
@@ -4,7 +4,7 @@ use shared::models::{CustomPizza, PizzaSize};
 use crate::api::client::fetch_pizzas;
 use crate::components::{
     custom_pizza_card::CustomPizzaCard, error_display::ErrorDisplay, layout::PageLayout,
-    loading::LoadingSpinner, pizza_card::PizzaCard,
+    loading::LoadingSpinner, pizza_card::PizzaCard, returning_customer::ReturningCustomer,
 };
 use crate::state::cart::{use_cart, CartItemType};
 
@@ -12,34 +12,38 @@ use crate::state::cart::{use_cart, CartItemType};
 pub fn MenuPage() -> impl IntoView {
     let cart = use_cart();
 
-    // Fetch pizzas from API
-    let pizzas = LocalResource::new(|| async move { fetch_pizzas().await });
+    // Fetch menu from API
+    let menu = LocalResource::new(|| async move { fetch_pizzas().await });
 
     // Error state for API failures
     let error_message = RwSignal::new(None::<String>);
 
     // Handle adding standard pizza to cart
     let add_standard_pizza = move |(pizza_id, size, quantity): (String, PizzaSize, u32)| {
-        pizzas.with(|result| {
-            if let Some(Ok(pizza_list)) = result {
-                if let Some(pizza) = pizza_list.iter().find(|p| p.id == pizza_id) {
-                    let unit_price = size.get_price(&pizza.price);
-                    let cart_item_type = CartItemType::StandardPizza {
-                        pizza_id: pizza.id.clone(),
-                        pizza_name: pizza.name.clone(),
-                        size,
-                    };
-                    cart.add_item(cart_item_type, quantity, unit_price);
-                }
+        menu.with(|result| {
+            if let Some(Ok(menu_data)) = result
+                && let Some(pizza) = menu_data.pizzas.iter().find(|p| p.id == pizza_id)
+            {
+                let unit_price = size.get_price(&pizza.price);
+                let cart_item_type = CartItemType::StandardPizza {
+                    pizza_id: pizza.id.clone(),
+                    pizza_name: pizza.name.clone(),
+                    size,
+                };
+                cart.add_item(cart_item_type, quantity, unit_price);
             }
         });
     };
 
     // Handle adding custom pizza to cart
     let add_custom_pizza = move |(custom, quantity): (CustomPizza, u32)| {
-        let unit_price = custom.get_price();
-        let cart_item_type = CartItemType::CustomPizza { custom };
-        cart.add_item(cart_item_type, quantity, unit_price);
+        menu.with(|result| {
+            if let Some(Ok(menu_data)) = result {
+                let unit_price = custom.size.get_price(&menu_data.custom_pizza_price);
+                let cart_item_type = CartItemType::CustomPizza { custom };
+                cart.add_item(cart_item_type, quantity, unit_price);
+            }
+        });
     };
 
     view! {
@@ -52,19 +56,25 @@ pub fn MenuPage() -> impl IntoView {
 
                 <Suspense fallback=move || view! { <LoadingSpinner /> }>
                     {move || {
-                        pizzas
+                        menu
                             .get()
                             .map(|result| match result {
-                                Ok(pizza_list) => {
+                                Ok(menu_data) => {
+                                    let returning_customer = view! {
+                                        <ReturningCustomer menu=menu_data.clone() />
+                                    };
+                                    let custom_price = menu_data.custom_pizza_price;
+                                    let pizza_list = menu_data.pizzas;
                                     if pizza_list.is_empty() {
                                         view! {
                                             <div class="menu-content">
+                                                {returning_customer}
                                                 <div class="empty-state">
                                                     <h3>"No pizzas available at the moment"</h3>
                                                     <p>"We're working on restocking our menu. In the meantime, you can create a custom pizza!"</p>
                                                 </div>
                                                 <div class="custom-pizza-section">
-                                                    <CustomPizzaCard on_add_to_cart=add_custom_pizza />
+                                                    <CustomPizzaCard price=custom_price on_add_to_cart=add_custom_pizza />
                                                 </div>
                                             </div>
                                         }
@@ -72,6 +82,7 @@ pub fn MenuPage() -> impl IntoView {
                                     } else {
                                         view! {
                                             <div class="menu-content">
+                                                {returning_customer}
                                                 <div class="pizza-grid">
                                                     {pizza_list
                                                         .into_iter()
@@ -87,7 +98,7 @@ pub fn MenuPage() -> impl IntoView {
                                                 </div>
 
                                                 <div class="custom-pizza-section">
-                                                    <CustomPizzaCard on_add_to_cart=add_custom_pizza />
+                                                    <CustomPizzaCard price=custom_price on_add_to_cart=add_custom_pizza />
                                                 </div>
                                             </div>
                                         }
@@ -1,3 +1,4 @@
+pub mod admin;
 pub mod confirmation;
 pub mod menu;
 pub mod order;
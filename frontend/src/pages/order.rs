@@ -1,17 +1,26 @@
 use chrono::{DateTime, Duration, Utc};
 use leptos::{ev, prelude::*, task::spawn_local};
 use leptos_router::hooks::use_navigate;
-use shared::dto::{CreateOrderRequest, OrderItemRequest};
-use shared::models::{CustomerInfo, OrderItemType};
+use shared::dto::CreateOrderRequest;
+use shared::models::{CustomerInfo, NotificationConsent, PaymentMethod};
+use shared::validation::{
+    normalize_phone_number, validate_customer_name, validate_email, validate_pickup_time,
+};
 
 use crate::api::client::create_order;
-use crate::components::{error_display::ErrorDisplay, layout::PageLayout};
+use crate::components::{
+    custom_instructions::CustomInstructions, error_display::ErrorDisplay, layout::PageLayout,
+    phone_verification::PhoneVerification,
+};
 use crate::state::cart::{use_cart, CartItemType};
+use crate::state::quote::use_quote;
 use crate::utils::format::format_currency;
+use crate::utils::phone::{phone_region, phone_verification_required};
 
 #[component]
 pub fn OrderPage() -> impl IntoView {
     let cart = use_cart();
+    let quote = use_quote();
     let navigate = use_navigate();
 
     // Redirect to menu if cart is empty
@@ -26,6 +35,11 @@ pub fn OrderPage() -> impl IntoView {
     let (customer_phone, set_customer_phone) = signal(String::new());
     let (pickup_date, set_pickup_date) = signal(String::new());
     let (pickup_time, set_pickup_time) = signal(String::new());
+    let (payment_method, set_payment_method) = signal(PaymentMethod::PayAtPickup);
+    let (notify_sms, set_notify_sms) = signal(false);
+    let (notify_email, set_notify_email) = signal(false);
+    let (email, set_email) = signal(String::new());
+    let verification_token = RwSignal::new(None::<String>);
     let (validation_errors, set_validation_errors) = signal(Vec::<String>::new());
     let (is_submitting, set_is_submitting) = signal(false);
     let (api_error, set_api_error) = signal(None::<String>);
@@ -45,42 +59,52 @@ pub fn OrderPage() -> impl IntoView {
         }
     });
 
-    let cart_total = Memo::new(move |_| cart.total());
     let cart_items = Memo::new(move |_| cart.items());
-
-    // Validation function
-    let validate_form = move || -> Vec<String> {
-        use shared::validation::{
-            validate_customer_name, validate_phone_number, validate_pickup_time,
-        };
-
-        let mut errors = Vec::new();
-
-        // Validate customer name
-        if let Err(e) = validate_customer_name(&customer_name.get()) {
-            errors.push(e);
-        }
-
-        // Validate phone
-        if let Err(e) = validate_phone_number(&customer_phone.get()) {
-            errors.push(e);
-        }
-
-        // Validate pickup time
+    let quoted = Memo::new(move |_| quote.quote());
+    let cart_total = Memo::new(move |_| quote.total().unwrap_or_else(|| cart.total()));
+    let quote_error = Signal::derive(move || quote.error());
+
+    // Fields show their error once the customer has left them
+    let (name_touched, set_name_touched) = signal(false);
+    let (phone_touched, set_phone_touched) = signal(false);
+    let (email_touched, set_email_touched) = signal(false);
+
+    let name_error = Memo::new(move |_| validate_customer_name(&customer_name.get()).err());
+    let normalized_phone =
+        Memo::new(move |_| normalize_phone_number(&customer_phone.get(), phone_region()));
+    let phone_error = Memo::new(move |_| normalized_phone.get().err());
+    let email_error = Memo::new(move |_| {
+        notify_email
+            .get()
+            .then(|| validate_email(&email.get()).err())
+            .flatten()
+    });
+    let pickup_error = Memo::new(move |_| {
         let date = pickup_date.get();
         let time = pickup_time.get();
         if date.is_empty() || time.is_empty() {
-            errors.push("Pickup date and time are required.".to_string());
-        } else {
-            if let Ok(pickup_datetime) =
-                format!("{}T{}:00Z", date, time).parse::<DateTime<Utc>>()
-            {
-                if let Err(e) = validate_pickup_time(pickup_datetime) {
-                    errors.push(e);
-                }
-            } else {
-                errors.push("Invalid date or time format.".to_string());
-            }
+            return Some("Pickup date and time are required.".to_string());
+        }
+        match format!("{}T{}:00Z", date, time).parse::<DateTime<Utc>>() {
+            Ok(pickup_datetime) => validate_pickup_time(pickup_datetime).err(),
+            Err(_) => Some("Invalid date or time format.".to_string()),
+        }
+    });
+
+    // Validation function
+    let validate_form = move || -> Vec<String> {
+        let mut errors: Vec<String> = [
+            name_error.get(),
+            phone_error.get(),
+            email_error.get(),
+            pickup_error.get(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        if phone_verification_required() && verification_token.get().is_none() {
+            errors.push("Verify your phone number with the code we text you.".to_string());
         }
 
         // Validate cart
@@ -100,6 +124,9 @@ pub fn OrderPage() -> impl IntoView {
         set_api_error.set(None);
 
         // Validate
+        set_name_touched.set(true);
+        set_phone_touched.set(true);
+        set_email_touched.set(true);
         let errors = validate_form();
         if !errors.is_empty() {
             set_validation_errors.set(errors);
@@ -112,24 +139,10 @@ pub fn OrderPage() -> impl IntoView {
         let customer = CustomerInfo {
             name: customer_name.get().trim().to_string(),
             phone: customer_phone.get().trim().to_string(),
+            normalized_phone: normalized_phone.get().ok(),
         };
 
-        let items: Vec<OrderItemRequest> = cart_items
-            .get()
-            .into_iter()
-            .map(|cart_item| {
-                let item_type = match cart_item.cart_item_type {
-                    CartItemType::StandardPizza { pizza_id, size, .. } => {
-                        OrderItemType::StandardPizza { pizza_id, size }
-                    }
-                    CartItemType::CustomPizza { custom } => OrderItemType::CustomPizza { custom },
-                };
-                OrderItemRequest {
-                    item_type,
-                    quantity: cart_item.quantity,
-                }
-            })
-            .collect();
+        let items = cart.order_item_requests();
 
         let pickup_datetime_str = format!("{}T{}:00Z", pickup_date.get(), pickup_time.get());
         let pickup_datetime = match pickup_datetime_str.parse::<DateTime<Utc>>() {
@@ -145,6 +158,12 @@ pub fn OrderPage() -> impl IntoView {
             customer,
             items,
             pickup_time: pickup_datetime,
+            payment_method: payment_method.get(),
+            notifications: NotificationConsent {
+                sms: notify_sms.get(),
+                email: notify_email.get().then(|| email.get().trim().to_string()),
+            },
+            verification_token: verification_token.get(),
         };
 
         // Call API
@@ -153,6 +172,11 @@ pub fn OrderPage() -> impl IntoView {
                 Ok(response) => {
                     // Clear cart and navigate to confirmation
                     cart.clear();
+                    if let Some(url) = response.payment_url {
+                        // Hand over to the payment provider's checkout page
+                        let _ = window().location().set_href(&url);
+                        return;
+                    }
                     let path = format!("/confirmation/{}", response.order_id);
                     leptos_router::hooks::use_navigate()(&path, Default::default());
                 }
@@ -182,23 +206,55 @@ pub fn OrderPage() -> impl IntoView {
                 <div class="order-content">
                     <div class="cart-review">
                         <h3>"Your Order"</h3>
+                        {move || {
+                            cart.notice()
+                                .map(|notice| {
+                                    view! {
+                                        <div class="cart-notice">
+                                            <span>{notice}</span>
+                                            <button
+                                                class="cart-notice-close"
+                                                on:click=move |_| cart.dismiss_notice()
+                                                aria-label="Dismiss"
+                                            >
+                                                "×"
+                                            </button>
+                                        </div>
+                                    }
+                                })
+                        }}
                         <div class="cart-items-list">
                             {move || {
                                 cart_items
                                     .get()
                                     .into_iter()
-                                    .map(|item| {
+                                    .enumerate()
+                                    .map(|(index, item)| {
                                         let item_id_for_remove = item.id.clone();
                                         let item_id_for_update = item.id.clone();
                                         let item_quantity = item.quantity;
+                                        // Prefer the server quote, fall back to the local estimate
+                                        let (unit_price, subtotal) = match quote.line(index) {
+                                            Some(line) => (line.unit_price, line.subtotal),
+                                            None => (item.unit_price, item.subtotal()),
+                                        };
                                         view! {
                                             <div class="cart-item">
                                                 <div class="item-info">
                                                     <div class="item-name">
                                                         {item.cart_item_type.display_name()}
                                                     </div>
+                                                    {match &item.cart_item_type {
+                                                        CartItemType::CustomPizza { custom } => Some(view! {
+                                                            <CustomInstructions
+                                                                item_id=item.id.clone()
+                                                                instructions=custom.instructions.clone()
+                                                            />
+                                                        }),
+                                                        CartItemType::StandardPizza { .. } => None,
+                                                    }}
                                                     <div class="item-price">
-                                                        {format_currency(item.unit_price)}
+                                                        {format_currency(unit_price)}
                                                         " x "
                                                         {item.quantity}
                                                     </div>
@@ -214,14 +270,14 @@ pub fn OrderPage() -> impl IntoView {
                                                             if let Ok(val) = event_target_value(&ev).parse::<u32>()
                                                             {
                                                                 update_item_quantity(
-                                                                    (item_id_for_update.clone(), val.max(1).min(10)),
+                                                                    (item_id_for_update.clone(), val.clamp(1, 10)),
                                                                 );
                                                             }
                                                         }
                                                     />
 
                                                     <div class="item-subtotal">
-                                                        {format_currency(item.subtotal())}
+                                                        {format_currency(subtotal)}
                                                     </div>
                                                     <button
                                                         class="remove-button"
@@ -239,10 +295,38 @@ pub fn OrderPage() -> impl IntoView {
                                     .collect::<Vec<_>>()
                             }}
                         </div>
+                        {move || {
+                            quoted
+                                .get()
+                                .filter(|q| q.discount_total > 0.0 || q.tax_total > 0.0)
+                                .map(|q| {
+                                    view! {
+                                        <div class="cart-breakdown">
+                                            <div class="breakdown-row">
+                                                <span>"Subtotal"</span>
+                                                <span>{format_currency(q.subtotal)}</span>
+                                            </div>
+                                            {(q.discount_total > 0.0).then(|| view! {
+                                                <div class="breakdown-row">
+                                                    <span>"Discounts"</span>
+                                                    <span>"-" {format_currency(q.discount_total)}</span>
+                                                </div>
+                                            })}
+                                            {(q.tax_total > 0.0).then(|| view! {
+                                                <div class="breakdown-row">
+                                                    <span>"Tax"</span>
+                                                    <span>{format_currency(q.tax_total)}</span>
+                                                </div>
+                                            })}
+                                        </div>
+                                    }
+                                })
+                        }}
                         <div class="cart-total">
                             <strong>"Total: "</strong>
                             {move || format_currency(cart_total.get())}
                         </div>
+                        <ErrorDisplay error=quote_error />
                     </div>
 
                     <div class="customer-form-section">
@@ -269,9 +353,17 @@ pub fn OrderPage() -> impl IntoView {
                                     type="text"
                                     id="customer-name"
                                     placeholder="Enter your name"
+                                    class:invalid=move || name_touched.get() && name_error.get().is_some()
                                     prop:value=move || customer_name.get()
                                     on:input=move |ev| set_customer_name.set(event_target_value(&ev))
+                                    on:blur=move |_| set_name_touched.set(true)
                                 />
+                                {move || {
+                                    name_error
+                                        .get()
+                                        .filter(|_| name_touched.get())
+                                        .map(|error| view! { <p class="field-error">{error}</p> })
+                                }}
                             </div>
 
                             <div class="form-group">
@@ -280,9 +372,37 @@ pub fn OrderPage() -> impl IntoView {
                                     type="tel"
                                     id="customer-phone"
                                     placeholder="(555) 123-4567"
+                                    autocomplete="tel"
+                                    class:invalid=move || phone_touched.get() && phone_error.get().is_some()
                                     prop:value=move || customer_phone.get()
                                     on:input=move |ev| set_customer_phone.set(event_target_value(&ev))
+                                    on:blur=move |_| set_phone_touched.set(true)
                                 />
+                                {move || match normalized_phone.get() {
+                                    Ok(phone) => {
+                                        view! {
+                                            <p class="form-hint">{format!("We'll reach you at {}", phone)}</p>
+                                        }
+                                            .into_any()
+                                    }
+                                    Err(error) if phone_touched.get() => {
+                                        view! { <p class="field-error">{error}</p> }.into_any()
+                                    }
+                                    Err(_) => {
+                                        view! {
+                                            <p class="form-hint">
+                                                "Include the country code if your number is from abroad."
+                                            </p>
+                                        }
+                                            .into_any()
+                                    }
+                                }}
+                                <Show when=phone_verification_required>
+                                    <PhoneVerification
+                                        phone=Signal::derive(move || normalized_phone.get().ok())
+                                        token=verification_token
+                                    />
+                                </Show>
                             </div>
 
                             <div class="form-group">
@@ -303,11 +423,77 @@ pub fn OrderPage() -> impl IntoView {
                                     prop:value=move || pickup_time.get()
                                     on:input=move |ev| set_pickup_time.set(event_target_value(&ev))
                                 />
+                                {move || {
+                                    pickup_error
+                                        .get()
+                                        .map(|error| view! { <p class="field-error">{error}</p> })
+                                }}
                                 <p class="form-hint">
                                     "Orders must be placed at least 30 minutes in advance."
                                 </p>
                             </div>
 
+                            <div class="form-group">
+                                <label>"Payment *"</label>
+                                <div class="payment-options">
+                                    {[PaymentMethod::PayAtPickup, PaymentMethod::PayOnline]
+                                        .into_iter()
+                                        .map(|method| {
+                                            view! {
+                                                <label class="payment-option">
+                                                    <input
+                                                        type="radio"
+                                                        name="payment-method"
+                                                        prop:checked=move || payment_method.get() == method
+                                                        on:change=move |_| set_payment_method.set(method)
+                                                    />
+                                                    {method.as_str()}
+                                                </label>
+                                            }
+                                        })
+                                        .collect::<Vec<_>>()}
+                                </div>
+                            </div>
+
+                            <div class="form-group">
+                                <label>"Order updates"</label>
+                                <label class="notification-option">
+                                    <input
+                                        type="checkbox"
+                                        prop:checked=move || notify_sms.get()
+                                        on:change=move |ev| set_notify_sms.set(event_target_checked(&ev))
+                                    />
+                                    "Text me when my order is confirmed and ready"
+                                </label>
+                                <label class="notification-option">
+                                    <input
+                                        type="checkbox"
+                                        prop:checked=move || notify_email.get()
+                                        on:change=move |ev| set_notify_email.set(event_target_checked(&ev))
+                                    />
+                                    "Email me order updates"
+                                </label>
+                                <Show when=move || notify_email.get()>
+                                    <input
+                                        type="email"
+                                        id="customer-email"
+                                        placeholder="you@example.com"
+                                        autocomplete="email"
+                                        aria-label="Email address"
+                                        class:invalid=move || email_touched.get() && email_error.get().is_some()
+                                        prop:value=move || email.get()
+                                        on:input=move |ev| set_email.set(event_target_value(&ev))
+                                        on:blur=move |_| set_email_touched.set(true)
+                                    />
+                                    {move || {
+                                        email_error
+                                            .get()
+                                            .filter(|_| email_touched.get())
+                                            .map(|error| view! { <p class="field-error">{error}</p> })
+                                    }}
+                                </Show>
+                            </div>
+
                             <button
                                 type="submit"
                                 class="submit-order-button"
@@ -0,0 +1,81 @@
+use leptos::prelude::*;
+
+use crate::api::error::ApiError;
+
+/// Session storage key holding the admin token
+const TOKEN_STORAGE_KEY: &str = "royal_pizza_admin_token";
+
+/// Admin login state
+///
+/// The token is kept in session storage, so it survives reloads but is
+/// forgotten when the browser tab is closed.
+#[derive(Debug, Clone, Copy)]
+pub struct AdminSession {
+    token: RwSignal<Option<String>>,
+}
+
+impl AdminSession {
+    /// Create a session restored from session storage
+    pub fn new() -> Self {
+        let token = session_storage()
+            .and_then(|storage| storage.get_item(TOKEN_STORAGE_KEY).ok().flatten());
+        Self {
+            token: RwSignal::new(token),
+        }
+    }
+
+    /// Get the admin token, if logged in (reactive read)
+    pub fn token(&self) -> Option<String> {
+        self.token.get()
+    }
+
+    /// Check if an admin is logged in
+    pub fn is_logged_in(&self) -> bool {
+        self.token.with(Option::is_some)
+    }
+
+    /// Remember a token that the API accepted
+    pub fn login(&self, token: String) {
+        if let Some(storage) = session_storage() {
+            let _ = storage.set_item(TOKEN_STORAGE_KEY, &token);
+        }
+        self.token.set(Some(token));
+    }
+
+    /// Forget the token
+    pub fn logout(&self) {
+        if let Some(storage) = session_storage() {
+            let _ = storage.remove_item(TOKEN_STORAGE_KEY);
+        }
+        self.token.set(None);
+    }
+
+    /// Log out when the API rejected the token, which sends the admin to the login page
+    pub fn handle_error(&self, err: &ApiError) {
+        if err.is_unauthorized() {
+            self.logout();
+        }
+    }
+}
+
+impl Default for AdminSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn session_storage() -> Option<web_sys::Storage> {
+    window().session_storage().ok().flatten()
+}
+
+/// Provide admin session to the context
+pub fn provide_admin_session() -> AdminSession {
+    let session = AdminSession::new();
+    provide_context(session);
+    session
+}
+
+/// Use admin session from context
+pub fn use_admin_session() -> AdminSession {
+    use_context::<AdminSession>().expect("AdminSession should be provided in the app root")
+}
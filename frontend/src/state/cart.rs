@@ -1,6 +1,18 @@
-use leptos::prelude::*;
+use leptos::{ev, prelude::*, task::spawn_local};
 use serde::{Deserialize, Serialize};
-use shared::models::{CustomPizza, PizzaSize};
+use shared::dto::{GetPizzasResponse, OrderItemRequest};
+use shared::models::{CustomPizza, OrderItem, OrderItemType, PizzaSize};
+
+use crate::api::client::fetch_pizzas;
+
+/// localStorage key holding the cart
+const CART_STORAGE_KEY: &str = "royal_pizza_cart";
+
+/// Version of the stored cart format
+///
+/// Bump it when `CartItem` changes shape; carts stored in another version are
+/// dropped instead of being misread.
+const CART_SCHEMA_VERSION: u32 = 1;
 
 /// Cart item type - either standard or custom pizza
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -44,36 +56,75 @@ impl CartItem {
         self.quantity as f64 * self.unit_price
     }
 
-    /// Create a unique ID for the cart item based on its type
+    /// Convert the cart item into an order item request for the API
+    pub fn to_order_item_request(&self) -> OrderItemRequest {
+        let item_type = match &self.cart_item_type {
+            CartItemType::StandardPizza { pizza_id, size, .. } => OrderItemType::StandardPizza {
+                pizza_id: pizza_id.clone(),
+                size: *size,
+            },
+            CartItemType::CustomPizza { custom } => OrderItemType::CustomPizza {
+                custom: custom.clone(),
+            },
+        };
+        OrderItemRequest {
+            item_type,
+            quantity: self.quantity,
+        }
+    }
+
+    /// Create the ID of a cart line from its contents
+    ///
+    /// Items with the same ID are the same product and share a cart line.
+    /// Custom pizzas are identified by a hash of their instructions, so the ID
+    /// is stable across reloads and different recipes never collide on length.
     pub fn generate_id(cart_item_type: &CartItemType) -> String {
         match cart_item_type {
             CartItemType::StandardPizza { pizza_id, size, .. } => {
                 format!("{}_{:?}", pizza_id, size)
             }
             CartItemType::CustomPizza { custom } => {
-                // Use hash of instructions + size for custom pizzas
-                format!("custom_{}_{:?}",
-                    custom.instructions.len(),
-                    custom.size)
+                format!(
+                    "custom_{:?}_{:016x}",
+                    custom.size,
+                    fnv1a_hash(custom.instructions.trim())
+                )
             }
         }
     }
 }
 
+/// Cart as stored in localStorage
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredCart {
+    version: u32,
+    items: Vec<CartItem>,
+}
+
 /// Cart state manager using reactive signals
 #[derive(Debug, Clone, Copy)]
 pub struct CartState {
     items: RwSignal<Vec<CartItem>>,
+    /// Message about items changed while revalidating a stored cart
+    notice: RwSignal<Option<String>>,
 }
 
 impl CartState {
     /// Create a new cart state
     pub fn new() -> Self {
         Self {
-            items: create_rw_signal(Vec::new()),
+            items: RwSignal::new(Vec::new()),
+            notice: RwSignal::new(None),
         }
     }
 
+    /// Create a cart state restored from localStorage
+    pub fn restore() -> Self {
+        let cart = Self::new();
+        cart.items.set(load_items());
+        cart
+    }
+
     /// Add item to cart (or increment quantity if exists)
     pub fn add_item(&self, cart_item_type: CartItemType, quantity: u32, unit_price: f64) {
         self.items.update(|items: &mut Vec<CartItem>| {
@@ -101,6 +152,48 @@ impl CartState {
         });
     }
 
+    /// Change the instructions of a custom pizza in the cart
+    ///
+    /// If the new recipe matches another custom pizza of the same size, the two
+    /// lines are merged. Returns the ID of the edited line.
+    pub fn update_custom_instructions(&self, id: &str, instructions: String) -> Result<String, String> {
+        shared::validation::validate_custom_instructions(&instructions)?;
+
+        let mut result = Err("Custom pizza not found in cart.".to_string());
+        self.items.update(|items: &mut Vec<CartItem>| {
+            let Some(index) = items.iter().position(|item| item.id == id) else {
+                return;
+            };
+            let CartItemType::CustomPizza { custom } = &items[index].cart_item_type else {
+                result = Err("Only custom pizzas have instructions.".to_string());
+                return;
+            };
+
+            let cart_item_type = CartItemType::CustomPizza {
+                custom: CustomPizza {
+                    instructions,
+                    size: custom.size,
+                },
+            };
+            let new_id = CartItem::generate_id(&cart_item_type);
+
+            match items.iter().position(|item| item.id == new_id) {
+                Some(existing) if existing != index => {
+                    let quantity = items[index].quantity;
+                    items[existing].quantity += quantity;
+                    items.remove(index);
+                }
+                _ => {
+                    let item = &mut items[index];
+                    item.id = new_id.clone();
+                    item.cart_item_type = cart_item_type;
+                }
+            }
+            result = Ok(new_id);
+        });
+        result
+    }
+
     /// Update item quantity
     pub fn update_quantity(&self, id: &str, quantity: u32) {
         if quantity == 0 {
@@ -120,6 +213,13 @@ impl CartState {
         self.items.set(Vec::new());
     }
 
+    /// Build order item requests for all items (reactive read)
+    pub fn order_item_requests(&self) -> Vec<OrderItemRequest> {
+        self.items.with(|items: &Vec<CartItem>| {
+            items.iter().map(CartItem::to_order_item_request).collect()
+        })
+    }
+
     /// Calculate total price of all items
     pub fn total(&self) -> f64 {
         self.items.with(|items: &Vec<CartItem>| {
@@ -144,9 +244,103 @@ impl CartState {
         self.items.get()
     }
 
-    /// Get items as a signal
-    pub fn items_signal(&self) -> RwSignal<Vec<CartItem>> {
-        self.items
+    /// Bring stored items in line with the current menu
+    ///
+    /// Pizzas that are no longer available are removed and prices are updated
+    /// to the menu prices. A notice describing the changes is kept for display.
+    pub fn revalidate(&self, menu: &GetPizzasResponse) {
+        let mut removed = Vec::new();
+        let mut repriced = false;
+
+        self.items.update(|items: &mut Vec<CartItem>| {
+            items.retain_mut(|item| {
+                let unit_price = match &mut item.cart_item_type {
+                    CartItemType::StandardPizza {
+                        pizza_id,
+                        pizza_name,
+                        size,
+                    } => match menu.pizzas.iter().find(|pizza| pizza.id == *pizza_id) {
+                        Some(pizza) if pizza.is_available => {
+                            *pizza_name = pizza.name.clone();
+                            size.get_price(&pizza.price)
+                        }
+                        _ => {
+                            removed.push(pizza_name.clone());
+                            return false;
+                        }
+                    },
+                    CartItemType::CustomPizza { custom } => {
+                        custom.size.get_price(&menu.custom_pizza_price)
+                    }
+                };
+
+                if item.unit_price != unit_price {
+                    item.unit_price = unit_price;
+                    repriced = true;
+                }
+                true
+            });
+        });
+
+        let mut changes = Vec::new();
+        if !removed.is_empty() {
+            changes.push(format!(
+                "No longer available and removed from your cart: {}.",
+                removed.join(", ")
+            ));
+        }
+        if repriced {
+            changes.push("Some prices in your cart have changed.".to_string());
+        }
+        if !changes.is_empty() {
+            self.notice.set(Some(changes.join(" ")));
+        }
+    }
+
+    /// Add the items of a previous order at today's menu prices
+    ///
+    /// Returns the names of pizzas that are no longer available and were left out.
+    pub fn add_order_items(&self, items: &[OrderItem], menu: &GetPizzasResponse) -> Vec<String> {
+        let mut unavailable = Vec::new();
+
+        for item in items {
+            match &item.item_type {
+                OrderItemType::StandardPizza { pizza_id, size } => {
+                    match menu.pizzas.iter().find(|pizza| pizza.id == *pizza_id) {
+                        Some(pizza) if pizza.is_available => self.add_item(
+                            CartItemType::StandardPizza {
+                                pizza_id: pizza.id.clone(),
+                                pizza_name: pizza.name.clone(),
+                                size: *size,
+                            },
+                            item.quantity,
+                            size.get_price(&pizza.price),
+                        ),
+                        Some(pizza) => unavailable.push(pizza.name.clone()),
+                        None => unavailable.push(pizza_id.clone()),
+                    }
+                }
+                OrderItemType::CustomPizza { custom } => self.add_item(
+                    CartItemType::CustomPizza {
+                        custom: custom.clone(),
+                    },
+                    item.quantity,
+                    custom.size.get_price(&menu.custom_pizza_price),
+                ),
+            }
+        }
+
+        unavailable
+    }
+
+    /// Get the message about items changed by revalidation, if any
+    pub fn notice(&self) -> Option<String> {
+        self.notice.get()
+    }
+
+    /// Hide the revalidation message
+    pub fn dismiss_notice(&self) {
+        self.notice.set(None);
     }
 }
 
@@ -157,14 +351,353 @@ impl Default for CartState {
 }
 
 /// Provide cart state to the context
+///
+/// The cart is restored from localStorage, checked against the current menu,
+/// saved on every change and kept in sync with other open tabs.
 pub fn provide_cart_state() -> CartState {
-    let cart = CartState::new();
+    let cart = CartState::restore();
     provide_context(cart);
+
+    if !cart.items.with_untracked(Vec::is_empty) {
+        spawn_local(async move {
+            if let Ok(menu) = fetch_pizzas().await {
+                cart.revalidate(&menu);
+            }
+        });
+    }
+
+    Effect::new(move |_| cart.items.with(|items| save_items(items)));
+
+    // Only fires for changes made by other tabs
+    let _ = window_event_listener(ev::storage, move |event| {
+        if event.key().as_deref() != Some(CART_STORAGE_KEY) {
+            return;
+        }
+        let items = event
+            .new_value()
+            .map(|value| parse_items(&value))
+            .unwrap_or_default();
+        if cart.items.with_untracked(|current| *current != items) {
+            cart.items.set(items);
+        }
+    });
+
     cart
 }
 
+fn load_items() -> Vec<CartItem> {
+    local_storage()
+        .and_then(|storage| storage.get_item(CART_STORAGE_KEY).ok().flatten())
+        .map(|value| parse_items(&value))
+        .unwrap_or_default()
+}
+
+/// Parse a stored cart, dropping it if it is unreadable or from another version
+///
+/// Line IDs are derived again, so carts saved before a change to
+/// `CartItem::generate_id` still merge correctly with newly added items.
+fn parse_items(value: &str) -> Vec<CartItem> {
+    let stored = serde_json::from_str::<StoredCart>(value)
+        .ok()
+        .filter(|cart| cart.version == CART_SCHEMA_VERSION)
+        .map(|cart| cart.items)
+        .unwrap_or_default();
+
+    let mut items: Vec<CartItem> = Vec::with_capacity(stored.len());
+    for mut item in stored {
+        item.id = CartItem::generate_id(&item.cart_item_type);
+        match items.iter_mut().find(|existing| existing.id == item.id) {
+            Some(existing) => existing.quantity += item.quantity,
+            None => items.push(item),
+        }
+    }
+    items
+}
+
+fn save_items(items: &[CartItem]) {
+    let Some(storage) = local_storage() else {
+        return;
+    };
+    let stored = StoredCart {
+        version: CART_SCHEMA_VERSION,
+        items: items.to_vec(),
+    };
+    if let Ok(value) = serde_json::to_string(&stored) {
+        let _ = storage.set_item(CART_STORAGE_KEY, &value);
+    }
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    window().local_storage().ok().flatten()
+}
+
+/// 64-bit FNV-1a hash, stable across builds unlike `std`'s default hasher
+fn fnv1a_hash(text: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    text.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(PRIME)
+    })
+}
+
 /// Use cart state from context
 pub fn use_cart() -> CartState {
     use_context::<CartState>()
         .expect("CartState should be provided in the app root")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::models::{Pizza, PizzaPrice, TaxCategory};
+
+    fn custom(instructions: &str, size: PizzaSize) -> CartItemType {
+        CartItemType::CustomPizza {
+            custom: CustomPizza {
+                instructions: instructions.to_string(),
+                size,
+            },
+        }
+    }
+
+    fn margherita() -> CartItemType {
+        CartItemType::StandardPizza {
+            pizza_id: "margherita".to_string(),
+            pizza_name: "Margherita".to_string(),
+            size: PizzaSize::Medium,
+        }
+    }
+
+    fn menu(pizzas: Vec<Pizza>) -> GetPizzasResponse {
+        GetPizzasResponse {
+            pizzas,
+            custom_pizza_price: CustomPizza::price_table(),
+        }
+    }
+
+    fn pizza(id: &str, name: &str, medium: f64) -> Pizza {
+        Pizza {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            ingredients: Vec::new(),
+            price: PizzaPrice {
+                small: medium - 2.0,
+                medium,
+                large: medium + 2.0,
+            },
+            image_url: None,
+            image: None,
+            is_available: true,
+            tax_category: TaxCategory::default(),
+        }
+    }
+
+    #[test]
+    fn test_custom_pizzas_with_same_length_instructions_stay_separate() {
+        let cart = CartState::new();
+        cart.add_item(custom("Extra cheese, no onions", PizzaSize::Large), 1, 17.99);
+        cart.add_item(custom("Double ham and pineapple", PizzaSize::Large), 1, 17.99);
+
+        assert_eq!(cart.items().len(), 2);
+        assert_eq!(cart.item_count(), 2);
+    }
+
+    #[test]
+    fn test_identical_items_share_a_line() {
+        let cart = CartState::new();
+        cart.add_item(custom("Extra cheese, no onions", PizzaSize::Small), 1, 10.99);
+        cart.add_item(custom("Extra cheese, no onions ", PizzaSize::Small), 2, 10.99);
+        cart.add_item(margherita(), 1, 12.99);
+        cart.add_item(margherita(), 1, 12.99);
+
+        let items = cart.items();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].quantity, 3);
+        assert_eq!(items[1].quantity, 2);
+    }
+
+    #[test]
+    fn test_same_recipe_in_another_size_is_another_line() {
+        let cart = CartState::new();
+        cart.add_item(custom("Extra cheese, no onions", PizzaSize::Small), 1, 10.99);
+        cart.add_item(custom("Extra cheese, no onions", PizzaSize::Large), 1, 17.99);
+
+        assert_eq!(cart.items().len(), 2);
+    }
+
+    #[test]
+    fn test_editing_instructions_updates_the_line() {
+        let cart = CartState::new();
+        cart.add_item(custom("Extra cheese, no onions", PizzaSize::Medium), 2, 14.99);
+        let id = cart.items()[0].id.clone();
+
+        let new_id = cart
+            .update_custom_instructions(&id, "Mushrooms and olives please".to_string())
+            .unwrap();
+
+        let items = cart.items();
+        assert_ne!(new_id, id);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].id, new_id);
+        assert_eq!(items[0].quantity, 2);
+        assert_eq!(
+            items[0].cart_item_type,
+            custom("Mushrooms and olives please", PizzaSize::Medium)
+        );
+    }
+
+    #[test]
+    fn test_editing_instructions_into_an_existing_recipe_merges_lines() {
+        let cart = CartState::new();
+        cart.add_item(custom("Extra cheese, no onions", PizzaSize::Medium), 2, 14.99);
+        cart.add_item(custom("Mushrooms and olives please", PizzaSize::Medium), 1, 14.99);
+        let id = cart.items()[1].id.clone();
+
+        let new_id = cart
+            .update_custom_instructions(&id, "Extra cheese, no onions".to_string())
+            .unwrap();
+
+        let items = cart.items();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].id, new_id);
+        assert_eq!(items[0].quantity, 3);
+    }
+
+    #[test]
+    fn test_editing_instructions_validates_them() {
+        let cart = CartState::new();
+        cart.add_item(custom("Extra cheese, no onions", PizzaSize::Medium), 1, 14.99);
+        cart.add_item(margherita(), 1, 12.99);
+        let items = cart.items();
+
+        assert!(cart
+            .update_custom_instructions(&items[0].id, "short".to_string())
+            .is_err());
+        assert!(cart
+            .update_custom_instructions(&items[1].id, "Extra cheese, no onions".to_string())
+            .is_err());
+        assert!(cart
+            .update_custom_instructions("missing", "Extra cheese, no onions".to_string())
+            .is_err());
+        assert_eq!(cart.items(), items);
+    }
+
+    #[test]
+    fn test_stored_carts_are_rekeyed_and_version_checked() {
+        let mut first = CartItem {
+            id: "custom_23_Large".to_string(),
+            cart_item_type: custom("Extra cheese, no onions", PizzaSize::Large),
+            quantity: 1,
+            unit_price: 17.99,
+        };
+        let mut second = first.clone();
+        second.quantity = 2;
+        first.id = "stale".to_string();
+
+        let stored = |version| {
+            serde_json::to_string(&StoredCart {
+                version,
+                items: vec![first.clone(), second.clone()],
+            })
+            .unwrap()
+        };
+
+        let items = parse_items(&stored(CART_SCHEMA_VERSION));
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].id, CartItem::generate_id(&first.cart_item_type));
+        assert_eq!(items[0].quantity, 3);
+
+        assert!(parse_items(&stored(CART_SCHEMA_VERSION + 1)).is_empty());
+        assert!(parse_items("not json").is_empty());
+    }
+
+    #[test]
+    fn test_revalidation_removes_unavailable_pizzas_and_updates_prices() {
+        let cart = CartState::new();
+        cart.add_item(margherita(), 1, 11.99);
+        cart.add_item(
+            CartItemType::StandardPizza {
+                pizza_id: "hawaiian".to_string(),
+                pizza_name: "Hawaiian".to_string(),
+                size: PizzaSize::Medium,
+            },
+            1,
+            13.99,
+        );
+        cart.add_item(custom("Extra cheese, no onions", PizzaSize::Medium), 1, 14.99);
+
+        cart.revalidate(&menu(vec![pizza("margherita", "Margherita Classic", 12.99)]));
+
+        let items = cart.items();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].unit_price, 12.99);
+        assert_eq!(
+            items[0].cart_item_type.display_name(),
+            "Margherita Classic - Medium"
+        );
+        assert_eq!(items[1].unit_price, 14.99);
+
+        let notice = cart.notice().unwrap();
+        assert!(notice.contains("Hawaiian"));
+        assert!(notice.contains("prices"));
+    }
+
+    #[test]
+    fn test_reordering_adds_available_items_at_menu_prices() {
+        let cart = CartState::new();
+        let mut unavailable = pizza("hawaiian", "Hawaiian", 13.99);
+        unavailable.is_available = false;
+        let order_item =
+            |item_type, quantity| OrderItem::new(String::new(), item_type, quantity, 9.99);
+        let items = vec![
+            order_item(
+                OrderItemType::StandardPizza {
+                    pizza_id: "margherita".to_string(),
+                    size: PizzaSize::Medium,
+                },
+                2,
+            ),
+            order_item(
+                OrderItemType::StandardPizza {
+                    pizza_id: "hawaiian".to_string(),
+                    size: PizzaSize::Medium,
+                },
+                1,
+            ),
+            order_item(
+                OrderItemType::CustomPizza {
+                    custom: CustomPizza {
+                        instructions: "Extra cheese, no onions".to_string(),
+                        size: PizzaSize::Medium,
+                    },
+                },
+                1,
+            ),
+        ];
+
+        let skipped = cart.add_order_items(
+            &items,
+            &menu(vec![pizza("margherita", "Margherita", 12.99), unavailable]),
+        );
+
+        assert_eq!(skipped, vec!["Hawaiian".to_string()]);
+        let items = cart.items();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].quantity, 2);
+        assert_eq!(items[0].unit_price, 12.99);
+        assert_eq!(items[1].unit_price, 14.99);
+    }
+
+    #[test]
+    fn test_revalidation_without_changes_leaves_no_notice() {
+        let cart = CartState::new();
+        cart.add_item(margherita(), 1, 12.99);
+
+        cart.revalidate(&menu(vec![pizza("margherita", "Margherita", 12.99)]));
+
+        assert_eq!(cart.items().len(), 1);
+        assert_eq!(cart.notice(), None);
+    }
+}
@@ -1 +1,3 @@
+pub mod admin;
 pub mod cart;
+pub mod quote;
@@ -0,0 +1,69 @@
+use leptos::prelude::*;
+use shared::dto::{QuoteLine, QuoteRequest, QuoteResponse};
+
+use crate::api::{client::fetch_quote, error::ApiError};
+use crate::state::cart::CartState;
+
+/// Server-side quote for the current cart contents
+///
+/// The quote is refetched whenever the cart changes. Until it arrives, callers
+/// fall back to the cart's local estimate.
+#[derive(Clone, Copy)]
+pub struct QuoteState {
+    quote: LocalResource<Option<Result<QuoteResponse, ApiError>>>,
+}
+
+impl QuoteState {
+    /// Create a quote state tracking the given cart
+    pub fn new(cart: CartState) -> Self {
+        let quote = LocalResource::new(move || {
+            let items = cart.order_item_requests();
+            async move {
+                if items.is_empty() {
+                    None
+                } else {
+                    Some(fetch_quote(QuoteRequest { items }).await)
+                }
+            }
+        });
+
+        Self { quote }
+    }
+
+    /// Get the latest successful quote, if any
+    pub fn quote(&self) -> Option<QuoteResponse> {
+        self.quote.get().flatten().and_then(Result::ok)
+    }
+
+    /// Get the quoted line at the given cart position
+    pub fn line(&self, index: usize) -> Option<QuoteLine> {
+        self.quote().and_then(|quote| quote.lines.into_iter().nth(index))
+    }
+
+    /// Get the quoted total, if available
+    pub fn total(&self) -> Option<f64> {
+        self.quote().map(|quote| quote.total)
+    }
+
+    /// Get a user-facing message if the cart could not be priced
+    pub fn error(&self) -> Option<String> {
+        self.quote
+            .get()
+            .flatten()
+            .and_then(Result::err)
+            .map(|err| err.user_message())
+    }
+}
+
+/// Provide quote state to the context
+pub fn provide_quote_state(cart: CartState) -> QuoteState {
+    let quote = QuoteState::new(cart);
+    provide_context(quote);
+    quote
+}
+
+/// Use quote state from context
+pub fn use_quote() -> QuoteState {
+    use_context::<QuoteState>()
+        .expect("QuoteState should be provided in the app root")
+}
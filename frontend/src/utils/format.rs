@@ -5,6 +5,11 @@ pub fn format_currency(amount: f64) -> String {
     format!("${:.2}", amount)
 }
 
+/// Format a rate fraction as a percentage string
+pub fn format_percent(rate: f64) -> String {
+    format!("{}%", (rate * 10000.0).round() / 100.0)
+}
+
 /// Format datetime in a readable format
 pub fn format_datetime(dt: &DateTime<Utc>) -> String {
     dt.format("%b %d, %Y at %I:%M %p").to_string()
@@ -14,8 +19,3 @@ pub fn format_datetime(dt: &DateTime<Utc>) -> String {
 pub fn format_date(dt: &DateTime<Utc>) -> String {
     dt.format("%Y-%m-%d").to_string()
 }
-
-/// Format time for input field (HH:MM format)
-pub fn format_time(dt: &DateTime<Utc>) -> String {
-    dt.format("%H:%M").to_string()
-}
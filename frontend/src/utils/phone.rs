@@ -0,0 +1,19 @@
+use shared::validation::{constants::DEFAULT_PHONE_REGION, PhoneRegion};
+
+/// Region of phone numbers typed without a country code, chosen at build time
+const PHONE_REGION_CODE: &str = match option_env!("TRUNK_PHONE_DEFAULT_REGION") {
+    Some(code) => code,
+    None => DEFAULT_PHONE_REGION,
+};
+
+/// Region used to read phone numbers, matching the backend's `PHONE_DEFAULT_REGION`
+pub fn phone_region() -> &'static PhoneRegion {
+    PhoneRegion::find(PHONE_REGION_CODE)
+        .or_else(|| PhoneRegion::find(DEFAULT_PHONE_REGION))
+        .expect("the default phone region is supported")
+}
+
+/// Whether orders need a verified phone number, matching the backend's `PHONE_VERIFICATION_REQUIRED`
+pub fn phone_verification_required() -> bool {
+    option_env!("TRUNK_PHONE_VERIFICATION_REQUIRED") == Some("true")
+}
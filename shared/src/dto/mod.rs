@@ -1,7 +1,11 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::models::{CustomerInfo, OrderItemType, Pizza};
+use crate::models::{
+    AuditEvent, Customer, CustomerInfo, LineTax, NotificationConsent, Order, OrderItemType,
+    OrderStatus, PaymentMethod, PaymentStatus, Pizza, PizzaPrice, PizzaSize, PriceHistoryEntry,
+    Refund, TaxBreakdown, WebhookDelivery, WebhookEndpoint, WebhookEvent,
+};
 
 /// Request DTO for creating a new order
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +13,14 @@ pub struct CreateOrderRequest {
     pub customer: CustomerInfo,
     pub items: Vec<OrderItemRequest>,
     pub pickup_time: DateTime<Utc>,
+    #[serde(default)]
+    pub payment_method: PaymentMethod,
+    /// Opt-in to status updates; nothing is sent without it
+    #[serde(default)]
+    pub notifications: NotificationConsent,
+    /// Proof the customer owns the phone number, from `POST /api/verify/confirm`
+    #[serde(default)]
+    pub verification_token: Option<String>,
 }
 
 /// Request DTO for an individual order item
@@ -25,12 +37,339 @@ pub struct CreateOrderResponse {
     pub order_number: String,
     pub total_amount: f64,
     pub pickup_time: DateTime<Utc>,
+    pub payment_status: PaymentStatus,
+    /// Where to send the customer to complete an online payment
+    #[serde(default)]
+    pub payment_url: Option<String>,
+}
+
+/// Request DTO for pricing cart items without placing an order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteRequest {
+    pub items: Vec<OrderItemRequest>,
+}
+
+/// Priced line of a quote, in the same order as the requested items
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteLine {
+    pub item_type: OrderItemType,
+    /// Display name, `<product_name> - <size_label>`
+    pub name: String,
+    pub product_name: String,
+    pub size_label: String,
+    pub quantity: u32,
+    pub unit_price: f64,
+    pub subtotal: f64,
+    pub discount_amount: f64,
+    pub tax: LineTax,
+    pub total: f64,
+}
+
+/// Response DTO with the authoritative pricing of a set of items
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteResponse {
+    pub lines: Vec<QuoteLine>,
+    pub subtotal: f64,
+    pub discount_total: f64,
+    pub tax_total: f64,
+    pub total: f64,
+    pub tax_breakdown: TaxBreakdown,
 }
 
 /// Response DTO for fetching pizzas
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetPizzasResponse {
     pub pizzas: Vec<Pizza>,
+    pub custom_pizza_price: PizzaPrice,
+}
+
+/// Response DTO with the name and price changes of a menu pizza, oldest first
+///
+/// Empty until the pizza is first edited; its current price applies since it was added.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPriceHistoryResponse {
+    pub pizza_id: String,
+    pub entries: Vec<PriceHistoryEntry>,
+}
+
+/// Request DTO for editing a menu pizza
+///
+/// Fields left out keep their current value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdatePizzaRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub price: Option<PizzaPrice>,
+    #[serde(default)]
+    pub is_available: Option<bool>,
+}
+
+/// Response DTO with one page of orders
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetOrdersResponse {
+    pub orders: Vec<Order>,
+    /// Pass as `cursor` to fetch the next page; absent on the last page
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// Field an order list is sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderSort {
+    #[default]
+    CreatedAt,
+    PickupTime,
+}
+
+/// Direction of a sorted list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Request DTO for moving an order to another status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateOrderStatusRequest {
+    pub status: OrderStatus,
+}
+
+/// Request DTO for refunding an order
+///
+/// Without `lines` everything not refunded yet is refunded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateRefundRequest {
+    #[serde(default)]
+    pub lines: Option<Vec<RefundLineRequest>>,
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// Request DTO for refunding part of an order item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefundLineRequest {
+    pub order_item_id: String,
+    pub quantity: u32,
+}
+
+/// Response DTO listing the refunds of an order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRefundsResponse {
+    pub refunds: Vec<Refund>,
+    pub refunded_amount: f64,
+}
+
+/// Time bucket used to aggregate sales
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportGrouping {
+    #[default]
+    Day,
+    /// ISO week, e.g. 2026-W07
+    Week,
+    /// Hour of the day across all days in the range, to find peak hours
+    Hour,
+}
+
+/// Response DTO with sales aggregated per time bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SalesReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub group_by: ReportGrouping,
+    pub periods: Vec<SalesPeriod>,
+}
+
+/// Sales of a single time bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SalesPeriod {
+    pub period: String,
+    pub order_count: u64,
+    pub gross_revenue: f64,
+    pub refunded_amount: f64,
+    pub net_revenue: f64,
+    pub average_ticket: f64,
+}
+
+/// Response DTO with revenue per menu item and the custom vs standard mix
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemSalesReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub items: Vec<ItemSales>,
+    pub mix: Vec<ItemMix>,
+}
+
+/// Units sold and revenue of one pizza in one size
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemSales {
+    /// `None` for custom pizzas
+    pub pizza_id: Option<String>,
+    pub name: String,
+    pub size: PizzaSize,
+    pub quantity: u64,
+    pub revenue: f64,
+}
+
+/// Share of standard or custom pizzas in the units sold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemMix {
+    pub kind: String,
+    pub quantity: u64,
+    pub revenue: f64,
+    pub quantity_share: f64,
+}
+
+/// Response DTO with the headline figures of a date range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SalesSummary {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub order_count: u64,
+    pub gross_revenue: f64,
+    pub refunded_amount: f64,
+    pub net_revenue: f64,
+    pub average_ticket: f64,
+    /// Cancelled orders over all orders in the range, regardless of the status filter
+    pub cancellation_rate: f64,
+    pub status_counts: Vec<StatusCount>,
+}
+
+/// Number of orders in a status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusCount {
+    pub status: OrderStatus,
+    pub count: u64,
+}
+
+/// Request DTO for a returning customer looking up their previous orders
+///
+/// The caller proves they own the phone number with a verification token and
+/// names any earlier order placed with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerLookupRequest {
+    pub phone: String,
+    pub order_number: String,
+    /// Proof the customer owns the phone number, from `POST /api/verify/confirm`
+    #[serde(default)]
+    pub verification_token: Option<String>,
+}
+
+/// Response DTO with a customer and their most recent orders
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerHistoryResponse {
+    pub customer: Customer,
+    pub orders: Vec<Order>,
+}
+
+/// Response DTO for the admin customer search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetCustomersResponse {
+    pub customers: Vec<Customer>,
+}
+
+/// Request DTO asking for a one-time code sent to a phone number
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendVerificationCodeRequest {
+    pub phone: String,
+}
+
+/// Response DTO after a one-time code was sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendVerificationCodeResponse {
+    /// The phone number in E.164 format, as the code was sent to it
+    pub phone: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Request DTO confirming a phone number with the code sent to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfirmVerificationCodeRequest {
+    pub phone: String,
+    pub code: String,
+}
+
+/// Response DTO with the token proving the phone number was verified
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfirmVerificationCodeResponse {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Request DTO for registering a webhook endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Request DTO for changing a webhook endpoint; absent fields stay unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateWebhookRequest {
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub events: Option<Vec<WebhookEvent>>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub is_active: Option<bool>,
+}
+
+/// Response DTO listing webhook endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetWebhooksResponse {
+    pub webhooks: Vec<WebhookEndpoint>,
+}
+
+/// Response DTO with the most recent deliveries of a webhook endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetWebhookDeliveriesResponse {
+    pub deliveries: Vec<WebhookDelivery>,
+}
+
+/// Response DTO with audit log entries, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetAuditEventsResponse {
+    pub events: Vec<AuditEvent>,
+}
+
+/// How a restore treats records that already exist in the database
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RestoreConflictPolicy {
+    /// Only restore into tables without records
+    #[default]
+    Fail,
+    /// Keep existing records and restore the others
+    Skip,
+    /// Replace existing records with the ones from the archive
+    Overwrite,
+}
+
+/// Response DTO with what a restore did to each table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoreResponse {
+    pub tables: Vec<RestoredTable>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoredTable {
+    pub table: String,
+    /// Records written, replaced ones included
+    pub restored: usize,
+    /// Existing records kept instead of the archived ones
+    pub skipped: usize,
+    /// Existing records overwritten by archived ones
+    pub replaced: usize,
 }
 
 /// Standard error response DTO
@@ -39,6 +378,9 @@ pub struct ErrorResponse {
     pub error: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<Vec<String>>,
+    /// Id of the failed request, also sent as `X-Request-Id`, to quote to support
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 impl ErrorResponse {
@@ -47,6 +389,7 @@ impl ErrorResponse {
         Self {
             error: error.into(),
             details: None,
+            request_id: None,
         }
     }
 
@@ -55,6 +398,13 @@ impl ErrorResponse {
         Self {
             error: error.into(),
             details: Some(details),
+            request_id: None,
         }
     }
+
+    /// Tag the response with the id of the request it answers
+    pub fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
 }
@@ -0,0 +1,112 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Change recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditAction {
+    #[serde(rename = "order.created")]
+    OrderCreated,
+    #[serde(rename = "order.status_changed")]
+    OrderStatusChanged,
+    /// Payment started, paid or failed
+    #[serde(rename = "order.payment_changed")]
+    OrderPaymentChanged,
+    #[serde(rename = "order.refunded")]
+    OrderRefunded,
+    #[serde(rename = "refund.created")]
+    RefundCreated,
+    #[serde(rename = "pizza.updated")]
+    PizzaUpdated,
+    #[serde(rename = "webhook.created")]
+    WebhookCreated,
+    #[serde(rename = "webhook.updated")]
+    WebhookUpdated,
+    #[serde(rename = "webhook.deleted")]
+    WebhookDeleted,
+    #[serde(rename = "webhook_delivery.retried")]
+    WebhookDeliveryRetried,
+    #[serde(rename = "backup.restored")]
+    BackupRestored,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::OrderCreated => "order.created",
+            AuditAction::OrderStatusChanged => "order.status_changed",
+            AuditAction::OrderPaymentChanged => "order.payment_changed",
+            AuditAction::OrderRefunded => "order.refunded",
+            AuditAction::RefundCreated => "refund.created",
+            AuditAction::PizzaUpdated => "pizza.updated",
+            AuditAction::WebhookCreated => "webhook.created",
+            AuditAction::WebhookUpdated => "webhook.updated",
+            AuditAction::WebhookDeleted => "webhook.deleted",
+            AuditAction::WebhookDeliveryRetried => "webhook_delivery.retried",
+            AuditAction::BackupRestored => "backup.restored",
+        }
+    }
+
+    /// Type of the record the action changes, e.g. `order`
+    pub fn entity_type(&self) -> &'static str {
+        let action = self.as_str();
+        action.split_once('.').map_or(action, |(entity, _)| entity)
+    }
+}
+
+/// Who is behind a change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActorKind {
+    /// A request with the admin token
+    Admin,
+    /// A request to the public API, e.g. placing an order
+    Customer,
+    /// The payment provider reporting the outcome of a payment
+    PaymentProvider,
+    /// The backend itself, e.g. the command line restore
+    System,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Actor {
+    pub kind: ActorKind,
+    /// Name the admin gave in `X-Admin-Name`, or the command that acted
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl Actor {
+    pub fn new(kind: ActorKind) -> Self {
+        Self { kind, name: None }
+    }
+
+    pub fn named(kind: ActorKind, name: impl Into<String>) -> Self {
+        Self {
+            kind,
+            name: Some(name.into()),
+        }
+    }
+}
+
+/// Value of a field before and after a change; `null` where the record did not exist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+}
+
+/// Entry of the append-only audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub id: String,
+    pub actor: Actor,
+    pub action: AuditAction,
+    pub entity_type: String,
+    pub entity_id: String,
+    /// Changed fields by name
+    pub changes: BTreeMap<String, FieldChange>,
+    /// Id of the request that made the change, see `X-Request-Id`
+    #[serde(default)]
+    pub request_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Returning customer, identified by their normalized phone number
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Customer {
+    /// Phone number in E.164 format, also the record key
+    pub id: String,
+    /// Name given with the most recent order
+    pub name: String,
+    pub phone: String,
+    pub order_count: u32,
+    #[serde(default)]
+    pub last_order_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
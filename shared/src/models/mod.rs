@@ -1,7 +1,21 @@
 // Domain models for Royal Pizza
 
 pub mod pizza;
+pub mod customer;
 pub mod order;
+pub mod notification;
+pub mod payment;
+pub mod refund;
+pub mod tax;
+pub mod webhook;
+pub mod audit;
 
 pub use pizza::*;
+pub use customer::*;
 pub use order::*;
+pub use notification::*;
+pub use payment::*;
+pub use refund::*;
+pub use tax::*;
+pub use webhook::*;
+pub use audit::*;
@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How a customer is notified
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NotificationChannel {
+    Sms,
+    Email,
+}
+
+impl NotificationChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationChannel::Sms => "SMS",
+            NotificationChannel::Email => "Email",
+        }
+    }
+}
+
+/// Channels the customer opted in to when placing the order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NotificationConsent {
+    /// Text the phone number given with the order
+    #[serde(default)]
+    pub sms: bool,
+    /// Address to email; no emails are sent without one
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+impl NotificationConsent {
+    pub fn is_empty(&self) -> bool {
+        !self.sms && self.email.is_none()
+    }
+}
+
+/// Delivery state of a queued notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NotificationStatus {
+    /// Waiting for its first or next attempt
+    Pending,
+    Sent,
+    /// Given up after a permanent error or too many attempts
+    Failed,
+}
+
+/// Message in the notification outbox
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: String,
+    pub order_id: String,
+    pub channel: NotificationChannel,
+    /// Phone number in E.164 format or email address
+    pub recipient: String,
+    pub subject: String,
+    pub body: String,
+    pub status: NotificationStatus,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub sent_at: Option<DateTime<Utc>>,
+}
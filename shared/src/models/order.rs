@@ -1,7 +1,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::notification::NotificationConsent;
+use super::payment::{PaymentMethod, PaymentStatus};
 use super::pizza::{CustomPizza, PizzaSize};
+use super::tax::{LineTax, TaxBreakdown};
 
 /// Complete order information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +16,21 @@ pub struct Order {
     pub pickup_time: DateTime<Utc>,
     pub status: OrderStatus,
     pub total_amount: f64,
+    #[serde(default)]
+    pub tax_breakdown: Option<TaxBreakdown>,
+    #[serde(default)]
+    pub payment_method: PaymentMethod,
+    #[serde(default)]
+    pub payment_status: PaymentStatus,
+    /// Reference of the payment at the provider, for online payments
+    #[serde(default)]
+    pub payment_id: Option<String>,
+    /// Sum of all refunds issued for this order
+    #[serde(default)]
+    pub refunded_amount: f64,
+    /// Channels the customer wants status updates on
+    #[serde(default)]
+    pub notifications: NotificationConsent,
     pub created_at: DateTime<Utc>,
 }
 
@@ -20,7 +38,11 @@ pub struct Order {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CustomerInfo {
     pub name: String,
+    /// Phone number as the customer typed it
     pub phone: String,
+    /// E.164 form of `phone`, set by the server when the order is placed
+    #[serde(default)]
+    pub normalized_phone: Option<String>,
 }
 
 /// Individual item in an order
@@ -28,9 +50,17 @@ pub struct CustomerInfo {
 pub struct OrderItem {
     pub id: String,
     pub item_type: OrderItemType,
+    /// Menu name when the order was placed, e.g. `Margherita`
+    #[serde(default)]
+    pub product_name: Option<String>,
+    /// Size when the order was placed, e.g. `Medium`
+    #[serde(default)]
+    pub size_label: Option<String>,
     pub quantity: u32,
     pub unit_price: f64,
     pub subtotal: f64,
+    #[serde(default)]
+    pub tax: Option<LineTax>,
 }
 
 impl OrderItem {
@@ -45,15 +75,29 @@ impl OrderItem {
         Self {
             id,
             item_type,
+            product_name: None,
+            size_label: None,
             quantity,
             unit_price,
             subtotal,
+            tax: None,
         }
     }
+
+    /// Name of the item as it was ordered, e.g. `Margherita - Medium`
+    ///
+    /// `None` for orders placed before names were kept with the items.
+    pub fn ordered_name(&self) -> Option<String> {
+        let product_name = self.product_name.as_deref()?;
+        Some(match &self.size_label {
+            Some(size_label) => format!("{} - {}", product_name, size_label),
+            None => product_name.to_string(),
+        })
+    }
 }
 
 /// Type of pizza item (standard from menu or custom)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum OrderItemType {
     StandardPizza { pizza_id: String, size: PizzaSize },
@@ -72,6 +116,16 @@ pub enum OrderStatus {
 }
 
 impl OrderStatus {
+    /// All statuses in the order an order moves through them
+    pub const ALL: [OrderStatus; 6] = [
+        OrderStatus::Pending,
+        OrderStatus::Confirmed,
+        OrderStatus::Preparing,
+        OrderStatus::Ready,
+        OrderStatus::PickedUp,
+        OrderStatus::Cancelled,
+    ];
+
     /// Convert status to string for display
     pub fn as_str(&self) -> &'static str {
         match self {
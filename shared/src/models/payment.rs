@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// How the customer pays for an order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaymentMethod {
+    #[default]
+    PayAtPickup,
+    PayOnline,
+}
+
+impl PaymentMethod {
+    /// Convert method to string for display
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentMethod::PayAtPickup => "Pay at Pickup",
+            PaymentMethod::PayOnline => "Pay Online",
+        }
+    }
+}
+
+/// Payment state of an order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaymentStatus {
+    /// Paid at the counter, nothing has been collected yet
+    #[default]
+    Unpaid,
+    /// Online payment started, waiting for the provider to report back
+    Pending,
+    Paid,
+    Failed,
+    /// Some order lines were refunded after payment
+    PartiallyRefunded,
+    Refunded,
+}
+
+impl PaymentStatus {
+    /// Whether money was collected online and can be refunded
+    pub fn is_refundable(&self) -> bool {
+        matches!(self, PaymentStatus::Paid | PaymentStatus::PartiallyRefunded)
+    }
+
+    /// Convert status to string for display
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentStatus::Unpaid => "Unpaid",
+            PaymentStatus::Pending => "Pending",
+            PaymentStatus::Paid => "Paid",
+            PaymentStatus::Failed => "Failed",
+            PaymentStatus::PartiallyRefunded => "Partially Refunded",
+            PaymentStatus::Refunded => "Refunded",
+        }
+    }
+}
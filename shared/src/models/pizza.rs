@@ -1,5 +1,8 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::tax::TaxCategory;
+
 /// Represents a pizza in the menu
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pizza {
@@ -8,18 +11,94 @@ pub struct Pizza {
     pub description: String,
     pub ingredients: Vec<String>,
     pub price: PizzaPrice,
+    /// Single image URL, the largest JPEG variant for uploaded images
     pub image_url: Option<String>,
+    /// Uploaded image in several widths, for responsive `srcset`s
+    #[serde(default)]
+    pub image: Option<PizzaImage>,
     pub is_available: bool,
+    #[serde(default)]
+    pub tax_category: TaxCategory,
+}
+
+/// Uploaded photo of a pizza, resized to several widths in each format
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PizzaImage {
+    /// Narrowest first within each format
+    pub variants: Vec<ImageVariant>,
+}
+
+impl PizzaImage {
+    /// `srcset` attribute value for the variants of one format, e.g. `a.webp 320w, b.webp 640w`
+    pub fn srcset(&self, format: ImageFormat) -> String {
+        self.variants
+            .iter()
+            .filter(|variant| variant.format == format)
+            .map(|variant| format!("{} {}w", variant.url, variant.width))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Largest variant of a format
+    pub fn largest(&self, format: ImageFormat) -> Option<&ImageVariant> {
+        self.variants
+            .iter()
+            .filter(|variant| variant.format == format)
+            .max_by_key(|variant| variant.width)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageVariant {
+    pub url: String,
+    /// Where the variant is kept in the backend's image store
+    pub key: String,
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Encoding of an image variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageFormat {
+    Webp,
+    Jpeg,
+}
+
+impl ImageFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ImageFormat::Webp => "image/webp",
+            ImageFormat::Jpeg => "image/jpeg",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Webp => "webp",
+            ImageFormat::Jpeg => "jpg",
+        }
+    }
 }
 
 /// Price structure for different pizza sizes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PizzaPrice {
     pub small: f64,
     pub medium: f64,
     pub large: f64,
 }
 
+/// Name and prices of a menu pizza from `valid_from` until the next entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceHistoryEntry {
+    pub id: String,
+    pub pizza_id: String,
+    pub name: String,
+    pub price: PizzaPrice,
+    pub valid_from: DateTime<Utc>,
+}
+
 /// Pizza size enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PizzaSize {
@@ -29,6 +108,15 @@ pub enum PizzaSize {
 }
 
 impl PizzaSize {
+    /// Convert size to string for display
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PizzaSize::Small => "Small",
+            PizzaSize::Medium => "Medium",
+            PizzaSize::Large => "Large",
+        }
+    }
+
     /// Get the price for this size from a PizzaPrice struct
     pub fn get_price(&self, price: &PizzaPrice) -> f64 {
         match self {
@@ -47,12 +135,17 @@ pub struct CustomPizza {
 }
 
 impl CustomPizza {
+    /// Fixed price table for custom pizzas
+    pub fn price_table() -> PizzaPrice {
+        PizzaPrice {
+            small: 10.99,
+            medium: 14.99,
+            large: 17.99,
+        }
+    }
+
     /// Fixed pricing for custom pizzas
     pub fn get_price(&self) -> f64 {
-        match self.size {
-            PizzaSize::Small => 10.99,
-            PizzaSize::Medium => 14.99,
-            PizzaSize::Large => 17.99,
-        }
+        self.size.get_price(&Self::price_table())
     }
 }
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Money returned to the customer for (part of) an order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Refund {
+    pub id: String,
+    pub order_id: String,
+    pub lines: Vec<RefundLine>,
+    pub amount: f64,
+    pub reason: String,
+    /// Reference of the refund at the payment provider
+    pub provider_refund_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Refunded quantity of a single order item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefundLine {
+    pub order_item_id: String,
+    pub quantity: u32,
+    pub amount: f64,
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// Product category used to pick the applicable tax rate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaxCategory {
+    #[default]
+    TakeawayFood,
+    Drink,
+}
+
+impl TaxCategory {
+    /// Convert category to string for display
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaxCategory::TakeawayFood => "Takeaway Food",
+            TaxCategory::Drink => "Drinks",
+        }
+    }
+}
+
+/// Net, tax and gross amounts of a single order line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineTax {
+    pub category: TaxCategory,
+    pub rate: f64,
+    pub net: f64,
+    pub tax: f64,
+    pub gross: f64,
+}
+
+/// Totals for all lines sharing the same category and rate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaxRateTotal {
+    pub category: TaxCategory,
+    pub rate: f64,
+    pub net: f64,
+    pub tax: f64,
+    pub gross: f64,
+}
+
+/// Tax breakdown of a complete order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaxBreakdown {
+    pub prices_include_tax: bool,
+    pub rates: Vec<TaxRateTotal>,
+    pub net_total: f64,
+    pub tax_total: f64,
+    pub gross_total: f64,
+}
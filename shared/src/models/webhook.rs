@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Order event a webhook endpoint can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebhookEvent {
+    #[serde(rename = "order.created")]
+    OrderCreated,
+    #[serde(rename = "order.status_changed")]
+    OrderStatusChanged,
+    /// Sent next to `order.status_changed` when an order is cancelled
+    #[serde(rename = "order.cancelled")]
+    OrderCancelled,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 3] = [
+        WebhookEvent::OrderCreated,
+        WebhookEvent::OrderStatusChanged,
+        WebhookEvent::OrderCancelled,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::OrderCreated => "order.created",
+            WebhookEvent::OrderStatusChanged => "order.status_changed",
+            WebhookEvent::OrderCancelled => "order.cancelled",
+        }
+    }
+}
+
+/// URL that order events are posted to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEndpoint {
+    pub id: String,
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    /// Key the payloads are signed with, shared with the receiver
+    pub secret: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookEndpoint {
+    pub fn is_subscribed(&self, event: WebhookEvent) -> bool {
+        self.is_active && self.events.contains(&event)
+    }
+}
+
+/// Delivery state of an event to an endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for its first or next attempt
+    Pending,
+    Delivered,
+    /// Given up after too many failed attempts; can be retried manually
+    DeadLetter,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookDeliveryStatus::Pending => "Pending",
+            WebhookDeliveryStatus::Delivered => "Delivered",
+            WebhookDeliveryStatus::DeadLetter => "Dead letter",
+        }
+    }
+}
+
+/// One event queued for one endpoint, with the outcome of its attempts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub endpoint_id: String,
+    pub event: WebhookEvent,
+    pub order_id: String,
+    /// JSON body exactly as signed and sent
+    pub payload: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    /// HTTP status of the last response, if the endpoint answered
+    #[serde(default)]
+    pub last_status_code: Option<u16>,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub delivered_at: Option<DateTime<Utc>>,
+}
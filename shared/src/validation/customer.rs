@@ -1,4 +1,5 @@
-use crate::validation::constants::{MAX_NAME_LENGTH, MIN_NAME_LENGTH};
+use crate::validation::constants::{MAX_EMAIL_LENGTH, MAX_NAME_LENGTH, MIN_NAME_LENGTH};
+use crate::validation::phone::{normalize_phone_number, PhoneRegion};
 
 pub fn validate_customer_name(name: &str) -> Result<(), String> {
     let trimmed = name.trim();
@@ -24,9 +25,39 @@ pub fn validate_customer_name(name: &str) -> Result<(), String> {
     Ok(())
 }
 
-pub fn validate_phone_number(phone: &str) -> Result<(), String> {
-    if phone.trim().is_empty() {
-        return Err("Phone number is required.".to_string());
+/// Checks that a phone number can be normalized to E.164
+pub fn validate_phone_number(phone: &str, default_region: &PhoneRegion) -> Result<(), String> {
+    normalize_phone_number(phone, default_region).map(|_| ())
+}
+
+/// Checks the shape of an email address: something before and after the `@`
+/// and a dot in the domain
+pub fn validate_email(email: &str) -> Result<(), String> {
+    let trimmed = email.trim();
+    if trimmed.is_empty() {
+        return Err("Email address is required.".to_string());
     }
+    if trimmed.len() > MAX_EMAIL_LENGTH {
+        return Err(format!(
+            "Email address cannot exceed {} characters.",
+            MAX_EMAIL_LENGTH
+        ));
+    }
+
+    let valid = match trimmed.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !trimmed.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+    if !valid {
+        return Err("Email address is not valid.".to_string());
+    }
+
     Ok(())
 }
@@ -1,15 +1,26 @@
 // Validation logic shared between frontend and backend
 pub mod customer;
 pub mod order;
+pub mod phone;
 pub mod pickup_time;
+pub mod pizza;
+pub mod webhook;
 
-pub use customer::{validate_customer_name, validate_phone_number};
+pub use customer::{validate_customer_name, validate_email, validate_phone_number};
 pub use order::validate_order_items;
+pub use phone::{normalize_phone_number, PhoneRegion, PHONE_REGIONS};
 pub use pickup_time::validate_pickup_time;
+pub use pizza::{validate_custom_instructions, validate_pizza_name, validate_pizza_price};
+pub use webhook::{validate_webhook_events, validate_webhook_url};
 
 pub mod constants {
     pub const MIN_NAME_LENGTH: usize = 2;
     pub const MAX_NAME_LENGTH: usize = 100;
+    pub const MAX_EMAIL_LENGTH: usize = 254;
     pub const MIN_PICKUP_LEAD_TIME_MINUTES: i64 = 30;
     pub const MIN_ORDER_ITEMS: usize = 1;
+    pub const MIN_INSTRUCTIONS_LENGTH: usize = 10;
+    pub const MAX_INSTRUCTIONS_LENGTH: usize = 500;
+    /// Region of phone numbers entered without a country code, unless configured otherwise
+    pub const DEFAULT_PHONE_REGION: &str = "US";
 }
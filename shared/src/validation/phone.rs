@@ -0,0 +1,146 @@
+/// Numbering plan of a country, enough to turn national numbers into E.164
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhoneRegion {
+    /// ISO 3166-1 alpha-2 code, e.g. `US`
+    pub code: &'static str,
+    /// Country calling code without the `+`
+    pub calling_code: &'static str,
+    /// Prefix dialled before national numbers and dropped in E.164
+    pub trunk_prefix: &'static str,
+    /// Allowed lengths of the national significant number
+    pub min_length: usize,
+    pub max_length: usize,
+}
+
+impl PhoneRegion {
+    /// Look up a supported region by its ISO code, ignoring case
+    pub fn find(code: &str) -> Option<&'static PhoneRegion> {
+        PHONE_REGIONS
+            .iter()
+            .find(|region| region.code.eq_ignore_ascii_case(code.trim()))
+    }
+}
+
+/// Regions national numbers can be entered for
+///
+/// Numbers in international format are accepted for any country; for the
+/// regions listed here their length is checked as well.
+pub const PHONE_REGIONS: &[PhoneRegion] = &[
+    region("US", "1", "1", 10, 10),
+    region("CA", "1", "1", 10, 10),
+    region("GB", "44", "0", 9, 10),
+    region("IE", "353", "0", 7, 9),
+    region("NL", "31", "0", 9, 9),
+    region("BE", "32", "0", 8, 9),
+    region("DE", "49", "0", 6, 13),
+    region("AT", "43", "0", 4, 13),
+    region("CH", "41", "0", 9, 9),
+    region("FR", "33", "0", 9, 9),
+    region("ES", "34", "", 9, 9),
+    region("IT", "39", "", 6, 11),
+    region("AU", "61", "0", 9, 9),
+];
+
+const fn region(
+    code: &'static str,
+    calling_code: &'static str,
+    trunk_prefix: &'static str,
+    min_length: usize,
+    max_length: usize,
+) -> PhoneRegion {
+    PhoneRegion {
+        code,
+        calling_code,
+        trunk_prefix,
+        min_length,
+        max_length,
+    }
+}
+
+/// Most digits an E.164 number can have, country code included
+const MAX_E164_DIGITS: usize = 15;
+/// Fewest digits accepted for a number in an unlisted country
+const MIN_E164_DIGITS: usize = 8;
+
+/// Parses a phone number as typed by a customer into E.164, e.g. `+15555550100`
+///
+/// Numbers starting with `+` or `00` are international; anything else is a
+/// national number of `default_region`. Spaces, dashes, dots, slashes and
+/// parentheses are ignored.
+pub fn normalize_phone_number(phone: &str, default_region: &PhoneRegion) -> Result<String, String> {
+    let trimmed = phone.trim();
+    if trimmed.is_empty() {
+        return Err("Phone number is required.".to_string());
+    }
+
+    let (international, rest) = match trimmed.strip_prefix('+') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+
+    let mut digits = String::with_capacity(rest.len());
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => digits.push(c),
+            ' ' | '-' | '.' | '/' | '(' | ')' => {}
+            _ => {
+                return Err(
+                    "Phone number may only contain digits, spaces, dashes and parentheses."
+                        .to_string(),
+                )
+            }
+        }
+    }
+
+    if digits.is_empty() {
+        return Err("Phone number is required.".to_string());
+    }
+
+    let international_digits = if international {
+        Some(digits.as_str())
+    } else {
+        digits.strip_prefix("00")
+    };
+
+    let (calling_code, national, region) = match international_digits {
+        Some(digits) => {
+            if digits.starts_with('0') {
+                return Err("Phone number has an invalid country code.".to_string());
+            }
+            match PHONE_REGIONS
+                .iter()
+                .find(|region| digits.starts_with(region.calling_code))
+            {
+                Some(region) => (
+                    region.calling_code,
+                    &digits[region.calling_code.len()..],
+                    Some(region),
+                ),
+                None => ("", digits, None),
+            }
+        }
+        None => {
+            let national = if default_region.trunk_prefix.is_empty() {
+                digits.as_str()
+            } else {
+                digits
+                    .strip_prefix(default_region.trunk_prefix)
+                    .unwrap_or(&digits)
+            };
+            (default_region.calling_code, national, Some(default_region))
+        }
+    };
+
+    let (min_length, max_length) = match region {
+        Some(region) => (region.min_length, region.max_length),
+        None => (MIN_E164_DIGITS, MAX_E164_DIGITS),
+    };
+    if national.len() < min_length {
+        return Err("Phone number is too short.".to_string());
+    }
+    if national.len() > max_length || calling_code.len() + national.len() > MAX_E164_DIGITS {
+        return Err("Phone number is too long.".to_string());
+    }
+
+    Ok(format!("+{}{}", calling_code, national))
+}
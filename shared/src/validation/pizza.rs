@@ -0,0 +1,47 @@
+use crate::models::PizzaPrice;
+use crate::validation::constants::{MAX_INSTRUCTIONS_LENGTH, MIN_INSTRUCTIONS_LENGTH};
+
+pub fn validate_pizza_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Pizza name is required.".to_string());
+    }
+    Ok(())
+}
+
+pub fn validate_pizza_price(price: &PizzaPrice) -> Result<(), String> {
+    let prices = [
+        ("Small", price.small),
+        ("Medium", price.medium),
+        ("Large", price.large),
+    ];
+
+    for (size, amount) in prices {
+        if !amount.is_finite() || amount < 0.0 {
+            return Err(format!("{} price must be a positive amount.", size));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn validate_custom_instructions(instructions: &str) -> Result<(), String> {
+    if instructions.trim().is_empty() {
+        return Err("Please provide instructions for your custom pizza.".to_string());
+    }
+
+    if instructions.len() < MIN_INSTRUCTIONS_LENGTH {
+        return Err(format!(
+            "Instructions must be at least {} characters.",
+            MIN_INSTRUCTIONS_LENGTH
+        ));
+    }
+
+    if instructions.len() > MAX_INSTRUCTIONS_LENGTH {
+        return Err(format!(
+            "Instructions cannot exceed {} characters.",
+            MAX_INSTRUCTIONS_LENGTH
+        ));
+    }
+
+    Ok(())
+}
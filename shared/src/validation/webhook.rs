@@ -0,0 +1,22 @@
+use crate::models::WebhookEvent;
+
+pub fn validate_webhook_url(url: &str) -> Result<(), String> {
+    let url = url.trim();
+    let host = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| "Webhook URL must start with http:// or https://.".to_string())?;
+
+    if host.is_empty() || host.starts_with('/') || url.chars().any(char::is_whitespace) {
+        return Err("Webhook URL is not valid.".to_string());
+    }
+
+    Ok(())
+}
+
+pub fn validate_webhook_events(events: &[WebhookEvent]) -> Result<(), String> {
+    if events.is_empty() {
+        return Err("Subscribe the webhook to at least one event.".to_string());
+    }
+    Ok(())
+}